@@ -0,0 +1,367 @@
+//! Compares the dense `ObjectStore` and packed indexes of `Scene` with the
+//! previous `FxHashMap<Id, Box<dyn Object>>` + `FxHashSet` index layout.
+//!
+//! Run with `cargo bench -p engine --bench scene`, add `--features parallel`
+//! to also compare the sequential and parallel tick.
+
+use rustc_hash::{FxHashMap, FxHashSet};
+use std::collections::VecDeque;
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+use engine::prelude::*;
+
+const SNAKE_COUNTS: [usize; 3] = [50, 200, 500];
+const SNAKE_LENGTH: usize = 24;
+const TICKS: u32 = 500;
+
+/// A snake-like movable which slithers around a square track
+#[derive(Debug)]
+struct Worm {
+    id: Id,
+    body: VecDeque<TCell>,
+    step: u16,
+    state: State,
+}
+
+impl Worm {
+    fn new(id: Id, origin: Position) -> Self {
+        let glyph = Glyph::new(None, None, 'o');
+        let body = (0..SNAKE_LENGTH)
+            .map(|index| {
+                let pos = Position::new(origin.x + index as u16, origin.y);
+                TCell::new(
+                    Occupant::new(id, Id::new(index as u64)),
+                    glyph,
+                    Some(pos),
+                    0,
+                )
+            })
+            .collect();
+
+        Self {
+            id,
+            body,
+            step: 0,
+            state: State::new(),
+        }
+    }
+
+    fn next_head(&self) -> Position {
+        let head = self
+            .body
+            .back()
+            .map(|t_cell| t_cell.pos)
+            .unwrap_or_default();
+        match (self.step / 8) % 4 {
+            0 => Position::new(head.x + 1, head.y),
+            1 => Position::new(head.x, head.y + 1),
+            2 => Position::new(head.x.saturating_sub(1), head.y),
+            _ => Position::new(head.x, head.y.saturating_sub(1)),
+        }
+    }
+
+    fn get_t_cells(&self) -> Box<dyn Iterator<Item = &TCell> + '_> {
+        Box::new(self.body.iter())
+    }
+}
+
+define_object! {
+    struct Worm,
+    id_field: id,
+    t_cells: custom(get_t_cells),
+    capabilities: {
+        Stateful { state_field: state }
+        Destructible {}
+        Spatial {}
+        Movable {
+            impl {
+                fn probe_move(&self) -> Box<dyn Iterator<Item = Position> + '_> {
+                    Box::new(std::iter::once(self.next_head()))
+                }
+
+                fn make_move(&mut self, _probe: Option<Vec<CellRef>>) -> Vec<Box<dyn Event>> {
+                    let next = self.next_head();
+                    if let Some(mut tail) = self.body.pop_front() {
+                        let init_pos = tail.pos;
+                        tail.pos = next;
+                        self.state.upsert_change(StateChange::Update { t_cell: tail, init_pos });
+                        self.body.push_back(tail);
+                    }
+                    self.step = self.step.wrapping_add(1);
+                    Vec::new()
+                }
+            }
+        }
+    }
+}
+
+fn worm_origin(index: usize) -> Position {
+    Position::new(10 + (index % 20) as u16 * 40, 10 + (index / 20) as u16 * 12)
+}
+
+// Packs the worms into a 250x250 grid, the tracks overlap past 168 worms
+#[cfg(feature = "parallel")]
+fn grid_origin(index: usize) -> Position {
+    Position::new(10 + (index % 6) as u16 * 36, 10 + (index / 6 % 28) as u16 * 8)
+}
+
+fn spawn_worms(count: usize) -> Vec<(Id, Box<dyn Object>)> {
+    let mut id_counter = IdCounter::new();
+    (0..count)
+        .map(|index| {
+            let id = id_counter.next();
+            (id, Box::new(Worm::new(id, worm_origin(index))) as Box<dyn Object>)
+        })
+        .collect()
+}
+
+/// The storage layout before `ObjectStore`
+struct HashMapScene {
+    objects: FxHashMap<Id, Box<dyn Object>>,
+    indexes: FxHashMap<ObjectIndex, FxHashSet<Id>>,
+}
+
+impl HashMapScene {
+    fn new(count: usize) -> Self {
+        let mut objects = FxHashMap::default();
+        let mut indexes: FxHashMap<ObjectIndex, FxHashSet<Id>> = FxHashMap::default();
+        for (id, object) in spawn_worms(count) {
+            for index in [ObjectIndex::Stateful, ObjectIndex::Movable] {
+                indexes.entry(index).or_default().insert(id);
+            }
+            objects.insert(id, object);
+        }
+        Self { objects, indexes }
+    }
+
+    fn tick(&mut self) -> usize {
+        let mut work = 0;
+        if let Some(ids) = self.indexes.get(&ObjectIndex::Movable) {
+            for id in ids {
+                if let Some(movable) = self
+                    .objects
+                    .get_mut(id)
+                    .and_then(|obj| obj.as_movable_mut())
+                {
+                    work += movable.probe_move().count();
+                    work += movable.make_move(None).len();
+                }
+            }
+        }
+        if let Some(ids) = self.indexes.get(&ObjectIndex::Stateful) {
+            for id in ids {
+                if let Some(stateful) = self
+                    .objects
+                    .get_mut(id)
+                    .and_then(|obj| obj.as_stateful_mut())
+                {
+                    work += stateful.state_mut().drain_changes().len();
+                }
+            }
+        }
+        work
+    }
+}
+
+struct StoreScene {
+    scene: Scene,
+}
+
+impl StoreScene {
+    fn new(count: usize) -> Self {
+        let mut scene = Scene::new();
+        for index in 0..count {
+            scene.attach_object(
+                |id| Box::new(Worm::new(id, worm_origin(index))),
+                Conflict::Ignore,
+            );
+        }
+        // Drops the init changes
+        scene.sync();
+        Self { scene }
+    }
+
+    fn move_all(&mut self) -> usize {
+        let scene = &mut self.scene;
+        let mut work = 0;
+        if let Some(ids) = scene.indexes.get(&ObjectIndex::Movable) {
+            for id in ids {
+                if let Some(movable) = scene
+                    .objects
+                    .get_mut(id)
+                    .and_then(|obj| obj.as_movable_mut())
+                {
+                    work += movable.probe_move().count();
+                    work += movable.make_move(None).len();
+                }
+            }
+        }
+        work
+    }
+
+    fn tick(&mut self) -> usize {
+        let mut work = self.move_all();
+        let scene = &mut self.scene;
+        if let Some(ids) = scene.indexes.get(&ObjectIndex::Stateful) {
+            for id in ids {
+                if let Some(stateful) = scene
+                    .objects
+                    .get_mut(id)
+                    .and_then(|obj| obj.as_stateful_mut())
+                {
+                    work += stateful.state_mut().drain_changes().len();
+                }
+            }
+        }
+        work
+    }
+}
+
+/// Spatial, object id value, t_cell id value, position and initial position
+#[cfg(feature = "parallel")]
+type ChangeKey = (bool, u64, u64, Option<(u16, u16)>, Option<(u16, u16)>);
+
+/// A scene with a grid, so moves go through `Scene::move_objects`
+#[cfg(feature = "parallel")]
+struct GridScene {
+    scene: Scene,
+}
+
+#[cfg(feature = "parallel")]
+impl GridScene {
+    fn new(count: usize, parallel: bool) -> Self {
+        let mut scene = Scene::new();
+        scene.set_parallel(parallel);
+        scene.attach_grid(SpatialGrid::new(
+            250,
+            250,
+            None,
+            Position::new(0, 0),
+            |_| Terrain::new(Glyph::new(None, None, ' '), 0),
+        ));
+        for index in 0..count {
+            scene.attach_object(
+                |id| Box::new(Worm::new(id, grid_origin(index))),
+                Conflict::Ignore,
+            );
+        }
+        scene.sync();
+        Self { scene }
+    }
+
+    fn tick(&mut self) -> usize {
+        let work = self.scene.move_objects().len();
+        self.scene.sync();
+        work + self.scene.global_state.filtered.spatial.len()
+    }
+
+    /// The synced changes without id generations, which differ between scenes
+    fn changes(&self) -> Vec<ChangeKey> {
+        let filtered = &self.scene.global_state.filtered;
+        let spatial = filtered.spatial.iter().map(|change| (true, change));
+        let non_spatial = filtered.non_spatial.iter().map(|change| (false, change));
+
+        let mut changes: Vec<_> = spatial
+            .chain(non_spatial)
+            .map(|(is_spatial, change)| {
+                let xy = |pos: Position| (pos.x, pos.y);
+                let (occ, pos, init_pos) = match change {
+                    StateChange::Create { new_t_cell } => {
+                        (new_t_cell.occ, Some(xy(new_t_cell.pos)), None)
+                    }
+                    StateChange::Update { t_cell, init_pos } => {
+                        (t_cell.occ, Some(xy(t_cell.pos)), Some(xy(*init_pos)))
+                    }
+                    StateChange::Delete { occupant, init_pos } => {
+                        (*occupant, None, Some(xy(*init_pos)))
+                    }
+                };
+                (is_spatial, occ.obj_id.value, occ.t_cell_id.value, pos, init_pos)
+            })
+            .collect();
+        changes.sort_unstable();
+        changes
+    }
+}
+
+#[cfg(feature = "parallel")]
+fn compare_parallel() {
+    println!(
+        "{:>8} {:>16} {:>16} {:>8}",
+        "snakes", "sequential", "parallel", "speedup"
+    );
+    for count in SNAKE_COUNTS {
+        let mut sequential = GridScene::new(count, false);
+        let mut parallel = GridScene::new(count, true);
+
+        // Both paths have to produce the exact same changes
+        for _ in 0..TICKS / 10 {
+            sequential.tick();
+            parallel.tick();
+            assert_eq!(sequential.changes(), parallel.changes());
+        }
+
+        let sequential_tick = measure(|| sequential.tick());
+        let parallel_tick = measure(|| parallel.tick());
+        println!(
+            "{:>8} {:>16.2?} {:>16.2?} {:>7.2}x",
+            count,
+            sequential_tick,
+            parallel_tick,
+            sequential_tick.as_secs_f64() / parallel_tick.as_secs_f64()
+        );
+    }
+}
+
+fn measure(mut tick: impl FnMut() -> usize) -> Duration {
+    // Warm up
+    for _ in 0..TICKS / 10 {
+        black_box(tick());
+    }
+
+    let start = Instant::now();
+    for _ in 0..TICKS {
+        black_box(tick());
+    }
+    start.elapsed() / TICKS
+}
+
+fn main() {
+    println!(
+        "{:>8} {:>16} {:>16} {:>8}",
+        "snakes", "hash map", "object store", "speedup"
+    );
+    for count in SNAKE_COUNTS {
+        let mut hash_map = HashMapScene::new(count);
+        let mut store = StoreScene::new(count);
+
+        let hash_map_tick = measure(|| hash_map.tick());
+        let store_tick = measure(|| store.tick());
+
+        println!(
+            "{:>8} {:>16.2?} {:>16.2?} {:>7.2}x",
+            count,
+            hash_map_tick,
+            store_tick,
+            hash_map_tick.as_secs_f64() / store_tick.as_secs_f64()
+        );
+    }
+
+    // Moves followed by a full sync, which drains and processes every change
+    for count in SNAKE_COUNTS {
+        let mut store = StoreScene::new(count);
+        let sync_tick = measure(|| {
+            let work = store.move_all();
+            store.scene.sync();
+            work
+        });
+        println!(
+            "{:>8} snakes: moves + Scene::sync {:>12.2?}",
+            count, sync_tick
+        );
+    }
+
+    #[cfg(feature = "parallel")]
+    compare_parallel();
+}
@@ -0,0 +1,68 @@
+//! Converts a PNG or GIF into the plain-text sprite format.
+//!
+//! Usage: asset-gen <input> [--mode half|ascii|braille] [--width <columns>] [--output <file>]
+
+use std::process::exit;
+
+use engine::core::asset::decode::load_frames;
+use engine::prelude::RenderMode;
+
+const DEFAULT_WIDTH: u16 = 40;
+
+fn usage() -> ! {
+    eprintln!(
+        "Usage: asset-gen <input.png|input.gif> [--mode half|ascii|braille] [--width <columns>] [--output <file>]"
+    );
+    exit(1);
+}
+
+fn main() {
+    let mut args = std::env::args().skip(1);
+    let mut input = None;
+    let mut output = None;
+    let mut mode = RenderMode::HalfBlock;
+    let mut width = DEFAULT_WIDTH;
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--mode" => {
+                mode = match args.next().as_deref() {
+                    Some("half") => RenderMode::HalfBlock,
+                    Some("ascii") => RenderMode::Ascii,
+                    Some("braille") => RenderMode::Braille,
+                    _ => usage(),
+                }
+            }
+            "--width" => {
+                width = match args.next().and_then(|value| value.parse().ok()) {
+                    Some(width) => width,
+                    None => usage(),
+                }
+            }
+            "--output" => output = Some(args.next().unwrap_or_else(|| usage())),
+            _ if input.is_none() => input = Some(arg),
+            _ => usage(),
+        }
+    }
+
+    let Some(input) = input else { usage() };
+
+    let frames = match load_frames(&input, mode, width) {
+        Ok(frames) => frames,
+        Err(err) => {
+            eprintln!("{}", err);
+            exit(1);
+        }
+    };
+
+    let text = frames.to_text();
+    match output {
+        Some(path) => {
+            if let Err(err) = std::fs::write(&path, text) {
+                eprintln!("Could not write {}: {}", path, err);
+                exit(1);
+            }
+        }
+        None => print!("{}", text),
+    }
+}
@@ -1,17 +1,28 @@
 use rustc_hash::FxHashMap;
 use std::hash::Hash;
+use std::io;
 use std::time::Duration;
 
+pub mod asset;
+pub mod console;
 pub mod event;
 pub mod global;
+pub mod inspector;
+pub mod profiler;
+pub mod resources;
 pub mod runtime;
+pub mod ui;
 
-use runtime::Runtime;
+use resources::Resources;
+use runtime::{Runtime, StageEntry};
 
-use crate::prelude::Stage;
+use crate::prelude::{LoadStatus, SceneLoader, Stage};
 
 pub enum ManagerDirective<K: Eq + Hash + Clone> {
     Switch(K),
+    Push(K),
+    Pop,
+    Load(SceneLoader, K),
     Refresh,
     Kill,
 }
@@ -20,6 +31,11 @@ pub struct RuntimeManager<K: Eq + Hash + Clone> {
     runtime: Runtime,
     stages: FxHashMap<K, Stage<K>>,
     active_key: Option<K>,
+    // Suspended stages below the active one, the bottom first
+    stack: Vec<K>,
+    resources: Resources,
+    // The stage waiting for its scene and the loader preparing it
+    pending_load: Option<(K, SceneLoader)>,
 }
 
 impl<K: Eq + Hash + Clone> RuntimeManager<K> {
@@ -28,6 +44,9 @@ impl<K: Eq + Hash + Clone> RuntimeManager<K> {
             runtime: Runtime::new(tick_rate),
             stages: FxHashMap::default(),
             active_key: None,
+            stack: Vec::new(),
+            resources: Resources::new(),
+            pending_load: None,
         }
     }
 
@@ -42,7 +61,75 @@ impl<K: Eq + Hash + Clone> RuntimeManager<K> {
         self.active_key = Some(key);
     }
 
-    pub fn run_app(&mut self) {
+    pub fn resources(&self) -> &Resources {
+        &self.resources
+    }
+
+    /// Resources inserted here are reachable from every stage through `scene.resources`
+    pub fn resources_mut(&mut self) -> &mut Resources {
+        &mut self.resources
+    }
+
+    /// Keys of the stages, from the bottom of the stack up to the active one
+    pub fn stack(&self) -> impl Iterator<Item = &K> {
+        self.stack.iter().chain(self.active_key.as_ref())
+    }
+
+    // Swaps the prepared scene in like `ReplaceScene`, an unfinished load is cancelled
+    fn finish_load(&mut self, key: &K) {
+        if self.pending_load.as_ref().is_none_or(|(load_key, _)| load_key != key) {
+            return;
+        }
+        let Some((_, mut loader)) = self.pending_load.take() else {
+            return;
+        };
+        self.resources.remove::<LoadStatus>();
+
+        let Some(scene) = loader.poll() else {
+            loader.cancel();
+            return;
+        };
+        if let Some(stage) = self.stages.get_mut(key) {
+            let old_scene = stage.replace_scene(scene);
+            stage.logic.collect_old_stage(Some(old_scene), None);
+        }
+    }
+
+    // The load's stage is left (or a newer load replaces it), its scene is never used
+    fn cancel_load(&mut self) {
+        if let Some((_, loader)) = self.pending_load.take() {
+            loader.cancel();
+        }
+        self.resources.remove::<LoadStatus>();
+    }
+
+    // A switch leaves the suspended stages behind, they exit from the top down
+    fn exit_stack(&mut self) {
+        while let Some(key) = self.stack.pop() {
+            if let Some(stage) = self.stages.get_mut(&key) {
+                std::mem::swap(&mut self.resources, &mut stage.scene.resources);
+                stage.logic.on_exit(&mut stage.scene);
+                std::mem::swap(&mut self.resources, &mut stage.scene.resources);
+            }
+        }
+        self.runtime.renderer.clear_backdrop();
+    }
+
+    // Only the suspended stages which keep rendering are drawn underneath
+    fn update_backdrop(&mut self) {
+        let scenes = self
+            .stack
+            .iter()
+            .filter_map(|key| self.stages.get(key))
+            .filter(|stage| stage.keep_rendering)
+            .map(|stage| stage.scene.as_ref());
+        self.runtime.renderer.set_backdrop(scenes);
+    }
+
+    /// Runs the active stage until one kills the app, then saves the persistent
+    /// resources and returns the error if they couldn't be saved
+    pub fn run_app(&mut self) -> io::Result<()> {
+        let mut entry = StageEntry::Enter;
         loop {
             if let Some(active_key) = self.active_key.clone() {
                 let mut active_stage = self
@@ -50,18 +137,72 @@ impl<K: Eq + Hash + Clone> RuntimeManager<K> {
                     .remove(&active_key)
                     .expect("Active stage does not exists!");
 
-                let directive = self.runtime.run(&mut active_stage);
+                // Lent to the running stage only
+                std::mem::swap(&mut self.resources, &mut active_stage.scene.resources);
+                let directive = self.runtime.run(&mut active_stage, entry);
+
+                let scene = &mut active_stage.scene;
+                match &directive {
+                    ManagerDirective::Kill => active_stage.logic.on_exit(scene),
+                    ManagerDirective::Switch(_) => {
+                        active_stage.logic.on_exit(scene);
+                        self.runtime.renderer.capture_outgoing(scene);
+                    }
+                    ManagerDirective::Pop if !self.stack.is_empty() => {
+                        active_stage.logic.on_exit(scene);
+                        self.runtime.renderer.capture_outgoing(scene);
+                    }
+                    ManagerDirective::Push(_) | ManagerDirective::Load(..) => {
+                        active_stage.logic.on_suspend(scene);
+                        self.runtime.renderer.capture_outgoing(scene);
+                    }
+                    ManagerDirective::Pop | ManagerDirective::Refresh => {}
+                }
+                // The hooks above still see the resources
+                std::mem::swap(&mut self.resources, &mut active_stage.scene.resources);
 
-                self.stages.insert(active_key, active_stage);
+                self.stages.insert(active_key.clone(), active_stage);
 
                 match directive {
                     ManagerDirective::Switch(new_key) => {
+                        self.cancel_load();
+                        self.exit_stack();
                         self.set_active_stage(new_key);
+                        entry = StageEntry::Enter;
+                    }
+                    ManagerDirective::Push(new_key) => {
+                        self.stack.push(active_key);
+                        self.set_active_stage(new_key);
+                        self.update_backdrop();
+                        entry = StageEntry::Enter;
+                    }
+                    ManagerDirective::Load(loader, loading_key) => {
+                        self.cancel_load();
+                        // The loading stage finds the status in its resources
+                        self.resources.insert(loader.status().clone());
+                        self.pending_load = Some((active_key.clone(), loader));
+                        self.stack.push(active_key);
+                        self.set_active_stage(loading_key);
+                        self.update_backdrop();
+                        entry = StageEntry::Enter;
+                    }
+                    ManagerDirective::Pop => {
+                        // The bottom stage can't be popped
+                        entry = match self.stack.pop() {
+                            Some(lower_key) => {
+                                self.finish_load(&lower_key);
+                                self.set_active_stage(lower_key);
+                                self.update_backdrop();
+                                StageEntry::Resume
+                            }
+                            None => StageEntry::Refresh,
+                        };
                     }
-                    ManagerDirective::Refresh => continue,
+                    ManagerDirective::Refresh => entry = StageEntry::Refresh,
                     ManagerDirective::Kill => {
+                        self.cancel_load();
                         self.runtime.renderer.kill();
-                        break;
+                        return self.resources.save();
                     }
                 }
             } else {
@@ -0,0 +1,28 @@
+use std::fmt::{self, Display, Formatter};
+
+pub mod convert;
+#[cfg(feature = "image")]
+pub mod decode;
+pub mod sprite;
+
+pub use convert::{RenderMode, RgbaImage};
+pub use sprite::{Frame, FrameSequence, Sprite};
+
+#[derive(Debug)]
+pub enum AssetError {
+    Io(String),
+    Decode(String),
+    Parse { line: usize, message: String },
+}
+
+impl Display for AssetError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            AssetError::Io(err) => write!(f, "Could not read asset: {}", err),
+            AssetError::Decode(err) => write!(f, "Could not decode image: {}", err),
+            AssetError::Parse { line, message } => {
+                write!(f, "Invalid sprite at line {}: {}", line, message)
+            }
+        }
+    }
+}
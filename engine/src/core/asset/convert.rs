@@ -0,0 +1,211 @@
+use crossterm::style::Color;
+
+use super::Sprite;
+use crate::prelude::Glyph;
+
+const ASCII_RAMP: &[char] = &[' ', '.', ':', '-', '=', '+', '*', '#', '%', '@'];
+const ALPHA_THRESHOLD: u8 = 128;
+
+// Braille dot bits for a 2x4 cell, indexed by [y][x]
+const BRAILLE_DOTS: [[u32; 2]; 4] = [[0x01, 0x08], [0x02, 0x10], [0x04, 0x20], [0x40, 0x80]];
+
+/// How pixels are mapped onto terminal cells
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RenderMode {
+    /// `▀`/`▄` with fg/bg colours, two pixels per cell (double vertical resolution)
+    HalfBlock,
+    /// Luminance ramp, one pixel per cell
+    Ascii,
+    /// Braille dots, 2x4 pixels per cell
+    Braille,
+}
+
+impl RenderMode {
+    /// Pixels covered by a single cell (width, height)
+    pub fn cell_size(&self) -> (u32, u32) {
+        match self {
+            RenderMode::HalfBlock => (1, 2),
+            RenderMode::Ascii => (1, 2),
+            RenderMode::Braille => (2, 4),
+        }
+    }
+}
+
+/// Raw 8-bit RGBA pixels
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RgbaImage {
+    pub width: u32,
+    pub height: u32,
+    pub pixels: Vec<[u8; 4]>,
+}
+
+impl RgbaImage {
+    pub fn new(width: u32, height: u32) -> Self {
+        Self {
+            width,
+            height,
+            pixels: vec![[0, 0, 0, 0]; (width * height) as usize],
+        }
+    }
+
+    pub fn from_raw(width: u32, height: u32, bytes: &[u8]) -> Self {
+        let pixels = bytes
+            .chunks_exact(4)
+            .map(|px| [px[0], px[1], px[2], px[3]])
+            .collect();
+        Self {
+            width,
+            height,
+            pixels,
+        }
+    }
+
+    pub fn get(&self, x: u32, y: u32) -> [u8; 4] {
+        if x < self.width && y < self.height {
+            self.pixels[(y * self.width + x) as usize]
+        } else {
+            [0, 0, 0, 0]
+        }
+    }
+
+    /// Box filtered resize, the alpha channel is averaged like the colours
+    pub fn resize(&self, width: u32, height: u32) -> Self {
+        let mut resized = Self::new(width, height);
+        if self.width == 0 || self.height == 0 {
+            return resized;
+        }
+
+        for y in 0..height {
+            let y0 = y * self.height / height;
+            let y1 = ((y + 1) * self.height / height).max(y0 + 1);
+            for x in 0..width {
+                let x0 = x * self.width / width;
+                let x1 = ((x + 1) * self.width / width).max(x0 + 1);
+
+                let mut sum = [0u32; 4];
+                let mut count = 0;
+                for sy in y0..y1 {
+                    for sx in x0..x1 {
+                        let px = self.get(sx, sy);
+                        for channel in 0..4 {
+                            sum[channel] += px[channel] as u32;
+                        }
+                        count += 1;
+                    }
+                }
+
+                resized.pixels[(y * width + x) as usize] = sum.map(|total| (total / count) as u8);
+            }
+        }
+        resized
+    }
+
+    /// Converts the image into a sprite which is `columns` cells wide.
+    /// The height follows the aspect ratio of the image.
+    pub fn to_sprite(&self, mode: RenderMode, columns: u16) -> Sprite {
+        let (cell_w, cell_h) = mode.cell_size();
+        let px_width = (columns as u32 * cell_w).max(1);
+        let px_height = (px_width * self.height / self.width.max(1)).max(1);
+        let rows = px_height.div_ceil(cell_h) as u16;
+        let image = self.resize(px_width, rows as u32 * cell_h);
+
+        let mut sprite = Sprite::new(columns, rows);
+        for y in 0..rows {
+            for x in 0..columns {
+                let (px_x, px_y) = (x as u32 * cell_w, y as u32 * cell_h);
+                let glyph = match mode {
+                    RenderMode::HalfBlock => half_block(&image, px_x, px_y),
+                    RenderMode::Ascii => ascii(&image, px_x, px_y),
+                    RenderMode::Braille => braille(&image, px_x, px_y),
+                };
+                sprite.set(x, y, glyph);
+            }
+        }
+        sprite
+    }
+}
+
+fn is_opaque(px: [u8; 4]) -> bool {
+    px[3] >= ALPHA_THRESHOLD
+}
+
+fn to_color(px: [u8; 4]) -> Color {
+    Color::Rgb {
+        r: px[0],
+        g: px[1],
+        b: px[2],
+    }
+}
+
+fn luminance(px: [u8; 4]) -> u8 {
+    ((px[0] as u32 * 299 + px[1] as u32 * 587 + px[2] as u32 * 114) / 1000) as u8
+}
+
+fn half_block(image: &RgbaImage, x: u32, y: u32) -> Option<Glyph> {
+    let top = image.get(x, y);
+    let bottom = image.get(x, y + 1);
+
+    match (is_opaque(top), is_opaque(bottom)) {
+        (true, true) => Some(Glyph::new(Some(to_color(top)), Some(to_color(bottom)), '▀')),
+        (true, false) => Some(Glyph::new(Some(to_color(top)), None, '▀')),
+        (false, true) => Some(Glyph::new(Some(to_color(bottom)), None, '▄')),
+        (false, false) => None,
+    }
+}
+
+fn ascii(image: &RgbaImage, x: u32, y: u32) -> Option<Glyph> {
+    let top = image.get(x, y);
+    let bottom = image.get(x, y + 1);
+    let px = [0, 1, 2, 3].map(|channel| ((top[channel] as u16 + bottom[channel] as u16) / 2) as u8);
+
+    if !is_opaque(px) {
+        return None;
+    }
+
+    let index = luminance(px) as usize * (ASCII_RAMP.len() - 1) / 255;
+    Some(Glyph::new(Some(to_color(px)), None, ASCII_RAMP[index]))
+}
+
+fn braille(image: &RgbaImage, x: u32, y: u32) -> Option<Glyph> {
+    let mut opaque = Vec::with_capacity(8);
+    for dy in 0..4 {
+        for dx in 0..2 {
+            let px = image.get(x + dx, y + dy);
+            if is_opaque(px) {
+                opaque.push((dx, dy, px));
+            }
+        }
+    }
+
+    if opaque.is_empty() {
+        return None;
+    }
+
+    // Dots are lit when brighter than the cell average
+    let average = opaque
+        .iter()
+        .map(|(_, _, px)| luminance(*px) as u32)
+        .sum::<u32>()
+        / opaque.len() as u32;
+
+    let mut bits = 0;
+    let mut sum = [0u32; 3];
+    let mut lit = 0;
+    for (dx, dy, px) in &opaque {
+        if luminance(*px) as u32 >= average {
+            bits |= BRAILLE_DOTS[*dy as usize][*dx as usize];
+            for channel in 0..3 {
+                sum[channel] += px[channel] as u32;
+            }
+            lit += 1;
+        }
+    }
+
+    let symbol = char::from_u32(0x2800 + bits).unwrap_or(' ');
+    let color = Color::Rgb {
+        r: (sum[0] / lit) as u8,
+        g: (sum[1] / lit) as u8,
+        b: (sum[2] / lit) as u8,
+    };
+    Some(Glyph::new(Some(color), None, symbol))
+}
@@ -0,0 +1,158 @@
+use std::fs::File;
+use std::io::BufReader;
+use std::path::Path;
+use std::time::Duration;
+
+use super::{AssetError, FrameSequence, RenderMode, RgbaImage, Sprite};
+
+// GIF delays are stored in 1/100th of a second
+const GIF_DELAY_UNIT_MS: u64 = 10;
+
+fn open(path: &Path) -> Result<BufReader<File>, AssetError> {
+    File::open(path)
+        .map(BufReader::new)
+        .map_err(|err| AssetError::Io(err.to_string()))
+}
+
+pub fn decode_png(path: impl AsRef<Path>) -> Result<RgbaImage, AssetError> {
+    let mut decoder = png::Decoder::new(open(path.as_ref())?);
+    decoder.set_transformations(png::Transformations::normalize_to_color8());
+    let mut reader = decoder
+        .read_info()
+        .map_err(|err| AssetError::Decode(err.to_string()))?;
+
+    let mut buffer = vec![0; reader.output_buffer_size().unwrap_or(0)];
+    let info = reader
+        .next_frame(&mut buffer)
+        .map_err(|err| AssetError::Decode(err.to_string()))?;
+    let bytes = &buffer[..info.buffer_size()];
+
+    let pixels = match info.color_type {
+        png::ColorType::Rgba => bytes
+            .chunks_exact(4)
+            .map(|px| [px[0], px[1], px[2], px[3]])
+            .collect(),
+        png::ColorType::Rgb => bytes
+            .chunks_exact(3)
+            .map(|px| [px[0], px[1], px[2], 255])
+            .collect(),
+        png::ColorType::GrayscaleAlpha => bytes
+            .chunks_exact(2)
+            .map(|px| [px[0], px[0], px[0], px[1]])
+            .collect(),
+        png::ColorType::Grayscale => bytes.iter().map(|px| [*px, *px, *px, 255]).collect(),
+        png::ColorType::Indexed => {
+            return Err(AssetError::Decode("unexpanded palette".to_string()));
+        }
+    };
+
+    Ok(RgbaImage {
+        width: info.width,
+        height: info.height,
+        pixels,
+    })
+}
+
+/// Decodes every GIF frame composed onto the full canvas
+pub fn decode_gif(path: impl AsRef<Path>) -> Result<Vec<(RgbaImage, Duration)>, AssetError> {
+    let mut options = gif::DecodeOptions::new();
+    options.set_color_output(gif::ColorOutput::RGBA);
+    let mut decoder = options
+        .read_info(open(path.as_ref())?)
+        .map_err(|err| AssetError::Decode(err.to_string()))?;
+
+    let width = decoder.width() as u32;
+    let height = decoder.height() as u32;
+    let mut canvas = RgbaImage::new(width, height);
+    let mut frames = Vec::new();
+
+    while let Some(frame) = decoder
+        .read_next_frame()
+        .map_err(|err| AssetError::Decode(err.to_string()))?
+    {
+        let previous = canvas.clone();
+        let (left, top) = (frame.left as u32, frame.top as u32);
+
+        for y in 0..frame.height as u32 {
+            for x in 0..frame.width as u32 {
+                let (cx, cy) = (left + x, top + y);
+                if cx >= width || cy >= height {
+                    continue;
+                }
+
+                let i = ((y * frame.width as u32 + x) * 4) as usize;
+                let px = [
+                    frame.buffer[i],
+                    frame.buffer[i + 1],
+                    frame.buffer[i + 2],
+                    frame.buffer[i + 3],
+                ];
+                // Transparent pixels keep what's underneath
+                if px[3] != 0 {
+                    canvas.pixels[(cy * width + cx) as usize] = px;
+                }
+            }
+        }
+
+        let delay = Duration::from_millis(frame.delay as u64 * GIF_DELAY_UNIT_MS);
+        frames.push((canvas.clone(), delay));
+
+        match frame.dispose {
+            gif::DisposalMethod::Background => {
+                for y in top..(top + frame.height as u32).min(height) {
+                    for x in left..(left + frame.width as u32).min(width) {
+                        canvas.pixels[(y * width + x) as usize] = [0, 0, 0, 0];
+                    }
+                }
+            }
+            gif::DisposalMethod::Previous => canvas = previous,
+            _ => {}
+        }
+    }
+
+    Ok(frames)
+}
+
+/// Loads a PNG or the first frame of a GIF as a sprite
+pub fn load_sprite(
+    path: impl AsRef<Path>,
+    mode: RenderMode,
+    columns: u16,
+) -> Result<Sprite, AssetError> {
+    load_frames(path, mode, columns)?
+        .frames
+        .into_iter()
+        .next()
+        .map(|frame| frame.sprite)
+        .ok_or(AssetError::Decode("image has no frames".to_string()))
+}
+
+/// Loads a PNG (single frame) or every frame of a GIF
+pub fn load_frames(
+    path: impl AsRef<Path>,
+    mode: RenderMode,
+    columns: u16,
+) -> Result<FrameSequence, AssetError> {
+    let path = path.as_ref();
+    let extension = path
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .map(|ext| ext.to_ascii_lowercase());
+
+    let images = match extension.as_deref() {
+        Some("png") => vec![(decode_png(path)?, Duration::ZERO)],
+        Some("gif") => decode_gif(path)?,
+        _ => {
+            return Err(AssetError::Decode(format!(
+                "unsupported format: {}",
+                path.display()
+            )));
+        }
+    };
+
+    let mut sequence = FrameSequence::new();
+    for (image, delay) in images {
+        sequence.push(image.to_sprite(mode, columns), delay);
+    }
+    Ok(sequence)
+}
@@ -0,0 +1,435 @@
+use crossterm::style::Color;
+use std::time::Duration;
+
+use super::AssetError;
+use crate::prelude::{Glyph, Id, IdCounter, Occupant, Position, TCell, Terrain};
+
+/// A 2D matrix of glyphs where `None` is a transparent cell.
+/// The anchor is the cell which gets placed on the spawn position.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Sprite {
+    pub width: u16,
+    pub height: u16,
+    pub anchor: Position,
+    cells: Vec<Option<Glyph>>,
+}
+
+impl Sprite {
+    pub fn new(width: u16, height: u16) -> Self {
+        Self {
+            width,
+            height,
+            anchor: Position::empty(),
+            cells: vec![None; width as usize * height as usize],
+        }
+    }
+
+    /// Builds a sprite from rows, shorter rows are padded with transparent cells
+    pub fn from_rows(rows: Vec<Vec<Option<Glyph>>>) -> Self {
+        let height = rows.len() as u16;
+        let width = rows.iter().map(|row| row.len()).max().unwrap_or(0) as u16;
+        let mut sprite = Self::new(width, height);
+
+        for (y, row) in rows.into_iter().enumerate() {
+            for (x, glyph) in row.into_iter().enumerate() {
+                sprite.set(x as u16, y as u16, glyph);
+            }
+        }
+        sprite
+    }
+
+    /// Builds a single coloured sprite from text, spaces are transparent
+    pub fn from_text(text: &str, fg_clr: Option<Color>, bg_clr: Option<Color>) -> Self {
+        let rows = text
+            .lines()
+            .map(|line| {
+                line.chars()
+                    .map(|symbol| match symbol {
+                        ' ' => None,
+                        _ => Some(Glyph::new(fg_clr, bg_clr, symbol)),
+                    })
+                    .collect()
+            })
+            .collect();
+        Self::from_rows(rows)
+    }
+
+    pub fn with_anchor(mut self, anchor: Position) -> Self {
+        self.anchor = anchor;
+        self
+    }
+
+    fn index(&self, x: u16, y: u16) -> Option<usize> {
+        if x < self.width && y < self.height {
+            Some(y as usize * self.width as usize + x as usize)
+        } else {
+            None
+        }
+    }
+
+    pub fn get(&self, x: u16, y: u16) -> Option<Glyph> {
+        self.index(x, y).and_then(|index| self.cells[index])
+    }
+
+    pub fn set(&mut self, x: u16, y: u16, glyph: Option<Glyph>) {
+        if let Some(index) = self.index(x, y) {
+            self.cells[index] = glyph;
+        }
+    }
+
+    /// Rotates the sprite 90 degrees clockwise (anchor included)
+    pub fn rotate_cw(&self) -> Self {
+        let mut rotated = Self::new(self.height, self.width);
+        for y in 0..self.height {
+            for x in 0..self.width {
+                rotated.set(self.height - 1 - y, x, self.get(x, y));
+            }
+        }
+        rotated.anchor = Position::new(
+            self.height.saturating_sub(1).saturating_sub(self.anchor.y),
+            self.anchor.x,
+        );
+        rotated
+    }
+
+    /// Rotates the sprite 90 degrees counter-clockwise (anchor included)
+    pub fn rotate_ccw(&self) -> Self {
+        let mut rotated = Self::new(self.height, self.width);
+        for y in 0..self.height {
+            for x in 0..self.width {
+                rotated.set(y, self.width - 1 - x, self.get(x, y));
+            }
+        }
+        rotated.anchor = Position::new(
+            self.anchor.y,
+            self.width.saturating_sub(1).saturating_sub(self.anchor.x),
+        );
+        rotated
+    }
+
+    /// Mirrors the sprite along the vertical axis
+    pub fn flip_horizontal(&self) -> Self {
+        let mut flipped = Self::new(self.width, self.height);
+        for y in 0..self.height {
+            for x in 0..self.width {
+                flipped.set(self.width - 1 - x, y, self.get(x, y));
+            }
+        }
+        flipped.anchor = Position::new(
+            self.width.saturating_sub(1).saturating_sub(self.anchor.x),
+            self.anchor.y,
+        );
+        flipped
+    }
+
+    /// Mirrors the sprite along the horizontal axis
+    pub fn flip_vertical(&self) -> Self {
+        let mut flipped = Self::new(self.width, self.height);
+        for y in 0..self.height {
+            for x in 0..self.width {
+                flipped.set(x, self.height - 1 - y, self.get(x, y));
+            }
+        }
+        flipped.anchor = Position::new(
+            self.anchor.x,
+            self.height.saturating_sub(1).saturating_sub(self.anchor.y),
+        );
+        flipped
+    }
+
+    /// Iterates the opaque cells with their offset inside the sprite
+    pub fn cells(&self) -> impl Iterator<Item = (Position, Glyph)> + '_ {
+        self.cells.iter().enumerate().filter_map(|(index, glyph)| {
+            glyph.map(|glyph| {
+                let x = (index % self.width as usize) as u16;
+                let y = (index / self.width as usize) as u16;
+                (Position::new(x, y), glyph)
+            })
+        })
+    }
+
+    /// World position of a sprite offset when the anchor is placed at `origin`
+    pub fn world_pos(&self, origin: Position, offset: Position) -> Position {
+        Position::new(
+            (origin.x + offset.x).saturating_sub(self.anchor.x),
+            (origin.y + offset.y).saturating_sub(self.anchor.y),
+        )
+    }
+
+    /// Creates t_cells for an object with the anchor placed at `origin`
+    pub fn to_t_cells(
+        &self,
+        obj_id: Id,
+        id_counter: &mut IdCounter,
+        origin: Position,
+        z_index: u8,
+    ) -> Vec<TCell> {
+        self.cells()
+            .map(|(offset, glyph)| {
+                TCell::new(
+                    Occupant::new(obj_id, id_counter.next()),
+                    glyph,
+                    Some(self.world_pos(origin, offset)),
+                    z_index,
+                )
+            })
+            .collect()
+    }
+
+    /// Terrain for a grid position, usable inside a `SpatialGrid` terrain generator.
+    /// Transparent and out of bounds cells fall back to `fallback`.
+    pub fn terrain_at(&self, grid_pos: Position, fallback: Terrain) -> Terrain {
+        match self.get(grid_pos.x, grid_pos.y) {
+            Some(glyph) => Terrain {
+                style: glyph,
+                ..fallback
+            },
+            None => fallback,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Frame {
+    pub sprite: Sprite,
+    pub delay: Duration,
+}
+
+/// An ordered list of sprites, e.g. the frames of a GIF
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct FrameSequence {
+    pub frames: Vec<Frame>,
+}
+
+impl FrameSequence {
+    pub fn new() -> Self {
+        Self { frames: Vec::new() }
+    }
+
+    pub fn push(&mut self, sprite: Sprite, delay: Duration) {
+        self.frames.push(Frame { sprite, delay });
+    }
+
+    pub fn len(&self) -> usize {
+        self.frames.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.frames.is_empty()
+    }
+
+    pub fn total_duration(&self) -> Duration {
+        self.frames.iter().map(|frame| frame.delay).sum()
+    }
+
+    /// Serializes the sequence into the plain-text sprite format:
+    ///
+    /// ```text
+    /// sprite <width> <height> <anchor_x> <anchor_y>
+    /// frame <delay_ms>
+    /// <cell> <cell> ...
+    /// ```
+    ///
+    /// A cell is either `.` (transparent) or `<symbol>:<fg>:<bg>`, where the symbol is
+    /// a hex code point and colours are `rrggbb` or `-` for none.
+    pub fn to_text(&self) -> String {
+        let mut text = String::new();
+        let Some(first) = self.frames.first() else {
+            return text;
+        };
+
+        text.push_str(&format!(
+            "sprite {} {} {} {}\n",
+            first.sprite.width, first.sprite.height, first.sprite.anchor.x, first.sprite.anchor.y
+        ));
+
+        for frame in &self.frames {
+            text.push_str(&format!("frame {}\n", frame.delay.as_millis()));
+            for y in 0..frame.sprite.height {
+                let row: Vec<String> = (0..frame.sprite.width)
+                    .map(|x| match frame.sprite.get(x, y) {
+                        Some(glyph) => format!(
+                            "{:x}:{}:{}",
+                            glyph.symbol as u32,
+                            color_to_hex(glyph.fg_clr),
+                            color_to_hex(glyph.bg_clr)
+                        ),
+                        None => ".".to_string(),
+                    })
+                    .collect();
+                text.push_str(&row.join(" "));
+                text.push('\n');
+            }
+        }
+        text
+    }
+
+    pub fn parse(source: &str) -> Result<Self, AssetError> {
+        let error = |line: usize, message: &str| AssetError::Parse {
+            line,
+            message: message.to_string(),
+        };
+
+        let mut lines = source
+            .lines()
+            .enumerate()
+            .map(|(index, line)| (index + 1, line.trim()))
+            .filter(|(_, line)| !line.is_empty());
+
+        let (line_nr, header) = lines.next().ok_or(error(1, "empty sprite"))?;
+        let header: Vec<u16> = header
+            .strip_prefix("sprite ")
+            .ok_or(error(line_nr, "missing sprite header"))?
+            .split_whitespace()
+            .map(|value| value.parse::<u16>())
+            .collect::<Result<_, _>>()
+            .map_err(|_| error(line_nr, "invalid sprite header"))?;
+
+        let [width, height, anchor_x, anchor_y] = header[..] else {
+            return Err(error(line_nr, "invalid sprite header"));
+        };
+
+        let mut sequence = FrameSequence::new();
+        while let Some((line_nr, line)) = lines.next() {
+            let delay = line
+                .strip_prefix("frame ")
+                .and_then(|delay| delay.trim().parse::<u64>().ok())
+                .ok_or(error(line_nr, "expected frame header"))?;
+
+            let mut sprite =
+                Sprite::new(width, height).with_anchor(Position::new(anchor_x, anchor_y));
+
+            for y in 0..height {
+                let (line_nr, row) = lines.next().ok_or(error(line_nr, "missing frame rows"))?;
+                let cells: Vec<&str> = row.split_whitespace().collect();
+                if cells.len() != width as usize {
+                    return Err(error(line_nr, "row does not match the sprite width"));
+                }
+                for (x, cell) in cells.into_iter().enumerate() {
+                    let glyph = parse_cell(cell).ok_or(error(line_nr, cell))?;
+                    sprite.set(x as u16, y, glyph);
+                }
+            }
+
+            sequence.push(sprite, Duration::from_millis(delay));
+        }
+
+        Ok(sequence)
+    }
+}
+
+impl Sprite {
+    /// Parses the first frame of the plain-text sprite format
+    pub fn parse(source: &str) -> Result<Self, AssetError> {
+        FrameSequence::parse(source)?
+            .frames
+            .into_iter()
+            .next()
+            .map(|frame| frame.sprite)
+            .ok_or(AssetError::Parse {
+                line: 1,
+                message: "sprite has no frames".to_string(),
+            })
+    }
+}
+
+fn parse_cell(cell: &str) -> Option<Option<Glyph>> {
+    if cell == "." {
+        return Some(None);
+    }
+
+    let mut parts = cell.split(':');
+    let symbol = char::from_u32(u32::from_str_radix(parts.next()?, 16).ok()?)?;
+    let fg_clr = hex_to_color(parts.next()?)?;
+    let bg_clr = hex_to_color(parts.next()?)?;
+    Some(Some(Glyph::new(fg_clr, bg_clr, symbol)))
+}
+
+fn color_to_hex(color: Option<Color>) -> String {
+    match color {
+        Some(Color::Rgb { r, g, b }) => format!("{:02x}{:02x}{:02x}", r, g, b),
+        _ => "-".to_string(),
+    }
+}
+
+fn hex_to_color(hex: &str) -> Option<Option<Color>> {
+    if hex == "-" {
+        return Some(None);
+    }
+    // Only ASCII hex digits, slicing a multi-byte char would panic and
+    // `from_str_radix` takes a leading sign
+    if hex.len() != 6 || !hex.bytes().all(|byte| byte.is_ascii_hexdigit()) {
+        return None;
+    }
+
+    let channel = |range| u8::from_str_radix(&hex[range], 16).ok();
+    Some(Some(Color::Rgb {
+        r: channel(0..2)?,
+        g: channel(2..4)?,
+        b: channel(4..6)?,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SPRITE: &str = "\
+sprite 2 2 0 1
+frame 100
+41:ff0000:- .
+. 42:-:00ff00
+";
+
+    fn parse_error(source: &str) -> usize {
+        match FrameSequence::parse(source) {
+            Err(AssetError::Parse { line, .. }) => line,
+            other => panic!("expected a parse error, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn round_trips_the_text_format() {
+        let sequence = FrameSequence::parse(SPRITE).unwrap();
+        assert_eq!(sequence.len(), 1);
+        assert_eq!(sequence.total_duration(), Duration::from_millis(100));
+
+        let sprite = &sequence.frames[0].sprite;
+        assert_eq!(sprite.anchor, Position::new(0, 1));
+        assert_eq!(
+            sprite.get(0, 0),
+            Some(Glyph::new(
+                Some(Color::Rgb { r: 255, g: 0, b: 0 }),
+                None,
+                'A'
+            ))
+        );
+        assert_eq!(sprite.get(1, 0), None);
+        assert_eq!(FrameSequence::parse(&sequence.to_text()).unwrap(), sequence);
+    }
+
+    #[test]
+    fn bad_hex() {
+        for cell in ["41:ff00:-", "41:gg0000:-", "41:+fffff:-", "zz:-:-", "41:-"] {
+            let source = SPRITE.replace("41:ff0000:-", cell);
+            assert_eq!(parse_error(&source), 3, "{cell}");
+        }
+    }
+
+    #[test]
+    fn short_rows() {
+        assert_eq!(parse_error(&SPRITE.replace("41:ff0000:- .", ".")), 3);
+        assert_eq!(parse_error(&SPRITE.replace("41:ff0000:- .", ". . .")), 3);
+        // The frame ends before its last row, the error points at the frame
+        assert_eq!(parse_error("sprite 2 2 0 0\nframe 100\n. .\n"), 2);
+    }
+
+    #[test]
+    fn non_ascii_input() {
+        // Six bytes but not six hex digits
+        assert_eq!(parse_error(&SPRITE.replace("ff0000", "ééé")), 3);
+        assert_eq!(parse_error(&SPRITE.replace("00ff00", "aébéc")), 4);
+        assert_eq!(parse_error(&SPRITE.replace("sprite", "spríte")), 1);
+        assert_eq!(parse_error(&SPRITE.replace("frame", "främe")), 2);
+    }
+}
@@ -0,0 +1,838 @@
+//! Drop-down debug console. Commands are registered by the engine and by the
+//! game, the game's commands run against its logic (the context `C`), which
+//! the console hands them back to through `ConsoleInput::Run`.
+//! The console also owns the object inspector, opened with `inspect`,
+//! and the profiler's overlay, opened with `profile`.
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers, MouseEvent};
+use crossterm::style::Color;
+use std::collections::VecDeque;
+use std::hash::Hash;
+
+mod args;
+mod builtins;
+
+pub use args::Args;
+
+use crate::core::inspector::Inspector;
+use crate::prelude::{
+    Axis, Border, Conflict, Handle, LogView, OVERLAY_Z_INDEX, Profiler, ProfilerOverlay, Rect,
+    RuntimeCommand, Scene, Size, Style, TextInput, Widget,
+};
+
+const HEIGHT: u16 = 14;
+const MAX_OUTPUT: usize = 200;
+const MAX_HISTORY: usize = 50;
+
+const TEXT_COLOR: Color = Color::Rgb {
+    r: 220,
+    g: 220,
+    b: 220,
+};
+const ECHO_COLOR: Color = Color::Rgb {
+    r: 130,
+    g: 170,
+    b: 255,
+};
+const ERROR_COLOR: Color = Color::Rgb {
+    r: 255,
+    g: 110,
+    b: 110,
+};
+const PANEL_COLOR: Color = Color::Rgb {
+    r: 20,
+    g: 20,
+    b: 28,
+};
+
+pub type CommandResult<K> = Result<Reply<K>, String>;
+
+/// Runs a command against the context the console was made for, usually the logic
+pub type Handler<K, C> = fn(&mut C, &mut Scene, &Args) -> CommandResult<K>;
+
+/// What a command printed and what the runtime should do about it
+pub struct Reply<K: Eq + Hash + Clone> {
+    pub message: Option<String>,
+    pub command: RuntimeCommand<K>,
+}
+
+impl<K: Eq + Hash + Clone> Reply<K> {
+    pub fn none() -> Self {
+        Self {
+            message: None,
+            command: RuntimeCommand::None,
+        }
+    }
+
+    pub fn message(message: impl Into<String>) -> Self {
+        Self {
+            message: Some(message.into()),
+            command: RuntimeCommand::None,
+        }
+    }
+
+    pub fn command(command: RuntimeCommand<K>) -> Self {
+        Self {
+            message: None,
+            command,
+        }
+    }
+
+    pub fn with_message(mut self, message: impl Into<String>) -> Self {
+        self.message = Some(message.into());
+        self
+    }
+}
+
+// Help, clear, history, inspect and profile need the console itself and are run by it
+enum Action<K: Eq + Hash + Clone, C> {
+    Help,
+    Clear,
+    History,
+    Inspect,
+    Profile,
+    Run(Handler<K, C>),
+}
+
+impl<K: Eq + Hash + Clone, C> Clone for Action<K, C> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<K: Eq + Hash + Clone, C> Copy for Action<K, C> {}
+
+pub struct ConsoleCommand<K: Eq + Hash + Clone, C> {
+    name: &'static str,
+    usage: &'static str,
+    help: &'static str,
+    completions: &'static [&'static str],
+    action: Action<K, C>,
+}
+
+impl<K: Eq + Hash + Clone, C> ConsoleCommand<K, C> {
+    pub fn new(
+        name: &'static str,
+        usage: &'static str,
+        help: &'static str,
+        handler: Handler<K, C>,
+    ) -> Self {
+        Self::with_action(name, usage, help, Action::Run(handler))
+    }
+
+    /// Words tab completion offers for the first argument
+    pub fn completions(mut self, completions: &'static [&'static str]) -> Self {
+        self.completions = completions;
+        self
+    }
+
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+
+    fn with_action(
+        name: &'static str,
+        usage: &'static str,
+        help: &'static str,
+        action: Action<K, C>,
+    ) -> Self {
+        Self {
+            name,
+            usage,
+            help,
+            completions: &[],
+            action,
+        }
+    }
+}
+
+/// A submitted command, run it with the console's context
+pub struct Invocation<K: Eq + Hash + Clone, C> {
+    handler: Handler<K, C>,
+    args: Args,
+}
+
+impl<K: Eq + Hash + Clone, C> Invocation<K, C> {
+    pub fn run(self, context: &mut C, scene: &mut Scene) -> CommandResult<K> {
+        (self.handler)(context, scene, &self.args)
+    }
+
+    pub fn args(&self) -> &Args {
+        &self.args
+    }
+}
+
+pub enum ConsoleInput<K: Eq + Hash + Clone, C> {
+    /// The console is closed and the key is left to the caller
+    Ignored,
+    Consumed,
+    /// Opened or closed, the frame needs a full render
+    Toggled,
+    /// A key bound by the console, like stepping a tick from the inspector
+    Command(RuntimeCommand<K>),
+    /// Pass the result on to `Console::report`
+    Run(Invocation<K, C>),
+}
+
+/// Takes the keyboard while it's open and drops down from the top of the screen.
+/// The output and the history are kept while it's closed.
+pub struct Console<K: Eq + Hash + Clone, C> {
+    commands: Vec<ConsoleCommand<K, C>>,
+    toggle_key: char,
+    height: u16,
+    screen: (u16, u16),
+    is_open: bool,
+    output: VecDeque<(String, Style)>,
+    history: Vec<String>,
+    // Entry shown in the input while browsing the history
+    history_index: Option<usize>,
+    // The line typed before browsing the history
+    draft: String,
+    border: Option<Handle<Border>>,
+    log: Option<Handle<LogView>>,
+    input: Option<Handle<TextInput>>,
+    inspector: Inspector,
+    profiler: ProfilerOverlay,
+}
+
+impl<K: Eq + Hash + Clone, C> Default for Console<K, C> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K: Eq + Hash + Clone, C> Console<K, C> {
+    /// Comes with the engine's commands, see `help`
+    pub fn new() -> Self {
+        let mut console = Self {
+            commands: Vec::new(),
+            toggle_key: '`',
+            height: HEIGHT,
+            screen: (80, 24),
+            is_open: false,
+            output: VecDeque::with_capacity(MAX_OUTPUT),
+            history: Vec::new(),
+            history_index: None,
+            draft: String::new(),
+            border: None,
+            log: None,
+            input: None,
+            inspector: Inspector::new(),
+            profiler: ProfilerOverlay::new(),
+        };
+        for command in builtins::commands() {
+            console.register(command);
+        }
+        console
+    }
+
+    pub fn toggle_key(mut self, key: char) -> Self {
+        self.toggle_key = key;
+        self
+    }
+
+    pub fn height(mut self, height: u16) -> Self {
+        self.height = height;
+        self
+    }
+
+    /// Replaces a command with the same name
+    pub fn register(&mut self, command: ConsoleCommand<K, C>) {
+        self.commands
+            .retain(|registered| registered.name != command.name);
+        let index = self
+            .commands
+            .partition_point(|registered| registered.name < command.name);
+        self.commands.insert(index, command);
+    }
+
+    pub fn is_open(&self) -> bool {
+        self.is_open
+    }
+
+    pub fn inspector(&self) -> &Inspector {
+        &self.inspector
+    }
+
+    pub fn inspector_mut(&mut self) -> &mut Inspector {
+        &mut self.inspector
+    }
+
+    pub fn profiler(&self) -> &ProfilerOverlay {
+        &self.profiler
+    }
+
+    pub fn profiler_mut(&mut self) -> &mut ProfilerOverlay {
+        &mut self.profiler
+    }
+
+    pub fn open(&mut self, scene: &mut Scene) {
+        if self.is_open {
+            return;
+        }
+        self.is_open = true;
+        self.screen = crossterm::terminal::size().unwrap_or(self.screen);
+
+        let panel = Style::new(Some(TEXT_COLOR), Some(PANEL_COLOR));
+        self.border = scene.attach_typed(
+            |id| {
+                Border::new(id, Rect::default())
+                    .title("Console")
+                    .style(Style::new(Some(ECHO_COLOR), Some(PANEL_COLOR)))
+                    .fill(Style::new(None, Some(PANEL_COLOR)))
+                    .z_index(OVERLAY_Z_INDEX)
+            },
+            Conflict::Ignore,
+        );
+        self.log = scene.attach_typed(
+            |id| LogView::new(id, Rect::default()).z_index(OVERLAY_Z_INDEX),
+            Conflict::Ignore,
+        );
+        self.input = scene.attach_typed(
+            |id| {
+                TextInput::new(id, Rect::default())
+                    .prompt("> ")
+                    .style(panel)
+                    .z_index(OVERLAY_Z_INDEX)
+            },
+            Conflict::Ignore,
+        );
+        self.place(scene);
+    }
+
+    pub fn close(&mut self, scene: &mut Scene) {
+        if !self.is_open {
+            return;
+        }
+        self.is_open = false;
+        self.history_index = None;
+
+        let ids = [
+            self.border.take().map(|handle| handle.id()),
+            self.log.take().map(|handle| handle.id()),
+            self.input.take().map(|handle| handle.id()),
+        ];
+        for id in ids.into_iter().flatten() {
+            scene.commands.despawn(id);
+        }
+    }
+
+    /// Attaches an open console to a new scene, after the stage's scene was replaced
+    pub fn reattach(&mut self, scene: &mut Scene) {
+        self.inspector.reattach(scene);
+        self.profiler.reattach(scene);
+        if self.is_open {
+            self.is_open = false;
+            (self.border, self.log, self.input) = (None, None, None);
+            self.open(scene);
+        }
+    }
+
+    pub fn toggle(&mut self, scene: &mut Scene) {
+        match self.is_open {
+            true => self.close(scene),
+            false => self.open(scene),
+        }
+    }
+
+    /// Every line of the message becomes an entry of the output
+    pub fn print(&mut self, scene: &mut Scene, message: impl Into<String>, style: Style) {
+        let mut log = self
+            .log
+            .and_then(|handle| handle.get_mut(&mut scene.objects));
+        for line in message.into().lines() {
+            if let Some(log) = log.as_mut() {
+                log.push(line, style);
+            }
+
+            self.output.push_back((line.to_string(), style));
+            if self.output.len() > MAX_OUTPUT {
+                self.output.pop_front();
+            }
+        }
+    }
+
+    pub fn info(&mut self, scene: &mut Scene, message: impl Into<String>) {
+        self.print(scene, message, Style::fg(TEXT_COLOR));
+    }
+
+    pub fn error(&mut self, scene: &mut Scene, message: impl Into<String>) {
+        self.print(
+            scene,
+            format!("error: {}", message.into()),
+            Style::fg(ERROR_COLOR),
+        );
+    }
+
+    pub fn resize(&mut self, scene: &mut Scene, width: u16, height: u16) {
+        self.screen = (width, height);
+        self.inspector.resize(scene, width, height);
+        self.place(scene);
+    }
+
+    /// Keeps the open overlays up to date, call it every tick before the sync
+    /// (e.g. from `dispatch_events`) to see the pending state changes
+    pub fn update(&mut self, scene: &mut Scene) {
+        self.inspector.update(scene);
+        self.profiler.update(scene);
+    }
+
+    /// Returns false if no overlay used the mouse event
+    pub fn handle_mouse(&mut self, mouse_event: MouseEvent, scene: &mut Scene) -> bool {
+        self.inspector.handle_mouse(mouse_event, scene)
+    }
+
+    /// Feeds a key to the console, the toggle key opens and closes it
+    pub fn handle_key(&mut self, key_event: KeyEvent, scene: &mut Scene) -> ConsoleInput<K, C> {
+        if !key_event.is_press() || key_event.modifiers.contains(KeyModifiers::CONTROL) {
+            return match self.is_open {
+                true => ConsoleInput::Consumed,
+                false => ConsoleInput::Ignored,
+            };
+        }
+
+        if key_event.code == KeyCode::Char(self.toggle_key) {
+            self.toggle(scene);
+            return ConsoleInput::Toggled;
+        }
+
+        // The overlays' keys work with the console closed
+        if key_event.code == KeyCode::F(9) {
+            self.profiler.toggle(scene);
+            self.place(scene);
+            return ConsoleInput::Toggled;
+        }
+        if self.inspector.is_open() {
+            match key_event.code {
+                KeyCode::F(10) => {
+                    scene.set_time_scale(0.0);
+                    return ConsoleInput::Command(RuntimeCommand::Step(1));
+                }
+                KeyCode::PageDown => {
+                    self.inspector.scroll(scene, 10);
+                    return ConsoleInput::Consumed;
+                }
+                KeyCode::PageUp => {
+                    self.inspector.scroll(scene, -10);
+                    return ConsoleInput::Consumed;
+                }
+                _ => {}
+            }
+        }
+
+        if !self.is_open {
+            return ConsoleInput::Ignored;
+        }
+
+        let Some(input) = self
+            .input
+            .and_then(|handle| handle.get_mut(&mut scene.objects))
+        else {
+            return ConsoleInput::Consumed;
+        };
+
+        match key_event.code {
+            KeyCode::Char(symbol) => input.insert(symbol),
+            KeyCode::Backspace => input.backspace(),
+            KeyCode::Delete => input.delete(),
+            KeyCode::Left => input.move_left(),
+            KeyCode::Right => input.move_right(),
+            KeyCode::Home => input.move_home(),
+            KeyCode::End => input.move_end(),
+            KeyCode::Up => self.recall(scene, true),
+            KeyCode::Down => self.recall(scene, false),
+            KeyCode::Tab => self.complete(scene),
+            KeyCode::Enter => {
+                let line = input.take();
+                return self.submit(scene, line);
+            }
+            KeyCode::Esc => {
+                self.close(scene);
+                return ConsoleInput::Toggled;
+            }
+            _ => {}
+        }
+        ConsoleInput::Consumed
+    }
+
+    /// Prints the outcome of a command and returns what the runtime should do
+    pub fn report(&mut self, scene: &mut Scene, result: CommandResult<K>) -> RuntimeCommand<K> {
+        match result {
+            Ok(reply) => {
+                if let Some(message) = reply.message {
+                    self.info(scene, message);
+                }
+                reply.command
+            }
+            Err(error) => {
+                self.error(scene, error);
+                RuntimeCommand::None
+            }
+        }
+    }
+
+    // The box spans the top of the screen next to the inspector,
+    // the input sits on its last inner row. The profiler's overlay
+    // sits at the bottom, next to the inspector as well.
+    fn place(&mut self, scene: &mut Scene) {
+        let (width, height) = self.screen;
+        let width = width.saturating_sub(self.inspector.width());
+        self.profiler.resize(scene, width, height);
+        let frame = Rect::new(0, 0, width, self.height.min(height));
+        let parts = frame
+            .inner(1)
+            .split(Axis::Vertical, &[Size::Fill, Size::Fixed(1)]);
+
+        if let Some(border) = self
+            .border
+            .and_then(|handle| handle.get_mut(&mut scene.objects))
+        {
+            border.set_rect(frame);
+        }
+        if let Some(log) = self
+            .log
+            .and_then(|handle| handle.get_mut(&mut scene.objects))
+        {
+            log.set_rect(parts[0]);
+            log.clear();
+            for (message, style) in &self.output {
+                log.push(message.clone(), *style);
+            }
+        }
+        if let Some(input) = self
+            .input
+            .and_then(|handle| handle.get_mut(&mut scene.objects))
+        {
+            input.set_rect(parts[1]);
+        }
+    }
+
+    fn submit(&mut self, scene: &mut Scene, line: String) -> ConsoleInput<K, C> {
+        self.history_index = None;
+        let Some((name, args)) = Args::parse(&line) else {
+            return ConsoleInput::Consumed;
+        };
+
+        self.print(scene, format!("> {line}"), Style::fg(ECHO_COLOR));
+        if self.history.last() != Some(&line) {
+            self.history.push(line);
+            if self.history.len() > MAX_HISTORY {
+                self.history.remove(0);
+            }
+        }
+
+        let Some(action) = self
+            .commands
+            .iter()
+            .find(|command| command.name == name)
+            .map(|command| command.action)
+        else {
+            self.error(scene, format!("unknown command '{name}', try 'help'"));
+            return ConsoleInput::Consumed;
+        };
+
+        match action {
+            Action::Help => self.help(scene, args.get(0)),
+            Action::Clear => {
+                self.output.clear();
+                if let Some(log) = self
+                    .log
+                    .and_then(|handle| handle.get_mut(&mut scene.objects))
+                {
+                    log.clear();
+                }
+            }
+            Action::History => {
+                let history: Vec<String> = self.history.clone();
+                for (index, line) in history.iter().enumerate() {
+                    self.info(scene, format!("{index:>3}  {line}"));
+                }
+            }
+            Action::Inspect => {
+                if let Err(error) = self.inspect(scene, &args) {
+                    self.error(scene, error);
+                }
+                // The console makes room for the inspector
+                self.place(scene);
+                return ConsoleInput::Command(RuntimeCommand::Redraw);
+            }
+            Action::Profile => {
+                if let Err(error) = self.profile(scene, &args) {
+                    self.error(scene, error);
+                }
+                self.place(scene);
+                return ConsoleInput::Command(RuntimeCommand::Redraw);
+            }
+            Action::Run(handler) => return ConsoleInput::Run(Invocation { handler, args }),
+        }
+        ConsoleInput::Consumed
+    }
+
+    fn inspect(&mut self, scene: &mut Scene, args: &Args) -> Result<(), String> {
+        let highlight: bool = args.named_or("highlight", true)?;
+        let target = match args.get(0) {
+            Some("off") => {
+                self.inspector.close(scene);
+                return Ok(());
+            }
+            Some(_) => Some(args.object(0, scene)?),
+            None => self.inspector.target(),
+        };
+
+        self.inspector.open(scene);
+        self.inspector.set_highlight(scene, highlight);
+        self.inspector.select(scene, target);
+        if let Some(id) = target {
+            self.info(scene, format!("inspecting #{}, F10 steps a tick", id.value));
+        }
+        Ok(())
+    }
+
+    fn profile(&mut self, scene: &mut Scene, args: &Args) -> Result<(), String> {
+        let Some(option) = args.get(0) else {
+            self.profiler.toggle(scene);
+            return Ok(());
+        };
+
+        let profiler = scene.resources.get_or_insert_with(Profiler::new);
+        let message = match option {
+            "on" => {
+                profiler.start();
+                "profiling".to_string()
+            }
+            "off" => {
+                profiler.stop();
+                "stopped profiling".to_string()
+            }
+            "clear" => {
+                profiler.clear();
+                "cleared the samples and the trace".to_string()
+            }
+            "export" => {
+                let path = args.get(1).unwrap_or("trace.json");
+                let spans = profiler
+                    .export_trace(path)
+                    .map_err(|err| format!("could not write {path}: {err}"))?;
+                format!("wrote {spans} spans to {path}")
+            }
+            other => return Err(format!("unknown option '{other}'")),
+        };
+        self.info(scene, message);
+        Ok(())
+    }
+
+    fn help(&mut self, scene: &mut Scene, name: Option<&str>) {
+        let lines: Vec<String> = self
+            .commands
+            .iter()
+            .filter(|command| name.is_none_or(|name| command.name == name))
+            .map(|command| format!("{:<28}{}", command.usage, command.help))
+            .collect();
+
+        if lines.is_empty() {
+            self.error(
+                scene,
+                format!("unknown command '{}'", name.unwrap_or_default()),
+            );
+        }
+        for line in lines {
+            self.info(scene, line);
+        }
+    }
+
+    // Completes command names and the first argument of commands with completions
+    fn complete(&mut self, scene: &mut Scene) {
+        let Some(text) = self
+            .input
+            .and_then(|handle| handle.get(&scene.objects))
+            .map(TextInput::text)
+        else {
+            return;
+        };
+
+        let (head, word) = match text.rfind(' ') {
+            Some(index) => text.split_at(index + 1),
+            None => ("", text.as_str()),
+        };
+        let candidates: Vec<&'static str> = match head.split_whitespace().collect::<Vec<_>>()[..] {
+            [] => self.commands.iter().map(|command| command.name).collect(),
+            [name] => self
+                .commands
+                .iter()
+                .find(|command| command.name == name)
+                .map(|command| command.completions.to_vec())
+                .unwrap_or_default(),
+            _ => Vec::new(),
+        };
+        let matches: Vec<&str> = candidates
+            .into_iter()
+            .filter(|candidate| candidate.starts_with(word))
+            .collect();
+
+        let completed = match matches[..] {
+            [] => return,
+            [single] => format!("{head}{single} "),
+            _ => {
+                let common = matches
+                    .iter()
+                    .skip(1)
+                    .fold(matches[0], |common, candidate| {
+                        common_prefix(common, candidate)
+                    });
+                self.info(scene, matches.join("  "));
+                format!("{head}{common}")
+            }
+        };
+
+        if let Some(input) = self
+            .input
+            .and_then(|handle| handle.get_mut(&mut scene.objects))
+        {
+            input.set_text(&completed);
+        }
+    }
+
+    // Steps through the history, past the newest entry is the line typed before
+    fn recall(&mut self, scene: &mut Scene, older: bool) {
+        let Some(input) = self
+            .input
+            .and_then(|handle| handle.get_mut(&mut scene.objects))
+        else {
+            return;
+        };
+        if self.history.is_empty() {
+            return;
+        }
+
+        let last = self.history.len() - 1;
+        let index = match (self.history_index, older) {
+            (None, true) => {
+                self.draft = input.text();
+                Some(last)
+            }
+            (None, false) => return,
+            (Some(index), true) => Some(index.saturating_sub(1)),
+            (Some(index), false) if index < last => Some(index + 1),
+            (Some(_), false) => None,
+        };
+
+        self.history_index = index;
+        match index {
+            Some(index) => input.set_text(&self.history[index]),
+            None => input.set_text(&std::mem::take(&mut self.draft)),
+        }
+    }
+}
+
+// The start both share, cut on a char boundary of `a`
+fn common_prefix<'a>(a: &'a str, b: &str) -> &'a str {
+    let end = a
+        .char_indices()
+        .zip(b.chars())
+        .find(|((_, a), b)| a != b)
+        .map(|((index, _), _)| index)
+        .unwrap_or(a.len().min(b.len()));
+    &a[..end]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn noop(_: &mut (), _: &mut Scene, _: &Args) -> CommandResult<()> {
+        Ok(Reply::none())
+    }
+
+    fn console() -> (Console<(), ()>, Scene) {
+        let mut console = Console::new();
+        console.register(
+            ConsoleCommand::new("paint", "paint <colour>", "Paints", noop)
+                .completions(&["grün", "grüße", "gelb"]),
+        );
+        let mut scene = Scene::new();
+        console.open(&mut scene);
+        (console, scene)
+    }
+
+    fn press(console: &mut Console<(), ()>, scene: &mut Scene, code: KeyCode) {
+        console.handle_key(KeyEvent::from(code), scene);
+    }
+
+    fn type_text(console: &mut Console<(), ()>, scene: &mut Scene, text: &str) {
+        for symbol in text.chars() {
+            press(console, scene, KeyCode::Char(symbol));
+        }
+    }
+
+    fn input(console: &Console<(), ()>, scene: &Scene) -> String {
+        console
+            .input
+            .and_then(|handle| handle.get(&scene.objects))
+            .map(TextInput::text)
+            .unwrap()
+    }
+
+    #[test]
+    fn completes_command_names() {
+        let (mut console, mut scene) = console();
+        // Several matches complete their common start, `pause` and `paint`
+        type_text(&mut console, &mut scene, "p");
+        press(&mut console, &mut scene, KeyCode::Tab);
+        assert_eq!(input(&console, &scene), "p");
+        type_text(&mut console, &mut scene, "a");
+        press(&mut console, &mut scene, KeyCode::Tab);
+        assert_eq!(input(&console, &scene), "pa");
+
+        type_text(&mut console, &mut scene, "i");
+        press(&mut console, &mut scene, KeyCode::Tab);
+        assert_eq!(input(&console, &scene), "paint ");
+    }
+
+    #[test]
+    fn completes_non_ascii_arguments() {
+        let (mut console, mut scene) = console();
+        type_text(&mut console, &mut scene, "paint g");
+        press(&mut console, &mut scene, KeyCode::Tab);
+        assert_eq!(input(&console, &scene), "paint g");
+
+        type_text(&mut console, &mut scene, "r");
+        press(&mut console, &mut scene, KeyCode::Tab);
+        assert_eq!(input(&console, &scene), "paint grü");
+
+        type_text(&mut console, &mut scene, "ß");
+        press(&mut console, &mut scene, KeyCode::Tab);
+        assert_eq!(input(&console, &scene), "paint grüße ");
+    }
+
+    #[test]
+    fn common_prefixes_end_on_char_boundaries() {
+        assert_eq!(common_prefix("grün", "grüße"), "grü");
+        assert_eq!(common_prefix("fä1", "fä2"), "fä");
+        assert_eq!(common_prefix("grün", "gr"), "gr");
+        assert_eq!(common_prefix("", "grün"), "");
+    }
+
+    #[test]
+    fn recalls_the_history() {
+        let (mut console, mut scene) = console();
+        for line in ["paint gelb", "paint grün", "paint grün"] {
+            type_text(&mut console, &mut scene, line);
+            press(&mut console, &mut scene, KeyCode::Enter);
+        }
+        // Repeated lines are kept once
+        assert_eq!(console.history, vec!["paint gelb", "paint grün"]);
+
+        type_text(&mut console, &mut scene, "draft");
+        press(&mut console, &mut scene, KeyCode::Up);
+        assert_eq!(input(&console, &scene), "paint grün");
+        press(&mut console, &mut scene, KeyCode::Up);
+        press(&mut console, &mut scene, KeyCode::Up);
+        assert_eq!(input(&console, &scene), "paint gelb");
+
+        press(&mut console, &mut scene, KeyCode::Down);
+        assert_eq!(input(&console, &scene), "paint grün");
+        // Past the newest entry is the line typed before
+        press(&mut console, &mut scene, KeyCode::Down);
+        assert_eq!(input(&console, &scene), "draft");
+    }
+}
@@ -0,0 +1,165 @@
+use std::str::FromStr;
+
+use crate::prelude::{Id, Scene};
+
+/// Arguments of a console command. Words are positional unless they look like
+/// `key=value`, double quotes keep spaces inside a single word.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Args {
+    positional: Vec<String>,
+    named: Vec<(String, String)>,
+}
+
+impl Args {
+    /// Splits a line into the command's name and its arguments
+    pub fn parse(line: &str) -> Option<(String, Args)> {
+        let mut words = split_words(line).into_iter();
+        let name = words.next()?;
+
+        let mut args = Args::default();
+        for word in words {
+            match word.split_once('=') {
+                Some((key, value)) if !key.is_empty() => {
+                    args.named.push((key.to_string(), value.to_string()));
+                }
+                _ => args.positional.push(word),
+            }
+        }
+        Some((name, args))
+    }
+
+    /// Number of positional arguments
+    pub fn len(&self) -> usize {
+        self.positional.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.positional.is_empty() && self.named.is_empty()
+    }
+
+    pub fn get(&self, index: usize) -> Option<&str> {
+        self.positional.get(index).map(String::as_str)
+    }
+
+    /// Parses a positional argument which has to be there
+    pub fn required<T: FromStr>(&self, index: usize, what: &str) -> Result<T, String> {
+        let word = self.get(index).ok_or_else(|| format!("missing {what}"))?;
+        parse_word(word, what)
+    }
+
+    /// Parses a positional argument, falls back to `default` if it's left out
+    pub fn optional<T: FromStr>(&self, index: usize, what: &str, default: T) -> Result<T, String> {
+        match self.get(index) {
+            Some(word) => parse_word(word, what),
+            None => Ok(default),
+        }
+    }
+
+    pub fn named(&self, key: &str) -> Option<&str> {
+        self.named
+            .iter()
+            .find(|(named_key, _)| named_key == key)
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// Parses a `key=value` argument, falls back to `default` if it's left out
+    pub fn named_or<T: FromStr>(&self, key: &str, default: T) -> Result<T, String> {
+        match self.named(key) {
+            Some(word) => parse_word(word, key),
+            None => Ok(default),
+        }
+    }
+
+    /// Resolves a positional object id. Ids are typed without their generation,
+    /// which is taken from the object in the scene with that value.
+    pub fn object(&self, index: usize, scene: &Scene) -> Result<Id, String> {
+        let value: u64 = self.required(index, "object id")?;
+        scene
+            .objects
+            .ids()
+            .find(|id| id.value == value)
+            .copied()
+            .ok_or_else(|| format!("no object with id {value}"))
+    }
+}
+
+fn parse_word<T: FromStr>(word: &str, what: &str) -> Result<T, String> {
+    word.parse()
+        .map_err(|_| format!("invalid {what}: '{word}'"))
+}
+
+// Whitespace separated, a quoted part may contain whitespace
+fn split_words(line: &str) -> Vec<String> {
+    let mut words = Vec::new();
+    let mut word = String::new();
+    let mut in_quotes = false;
+    let mut has_word = false;
+
+    for symbol in line.chars() {
+        match symbol {
+            '"' => {
+                in_quotes = !in_quotes;
+                has_word = true;
+            }
+            symbol if symbol.is_whitespace() && !in_quotes => {
+                if has_word {
+                    words.push(std::mem::take(&mut word));
+                    has_word = false;
+                }
+            }
+            symbol => {
+                word.push(symbol);
+                has_word = true;
+            }
+        }
+    }
+
+    if has_word {
+        words.push(word);
+    }
+    words
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn splits_the_name_from_the_arguments() {
+        let (name, args) = Args::parse("  spawn food 3  ").unwrap();
+        assert_eq!(name, "spawn");
+        assert_eq!(args.len(), 2);
+        assert_eq!(args.get(0), Some("food"));
+        assert_eq!(args.get(2), None);
+        assert!(Args::parse("   ").is_none());
+    }
+
+    #[test]
+    fn named_and_quoted_arguments() {
+        let (_, args) = Args::parse(r#"say "hello  there" force=true =x"#).unwrap();
+        assert_eq!(args.get(0), Some("hello  there"));
+        assert_eq!(args.get(1), Some("=x"));
+        assert_eq!(args.named("force"), Some("true"));
+        assert_eq!(args.named("missing"), None);
+
+        let (_, args) = Args::parse(r#"say """#).unwrap();
+        assert_eq!(args.get(0), Some(""));
+    }
+
+    #[test]
+    fn typed_arguments() {
+        let (_, args) = Args::parse("step 3 x speed=2.5").unwrap();
+        assert_eq!(args.required::<u32>(0, "ticks"), Ok(3));
+        assert_eq!(
+            args.required::<u32>(1, "ticks"),
+            Err("invalid ticks: 'x'".to_string())
+        );
+        assert_eq!(
+            args.required::<u32>(2, "ticks"),
+            Err("missing ticks".to_string())
+        );
+        assert_eq!(args.optional(2, "ticks", 1), Ok(1));
+        assert_eq!(args.named_or("speed", 1.0), Ok(2.5));
+        assert_eq!(args.named_or("scale", 1.0), Ok(1.0));
+    }
+}
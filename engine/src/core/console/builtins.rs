@@ -0,0 +1,144 @@
+use std::hash::Hash;
+use std::time::Duration;
+
+use super::{Action, Args, CommandResult, ConsoleCommand, Reply};
+use crate::core::inspector::type_name;
+use crate::prelude::{RuntimeCommand, Scene};
+
+// Ids listed per type before the rest is cut off
+const MAX_LISTED: usize = 12;
+
+/// The commands every console starts with
+pub(super) fn commands<K: Eq + Hash + Clone, C>() -> Vec<ConsoleCommand<K, C>> {
+    vec![
+        ConsoleCommand::with_action("help", "help [command]", "Lists the commands", Action::Help),
+        ConsoleCommand::with_action("clear", "clear", "Clears the output", Action::Clear),
+        ConsoleCommand::with_action(
+            "history",
+            "history",
+            "Lists the last commands",
+            Action::History,
+        ),
+        ConsoleCommand::new("pause", "pause", "Pauses or resumes the scene", pause),
+        ConsoleCommand::new("tick", "tick [count]", "Pauses and runs single ticks", tick),
+        ConsoleCommand::new("setspeed", "setspeed <ms>", "Sets the tick rate", set_speed),
+        ConsoleCommand::new(
+            "timescale",
+            "timescale <scale>",
+            "Scales the time",
+            time_scale,
+        ),
+        ConsoleCommand::new(
+            "seed",
+            "seed [seed]",
+            "Shows or sets the particle seed",
+            seed,
+        ),
+        ConsoleCommand::new("list", "list [type]", "Lists the ids of objects", list),
+        ConsoleCommand::with_action(
+            "inspect",
+            "inspect [id|off]",
+            "Opens the inspector, highlight=false",
+            Action::Inspect,
+        ),
+        ConsoleCommand::with_action(
+            "profile",
+            "profile [on|off|clear|export <path>]",
+            "Toggles the profiler overlay (F9)",
+            Action::Profile,
+        )
+        .completions(&["on", "off", "clear", "export"]),
+        ConsoleCommand::new("kill", "kill <id> [force=true]", "Despawns an object", kill),
+    ]
+}
+
+fn pause<K: Eq + Hash + Clone, C>(_: &mut C, scene: &mut Scene, _: &Args) -> CommandResult<K> {
+    let reply = match scene.is_paused() {
+        true => Reply::command(RuntimeCommand::SetTimeScale(1.0)).with_message("resumed"),
+        false => Reply::command(RuntimeCommand::SetTimeScale(0.0)).with_message("paused"),
+    };
+    Ok(reply)
+}
+
+fn tick<K: Eq + Hash + Clone, C>(_: &mut C, scene: &mut Scene, args: &Args) -> CommandResult<K> {
+    let count: u32 = args.optional(0, "tick count", 1)?;
+    scene.set_time_scale(0.0);
+    Ok(Reply::command(RuntimeCommand::Step(count)).with_message(format!("running {count} ticks")))
+}
+
+fn set_speed<K: Eq + Hash + Clone, C>(_: &mut C, _: &mut Scene, args: &Args) -> CommandResult<K> {
+    let millis: u64 = args.required(0, "milliseconds")?;
+    if millis == 0 {
+        return Err("the tick rate has to be at least 1ms".to_string());
+    }
+    Ok(
+        Reply::command(RuntimeCommand::SetTickRate(Duration::from_millis(millis)))
+            .with_message(format!("ticking every {millis}ms")),
+    )
+}
+
+fn time_scale<K: Eq + Hash + Clone, C>(_: &mut C, _: &mut Scene, args: &Args) -> CommandResult<K> {
+    let scale: f32 = args.required(0, "time scale")?;
+    Ok(Reply::command(RuntimeCommand::SetTimeScale(scale))
+        .with_message(format!("time scale {scale}")))
+}
+
+fn seed<K: Eq + Hash + Clone, C>(_: &mut C, scene: &mut Scene, args: &Args) -> CommandResult<K> {
+    match args.get(0) {
+        Some(_) => {
+            let seed: u64 = args.required(0, "seed")?;
+            scene.particles.reseed(seed);
+            Ok(Reply::message(format!("particle seed set to {seed}")))
+        }
+        None => Ok(Reply::message(format!(
+            "particle seed {}",
+            scene.particles.seed()
+        ))),
+    }
+}
+
+fn list<K: Eq + Hash + Clone, C>(_: &mut C, scene: &mut Scene, args: &Args) -> CommandResult<K> {
+    let filter = args.get(0);
+    let mut types: Vec<(String, Vec<u64>)> = Vec::new();
+    for object in scene.objects.values() {
+        let name = type_name(object.as_ref());
+        if filter.is_some_and(|filter| !name.eq_ignore_ascii_case(filter)) {
+            continue;
+        }
+        match types.iter_mut().find(|(type_name, _)| *type_name == name) {
+            Some((_, ids)) => ids.push(object.id().value),
+            None => types.push((name, vec![object.id().value])),
+        }
+    }
+
+    if types.is_empty() {
+        return Err("no objects found".to_string());
+    }
+
+    types.sort();
+    let lines: Vec<String> = types
+        .into_iter()
+        .map(|(name, mut ids)| {
+            ids.sort_unstable();
+            let count = ids.len();
+            let listed: Vec<String> = ids.iter().take(MAX_LISTED).map(u64::to_string).collect();
+            let rest = match count > MAX_LISTED {
+                true => format!(" (+{})", count - MAX_LISTED),
+                false => String::new(),
+            };
+            format!("{name} [{count}]: {}{rest}", listed.join(", "))
+        })
+        .collect();
+    Ok(Reply::message(lines.join("\n")))
+}
+
+fn kill<K: Eq + Hash + Clone, C>(_: &mut C, scene: &mut Scene, args: &Args) -> CommandResult<K> {
+    let id = args.object(0, scene)?;
+    let force: bool = args.named_or("force", false)?;
+    if scene.protected_ids.contains(&id) && !force {
+        return Err(format!("#{} is protected, add force=true", id.value));
+    }
+
+    scene.commands.despawn(id);
+    Ok(Reply::message(format!("despawned #{}", id.value)))
+}
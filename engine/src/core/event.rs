@@ -1,54 +1,144 @@
-use crate::prelude::Scene;
+use crate::prelude::{Scene, Span};
 use std::any::{Any, TypeId};
-use rustc_hash::FxHashMap;
+use std::hash::{Hash, Hasher};
+use rustc_hash::{FxHashMap, FxHashSet, FxHasher};
 
-// TODO - Fix event duplications
-// Self notes - (EventBus, EventKey, HashMap + Vec for O(1))
+/// How many rounds of events pushed by handlers are dispatched within one tick
+pub const DEFAULT_MAX_DEPTH: usize = 8;
 
-pub trait Event: 'static + Any {
+/// Events are `Send`, the scene holding them can be prepared on a loading thread
+pub trait Event: 'static + Any + Send {
     fn as_any(&self) -> &dyn Any;
     fn log_message(&self) -> String {
         format!("Event triggered!")
     }
+
+    /// Events of the same type with the same key are only dispatched once per tick,
+    /// events without a key are never deduplicated
+    fn dedup_key(&self) -> Option<EventKey> {
+        None
+    }
+
+    /// Name of the event's type without its path, the profiler times each type on its own
+    fn event_name(&self) -> &'static str {
+        let path = std::any::type_name::<Self>();
+        let path = path.split('<').next().unwrap_or(path);
+        path.rsplit("::").next().unwrap_or(path)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct EventKey(u64);
+
+impl EventKey {
+    pub fn of<T: Hash>(value: &T) -> Self {
+        let mut hasher = FxHasher::default();
+        value.hash(&mut hasher);
+        Self(hasher.finish())
+    }
+}
+
+/// Whether the handlers after the current one still get the event
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Propagation {
+    Continue,
+    Stop,
 }
 
 pub trait EventHandler<E: Event> {
-    fn handle_event(&mut self, event: &E, scene: &mut Scene);
+    fn handle_event(&mut self, event: &E, scene: &mut Scene) -> Propagation;
 }
 
+type HandlerFn = Box<dyn FnMut(&dyn Any, &mut Scene) -> Propagation>;
+
 pub struct EventManager {
-    handlers: FxHashMap<TypeId, Vec<Box<dyn FnMut(&dyn Any, &mut Scene)>>>,
+    // Sorted from the highest priority down
+    handlers: FxHashMap<TypeId, Vec<(i32, HandlerFn)>>,
+    max_depth: usize,
+    dispatched: Vec<Box<dyn Event>>,
 }
 
 impl EventManager {
     pub fn new() -> Self {
         Self {
             handlers: FxHashMap::default(),
+            max_depth: DEFAULT_MAX_DEPTH,
+            dispatched: Vec::new(),
         }
     }
 
-    pub fn register<E: Event, H: EventHandler<E> + 'static>(&mut self, mut handler: H) {
+    /// Sets how many rounds of events pushed by handlers are dispatched in the same tick.
+    /// Events pushed in the last round wait for the next tick.
+    pub fn set_max_depth(&mut self, max_depth: usize) {
+        self.max_depth = max_depth.max(1);
+    }
+
+    pub fn register<E: Event, H: EventHandler<E> + 'static>(&mut self, handler: H) {
+        self.register_with_priority(handler, 0);
+    }
+
+    /// Registers a handler which runs before the handlers of a lower priority,
+    /// handlers of the same priority run in the order they were registered
+    pub fn register_with_priority<E: Event, H: EventHandler<E> + 'static>(
+        &mut self,
+        mut handler: H,
+        priority: i32,
+    ) {
         let event_type_id = TypeId::of::<E>();
         let entry = self.handlers.entry(event_type_id).or_default();
 
         let callback = move |event_any: &dyn Any, scene: &mut Scene| {
-            if let Some(event) = event_any.downcast_ref::<E>() {
-                handler.handle_event(event, scene);
+            match event_any.downcast_ref::<E>() {
+                Some(event) => handler.handle_event(event, scene),
+                None => Propagation::Continue,
             }
         };
 
-        entry.push(Box::new(callback));
+        let index = entry.partition_point(|(other, _)| *other >= priority);
+        entry.insert(index, (priority, Box::new(callback)));
     }
 
+    /// Dispatches the scene's events. Events pushed by the handlers are dispatched
+    /// in the following rounds of the same tick, up to the max depth.
+    /// A keyed event is dispatched once per tick, which breaks cycles of keyed events,
+    /// handlers pushing unkeyed events back and forth are only stopped by the max depth.
     pub fn dispatch(&mut self, scene: &mut Scene) {
-        let events = std::mem::take(&mut scene.event_bus);
-        for event in &events {
-            let event_any = event.as_any();
-            if let Some(handlers) = self.handlers.get_mut(&event_any.type_id()) {
-                for handler_fn in handlers {
-                    handler_fn(event_any, scene);
+        self.dispatched.clear();
+        let mut seen: FxHashSet<(TypeId, EventKey)> = FxHashSet::default();
+
+        for _ in 0..self.max_depth {
+            if scene.event_bus.is_empty() {
+                break;
+            }
+
+            let events = std::mem::take(&mut scene.event_bus);
+            for event in events {
+                let event_any = event.as_any();
+                let type_id = event_any.type_id();
+
+                // Also guards against cycles of keyed events, unkeyed ones run until the max depth
+                if let Some(key) = event.dedup_key()
+                    && !seen.insert((type_id, key))
+                {
+                    continue;
+                }
+
+                if let Some(handlers) = self.handlers.get_mut(&type_id) {
+                    let span = Span::start(&scene.resources);
+                    for (_, handler_fn) in handlers.iter_mut() {
+                        if handler_fn(event_any, scene) == Propagation::Stop {
+                            break;
+                        }
+                    }
+                    span.end_event(&mut scene.resources, event.event_name());
                 }
+                self.dispatched.push(event);
             }
         }
     }
+
+    /// The events handled by the last dispatch, in order
+    pub fn dispatched(&self) -> &[Box<dyn Event>] {
+        &self.dispatched
+    }
 }
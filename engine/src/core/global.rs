@@ -1,16 +1,32 @@
 use rustc_hash::FxHashMap;
 use std::hash::Hash;
+use std::sync::atomic::{AtomicU32, Ordering};
 
 use rand::Rng;
 
+// Every counter (and every reset) starts a new generation
+static NEXT_GENERATION: AtomicU32 = AtomicU32::new(1);
+
+/// A generation no counter or object slot has used before
+pub(crate) fn next_generation() -> u32 {
+    NEXT_GENERATION.fetch_add(1, Ordering::Relaxed)
+}
+
+/// An object id. The generation tells ids of different counters (or of a
+/// counter before and after a reset) apart, so an id which outlived its
+/// object can never point to a newer object with the same value.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub struct Id {
     pub value: u64,
+    pub generation: u32,
 }
 
 impl Id {
     pub fn new(id: u64) -> Self {
-        Id { value: id }
+        Id {
+            value: id,
+            generation: 0,
+        }
     }
 }
 
@@ -22,18 +38,25 @@ pub struct IdCounter {
 impl IdCounter {
     pub fn new() -> Self {
         Self {
-            counter: Id::new(0),
+            counter: Id {
+                value: 0,
+                generation: next_generation(),
+            },
         }
     }
 
     pub fn next(&mut self) -> Id {
-        let id = self.counter.value;
+        let id = self.counter;
         self.counter.value += 1;
-        Id::new(id)
+        id
     }
 
     pub fn reset(&mut self) {
-        self.counter.value = 0;
+        self.counter = Self::new().counter;
+    }
+
+    pub fn generation(&self) -> u32 {
+        self.counter.generation
     }
 }
 
@@ -53,12 +76,19 @@ impl Position {
     }
 }
 
-#[derive(Debug)]
+/// Packed set of items, iterating walks a plain `Vec`
+#[derive(Debug, Clone)]
 pub struct SlotMap<T: Eq + Hash + Copy> {
     items: Vec<T>,
     map: FxHashMap<T, usize>,
 }
 
+impl<T: Eq + Hash + Copy> Default for SlotMap<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl<T: Eq + Hash + Copy> SlotMap<T> {
     pub fn new() -> Self {
         Self {
@@ -75,6 +105,14 @@ impl<T: Eq + Hash + Copy> SlotMap<T> {
         self.items.is_empty()
     }
 
+    pub fn contains(&self, item: &T) -> bool {
+        self.map.contains_key(item)
+    }
+
+    pub fn iter(&self) -> std::slice::Iter<'_, T> {
+        self.items.iter()
+    }
+
     pub fn insert(&mut self, item: T) -> bool {
         if self.map.contains_key(&item) {
             return false;
@@ -113,3 +151,12 @@ impl<T: Eq + Hash + Copy> SlotMap<T> {
         self.map.clear();
     }
 }
+
+impl<'a, T: Eq + Hash + Copy> IntoIterator for &'a SlotMap<T> {
+    type Item = &'a T;
+    type IntoIter = std::slice::Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.items.iter()
+    }
+}
@@ -0,0 +1,397 @@
+//! Live inspector overlay. Shows what the engine knows about a single object,
+//! refreshed every tick so it can be followed tick by tick while paused.
+
+use crossterm::event::{MouseButton, MouseEvent, MouseEventKind};
+use crossterm::execute;
+use crossterm::style::Color;
+use std::io::stdout;
+
+use crate::prelude::{
+    Border, Canvas, Conflict, Glyph, Handle, Id, OVERLAY_Z_INDEX, Object, ObjectIndex, Position,
+    Rect, Scene, StateChange, Style, TCell, TextBlock, UI_Z_INDEX, Widget,
+};
+
+const WIDTH: u16 = 56;
+
+const INDEXES: [ObjectIndex; 8] = [
+    ObjectIndex::Stateful,
+    ObjectIndex::Destructible,
+    ObjectIndex::Active,
+    ObjectIndex::Spatial,
+    ObjectIndex::Movable,
+    ObjectIndex::Animated,
+    ObjectIndex::Snapshot,
+    ObjectIndex::StatefulSpatial,
+];
+
+const TITLE_COLOR: Color = Color::Rgb {
+    r: 130,
+    g: 170,
+    b: 255,
+};
+const TEXT_COLOR: Color = Color::Rgb {
+    r: 220,
+    g: 220,
+    b: 220,
+};
+const DIM_COLOR: Color = Color::Rgb {
+    r: 140,
+    g: 140,
+    b: 150,
+};
+const PANEL_COLOR: Color = Color::Rgb {
+    r: 20,
+    g: 20,
+    b: 28,
+};
+
+/// The inspected object's cells drawn inverted on top of it
+#[derive(Debug)]
+pub struct Highlight {
+    id: Id,
+    canvas: Canvas,
+}
+
+impl Highlight {
+    pub fn new(id: Id) -> Self {
+        let mut canvas = Canvas::new(id, Rect::new(0, 0, u16::MAX, u16::MAX));
+        canvas.set_z_index(UI_Z_INDEX);
+        Self { id, canvas }
+    }
+
+    pub fn set_cells<'a>(&mut self, t_cells: impl Iterator<Item = &'a TCell>) {
+        self.canvas.clear();
+        for t_cell in t_cells {
+            let glyph = t_cell.style;
+            let inverted = Glyph::new(
+                Some(glyph.bg_clr.unwrap_or(Color::Black)),
+                Some(glyph.fg_clr.unwrap_or(Color::White)),
+                glyph.symbol,
+            );
+            self.canvas.put(t_cell.pos.x, t_cell.pos.y, inverted);
+        }
+    }
+
+    fn get_t_cells(&self) -> Box<dyn Iterator<Item = &TCell> + '_> {
+        self.canvas.t_cells()
+    }
+}
+
+crate::define_object! {
+    struct Highlight,
+    id_field: id,
+    t_cells: custom(get_t_cells),
+    capabilities: {
+        Snapshot {}
+        Destructible {}
+    }
+}
+
+/// A panel on the right of the screen describing the selected object:
+/// the indexes it's in (its capabilities), whether it's protected, its
+/// t_cells, its pending state changes and its `Debug` output.
+/// Objects are selected by id or by clicking one of their cells, which
+/// needs the mouse, so it's captured while the inspector is open.
+pub struct Inspector {
+    target: Option<Id>,
+    is_open: bool,
+    highlight_cells: bool,
+    // First line of the panel's content shown
+    scroll: usize,
+    screen: (u16, u16),
+    border: Option<Handle<Border>>,
+    text: Option<Handle<TextBlock>>,
+    highlight: Option<Handle<Highlight>>,
+}
+
+impl Default for Inspector {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Inspector {
+    pub fn new() -> Self {
+        Self {
+            target: None,
+            is_open: false,
+            highlight_cells: true,
+            scroll: 0,
+            screen: (80, 24),
+            border: None,
+            text: None,
+            highlight: None,
+        }
+    }
+
+    pub fn is_open(&self) -> bool {
+        self.is_open
+    }
+
+    pub fn target(&self) -> Option<Id> {
+        self.target
+    }
+
+    /// Columns the panel takes up on the right of the screen, 0 while it's closed
+    pub fn width(&self) -> u16 {
+        match self.is_open {
+            true => WIDTH.min(self.screen.0 / 2),
+            false => 0,
+        }
+    }
+
+    pub fn open(&mut self, scene: &mut Scene) {
+        if self.is_open {
+            return;
+        }
+        self.is_open = true;
+        self.screen = crossterm::terminal::size().unwrap_or(self.screen);
+        let _ = execute!(stdout(), crossterm::event::EnableMouseCapture);
+
+        self.border = scene.attach_typed(
+            |id| {
+                Border::new(id, Rect::default())
+                    .title("Inspector")
+                    .style(Style::new(Some(TITLE_COLOR), Some(PANEL_COLOR)))
+                    .fill(Style::new(None, Some(PANEL_COLOR)))
+                    .z_index(OVERLAY_Z_INDEX)
+            },
+            Conflict::Ignore,
+        );
+        self.text = scene.attach_typed(
+            |id| TextBlock::new(id, Rect::default()).z_index(OVERLAY_Z_INDEX),
+            Conflict::Ignore,
+        );
+        self.highlight = scene.attach_typed(Highlight::new, Conflict::Ignore);
+        self.place(scene);
+        self.update(scene);
+    }
+
+    pub fn close(&mut self, scene: &mut Scene) {
+        if !self.is_open {
+            return;
+        }
+        self.is_open = false;
+        let _ = execute!(stdout(), crossterm::event::DisableMouseCapture);
+
+        let ids = [
+            self.border.take().map(|handle| handle.id()),
+            self.text.take().map(|handle| handle.id()),
+            self.highlight.take().map(|handle| handle.id()),
+        ];
+        for id in ids.into_iter().flatten() {
+            scene.commands.despawn(id);
+        }
+    }
+
+    /// Attaches an open inspector to a new scene, after the stage's scene was replaced
+    pub fn reattach(&mut self, scene: &mut Scene) {
+        if self.is_open {
+            self.is_open = false;
+            (self.border, self.text, self.highlight) = (None, None, None);
+            self.target = None;
+            self.open(scene);
+        }
+    }
+
+    pub fn select(&mut self, scene: &mut Scene, target: Option<Id>) {
+        self.target = target;
+        self.scroll = 0;
+        self.update(scene);
+    }
+
+    /// Selects the topmost object drawn at a position, widgets are skipped
+    pub fn select_at(&mut self, scene: &mut Scene, pos: Position) -> Option<Id> {
+        let target = scene
+            .objects
+            .values()
+            .flat_map(|object| object.t_cells())
+            .filter(|t_cell| t_cell.pos == pos && t_cell.z_index < UI_Z_INDEX - 1)
+            .max_by_key(|t_cell| t_cell.z_index)
+            .map(|t_cell| t_cell.occ.obj_id);
+        if target.is_some() {
+            self.select(scene, target);
+        }
+        target
+    }
+
+    pub fn set_highlight(&mut self, scene: &mut Scene, highlight_cells: bool) {
+        self.highlight_cells = highlight_cells;
+        self.update(scene);
+    }
+
+    pub fn scroll(&mut self, scene: &mut Scene, lines: isize) {
+        self.scroll = self.scroll.saturating_add_signed(lines);
+        self.update(scene);
+    }
+
+    /// Left clicks select, the wheel scrolls the panel. Returns false for
+    /// mouse events the inspector didn't use.
+    pub fn handle_mouse(&mut self, mouse_event: MouseEvent, scene: &mut Scene) -> bool {
+        if !self.is_open {
+            return false;
+        }
+
+        match mouse_event.kind {
+            MouseEventKind::Down(MouseButton::Left) => {
+                let pos = Position::new(mouse_event.column, mouse_event.row);
+                self.select_at(scene, pos).is_some()
+            }
+            MouseEventKind::ScrollDown => {
+                self.scroll(scene, 3);
+                true
+            }
+            MouseEventKind::ScrollUp => {
+                self.scroll(scene, -3);
+                true
+            }
+            _ => false,
+        }
+    }
+
+    pub fn resize(&mut self, scene: &mut Scene, width: u16, height: u16) {
+        self.screen = (width, height);
+        self.place(scene);
+    }
+
+    /// Describes the target as it is now, call it every tick before the sync
+    pub fn update(&mut self, scene: &mut Scene) {
+        if !self.is_open {
+            return;
+        }
+
+        let object = self.target.and_then(|id| scene.objects.get(&id));
+        let cells: Vec<TCell> = match (object, self.highlight_cells) {
+            (Some(object), true) => object.t_cells().copied().collect(),
+            _ => Vec::new(),
+        };
+        let lines = match (self.target, object) {
+            (Some(id), Some(object)) => describe(scene, id, object),
+            (Some(id), None) => vec![(format!("#{} is gone", id.value), Style::fg(DIM_COLOR))],
+            (None, _) => vec![(
+                "Click an object or 'inspect <id>'".to_string(),
+                Style::fg(DIM_COLOR),
+            )],
+        };
+
+        self.scroll = self.scroll.min(lines.len().saturating_sub(1));
+        let visible = lines.into_iter().skip(self.scroll).collect();
+        if let Some(text) = self
+            .text
+            .and_then(|handle| handle.get_mut(&mut scene.objects))
+        {
+            text.set_lines(visible);
+        }
+        if let Some(highlight) = self
+            .highlight
+            .and_then(|handle| handle.get_mut(&mut scene.objects))
+        {
+            highlight.set_cells(cells.iter());
+        }
+    }
+
+    fn place(&mut self, scene: &mut Scene) {
+        let (width, height) = self.screen;
+        let panel_width = self.width();
+        let frame = Rect::new(width - panel_width, 0, panel_width, height);
+
+        if let Some(border) = self
+            .border
+            .and_then(|handle| handle.get_mut(&mut scene.objects))
+        {
+            border.set_rect(frame);
+        }
+        if let Some(text) = self
+            .text
+            .and_then(|handle| handle.get_mut(&mut scene.objects))
+        {
+            text.set_rect(frame.inner(1));
+        }
+    }
+}
+
+fn describe(scene: &Scene, id: Id, object: &dyn Object) -> Vec<(String, Style)> {
+    let title = Style::fg(TITLE_COLOR);
+    let text = Style::fg(TEXT_COLOR);
+    let dim = Style::fg(DIM_COLOR);
+
+    let indexes: Vec<String> = INDEXES
+        .iter()
+        .filter(|index| {
+            scene
+                .indexes
+                .get(index)
+                .is_some_and(|ids| ids.contains(&id))
+        })
+        .map(|index| format!("{index:?}"))
+        .collect();
+    let protected = match scene.protected_ids.contains(&id) {
+        true => "yes",
+        false => "no",
+    };
+
+    let mut lines = vec![
+        (format!("#{} {}", id.value, type_name(object)), title),
+        (format!("generation {}", id.generation), dim),
+        (format!("protected: {protected}"), text),
+        (format!("in: {}", indexes.join(", ")), text),
+        (String::new(), text),
+    ];
+
+    let mut t_cells: Vec<&TCell> = object.t_cells().collect();
+    t_cells.sort_by_key(|t_cell| (t_cell.pos.y, t_cell.pos.x));
+    lines.push((format!("t_cells ({})", t_cells.len()), title));
+    lines.extend(t_cells.iter().map(|t_cell| {
+        (
+            format!(
+                "  {:>3},{:<3} '{}' z{:<3} #{}",
+                t_cell.pos.x,
+                t_cell.pos.y,
+                t_cell.style.symbol,
+                t_cell.z_index,
+                t_cell.occ.t_cell_id.value
+            ),
+            text,
+        )
+    }));
+    lines.push((String::new(), text));
+
+    let changes: Vec<String> = object
+        .as_stateful()
+        .map(|stateful| stateful.state_changes().map(describe_change).collect())
+        .unwrap_or_default();
+    lines.push((format!("pending changes ({})", changes.len()), title));
+    lines.extend(changes.into_iter().map(|change| (change, text)));
+    lines.push((String::new(), text));
+
+    lines.push(("debug".to_string(), title));
+    let debug = format!("{object:#?}");
+    lines.extend(debug.lines().map(|line| (line.to_string(), dim)));
+    lines
+}
+
+fn describe_change(change: &StateChange) -> String {
+    match change {
+        StateChange::Create { new_t_cell } => format!(
+            "  create {},{} '{}'",
+            new_t_cell.pos.x, new_t_cell.pos.y, new_t_cell.style.symbol
+        ),
+        StateChange::Update { t_cell, init_pos } => format!(
+            "  update {},{} -> {},{} '{}'",
+            init_pos.x, init_pos.y, t_cell.pos.x, t_cell.pos.y, t_cell.style.symbol
+        ),
+        StateChange::Delete { init_pos, .. } => {
+            format!("  delete {},{}", init_pos.x, init_pos.y)
+        }
+    }
+}
+
+/// Name of an object's type, as its `Debug` output starts with it
+pub(crate) fn type_name(object: &dyn Object) -> String {
+    let debug = format!("{object:?}");
+    debug
+        .split(|symbol: char| !(symbol.is_alphanumeric() || symbol == '_'))
+        .next()
+        .unwrap_or_default()
+        .to_string()
+}
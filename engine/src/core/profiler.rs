@@ -0,0 +1,306 @@
+//! Frame profiler. Times each phase of the runtime's loop, keeps the last
+//! samples of every phase for the overlay and a trace of every span, which
+//! can be exported for `chrome://tracing` or Perfetto.
+
+use std::collections::VecDeque;
+use std::fmt::Write as _;
+use std::io;
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+mod overlay;
+
+pub use overlay::ProfilerOverlay;
+
+use crate::prelude::Resources;
+
+/// A whole iteration of the runtime's loop, from the update to the render
+pub const FRAME: &str = "frame";
+/// The logic's update (the input) and the command it returned
+pub const UPDATE: &str = "update";
+/// Movables probing the grid
+pub const PROBE: &str = "probe";
+pub const MAKE_MOVE: &str = "make_move";
+/// Updates of the active objects
+pub const ACTIVE: &str = "active";
+/// The logic's `dispatch_events`, every event type is timed on its own too
+pub const DISPATCH: &str = "dispatch";
+pub const SYNC: &str = "sync";
+/// Applying the spatial state changes to the grid, part of the sync
+pub const GRID_SYNC: &str = "grid sync";
+/// Drawing the frame and flushing it to the terminal
+pub const RENDER: &str = "render";
+
+// The phases in the order they run, the event types follow dispatch
+// and the phases timed by the game come last
+const PHASES: [&str; 9] = [
+    FRAME, UPDATE, PROBE, MAKE_MOVE, ACTIVE, DISPATCH, SYNC, GRID_SYNC, RENDER,
+];
+
+/// Samples kept per phase for the averages and percentiles
+pub const WINDOW: usize = 120;
+// Spans kept for the trace, the oldest are dropped first
+const MAX_SPANS: usize = 200_000;
+
+// Events sort after the engine's phase of the same rank
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum Category {
+    Engine,
+    Event,
+}
+
+impl Category {
+    fn name(self) -> &'static str {
+        match self {
+            Category::Engine => "engine",
+            Category::Event => "event",
+        }
+    }
+}
+
+/// Rolling statistics of a phase over its last `WINDOW` samples
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Stats {
+    pub count: usize,
+    pub average: Duration,
+    pub p50: Duration,
+    pub p95: Duration,
+    pub p99: Duration,
+    pub max: Duration,
+}
+
+/// The last samples of a phase, or of the handlers of an event type
+#[derive(Debug)]
+pub struct Phase {
+    name: &'static str,
+    category: Category,
+    samples: VecDeque<Duration>,
+}
+
+impl Phase {
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+
+    pub fn is_event(&self) -> bool {
+        self.category == Category::Event
+    }
+
+    /// Oldest first
+    pub fn samples(&self) -> &VecDeque<Duration> {
+        &self.samples
+    }
+
+    pub fn stats(&self) -> Stats {
+        let mut sorted: Vec<Duration> = self.samples.iter().copied().collect();
+        if sorted.is_empty() {
+            return Stats::default();
+        }
+        sorted.sort_unstable();
+
+        let count = sorted.len();
+        let total: Duration = sorted.iter().sum();
+        let percentile = |p: f64| sorted[((count - 1) as f64 * p).round() as usize];
+        Stats {
+            count,
+            average: total / count as u32,
+            p50: percentile(0.50),
+            p95: percentile(0.95),
+            p99: percentile(0.99),
+            max: sorted[count - 1],
+        }
+    }
+
+    fn rank(&self) -> (usize, Category, &'static str) {
+        let dispatch = PHASES.iter().position(|phase| *phase == DISPATCH);
+        let index = match self.category {
+            Category::Engine => PHASES.iter().position(|phase| *phase == self.name),
+            Category::Event => dispatch,
+        };
+        (index.unwrap_or(PHASES.len()), self.category, self.name)
+    }
+}
+
+#[derive(Debug)]
+struct TraceSpan {
+    name: &'static str,
+    category: Category,
+    // Since the profiler's epoch
+    start: Duration,
+    duration: Duration,
+}
+
+/// Kept in the resources (`scene.resources`) so it records across stages.
+/// Nothing is timed unless a profiler is there and recording, see `Span`.
+#[derive(Debug)]
+pub struct Profiler {
+    is_recording: bool,
+    epoch: Instant,
+    phases: Vec<Phase>,
+    spans: VecDeque<TraceSpan>,
+}
+
+impl Default for Profiler {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Profiler {
+    pub fn new() -> Self {
+        Self {
+            is_recording: false,
+            epoch: Instant::now(),
+            phases: Vec::new(),
+            spans: VecDeque::new(),
+        }
+    }
+
+    pub fn is_recording(&self) -> bool {
+        self.is_recording
+    }
+
+    pub fn start(&mut self) {
+        self.is_recording = true;
+    }
+
+    pub fn stop(&mut self) {
+        self.is_recording = false;
+    }
+
+    /// Drops the samples and the trace
+    pub fn clear(&mut self) {
+        self.epoch = Instant::now();
+        self.phases.clear();
+        self.spans.clear();
+    }
+
+    /// Spans in the trace
+    pub fn span_count(&self) -> usize {
+        self.spans.len()
+    }
+
+    /// Every phase recorded so far, in the order they run
+    pub fn phases(&self) -> Vec<&Phase> {
+        let mut phases: Vec<&Phase> = self.phases.iter().collect();
+        phases.sort_by_key(|phase| phase.rank());
+        phases
+    }
+
+    pub fn phase(&self, name: &str) -> Option<&Phase> {
+        self.phases
+            .iter()
+            .find(|phase| phase.category == Category::Engine && phase.name == name)
+    }
+
+    /// Writes the trace to a file, returns how many spans were written
+    pub fn export_trace(&self, path: impl AsRef<Path>) -> io::Result<usize> {
+        std::fs::write(path, self.trace_json())?;
+        Ok(self.spans.len())
+    }
+
+    /// The trace in the Chrome trace event format, one complete event per span
+    pub fn trace_json(&self) -> String {
+        let mut json = String::from("{\"displayTimeUnit\":\"ms\",\"traceEvents\":[\n");
+        json.push_str(
+            "{\"name\":\"process_name\",\"ph\":\"M\",\"pid\":1,\"tid\":1,\"args\":{\"name\":\"engine\"}}",
+        );
+        for span in &self.spans {
+            let _ = write!(
+                json,
+                ",\n{{\"name\":\"{}\",\"cat\":\"{}\",\"ph\":\"X\",\"ts\":{:.3},\"dur\":{:.3},\"pid\":1,\"tid\":1}}",
+                escape(span.name),
+                span.category.name(),
+                micros(span.start),
+                micros(span.duration),
+            );
+        }
+        json.push_str("\n]}\n");
+        json
+    }
+
+    fn push(&mut self, name: &'static str, category: Category, start: Instant, end: Instant) {
+        let duration = end.saturating_duration_since(start);
+        let index = match self
+            .phases
+            .iter()
+            .position(|phase| phase.category == category && phase.name == name)
+        {
+            Some(index) => index,
+            None => {
+                self.phases.push(Phase {
+                    name,
+                    category,
+                    samples: VecDeque::with_capacity(WINDOW),
+                });
+                self.phases.len() - 1
+            }
+        };
+
+        let samples = &mut self.phases[index].samples;
+        samples.push_back(duration);
+        if samples.len() > WINDOW {
+            samples.pop_front();
+        }
+
+        self.spans.push_back(TraceSpan {
+            name,
+            category,
+            start: start.saturating_duration_since(self.epoch),
+            duration,
+        });
+        if self.spans.len() > MAX_SPANS {
+            self.spans.pop_front();
+        }
+    }
+}
+
+/// Times a phase from `start` to `end`, e.g. `Span::start(&scene.resources)`
+/// then `span.end(&mut scene.resources, "ai")`. It does nothing unless the
+/// resources hold a recording profiler.
+#[must_use]
+pub struct Span(Option<Instant>);
+
+impl Span {
+    pub fn start(resources: &Resources) -> Self {
+        let is_recording = resources
+            .get::<Profiler>()
+            .is_some_and(Profiler::is_recording);
+        Self(is_recording.then(Instant::now))
+    }
+
+    pub fn end(self, resources: &mut Resources, phase: &'static str) {
+        self.finish(resources, phase, Category::Engine);
+    }
+
+    /// Ends the span of an event's handlers, `name` is the event's type
+    pub fn end_event(self, resources: &mut Resources, name: &'static str) {
+        self.finish(resources, name, Category::Event);
+    }
+
+    fn finish(self, resources: &mut Resources, name: &'static str, category: Category) {
+        if let (Some(start), Some(profiler)) = (self.0, resources.get_mut::<Profiler>()) {
+            profiler.push(name, category, start, Instant::now());
+        }
+    }
+}
+
+/// Short form of a duration, e.g. `850.0µs` or `12.40ms`
+pub fn format_duration(duration: Duration) -> String {
+    let micros = micros(duration);
+    if micros < 1_000.0 {
+        format!("{micros:.1}µs")
+    } else if micros < 1_000_000.0 {
+        format!("{:.2}ms", micros / 1_000.0)
+    } else {
+        format!("{:.2}s", micros / 1_000_000.0)
+    }
+}
+
+fn micros(duration: Duration) -> f64 {
+    duration.as_secs_f64() * 1_000_000.0
+}
+
+fn escape(text: &str) -> String {
+    text.replace('\\', "\\\\").replace('"', "\\\"")
+}
@@ -0,0 +1,266 @@
+use crossterm::style::Color;
+use std::time::Duration;
+
+use super::{Phase, Profiler, WINDOW, format_duration};
+use crate::prelude::{
+    Border, Conflict, Handle, OVERLAY_Z_INDEX, Rect, Scene, Style, TextBlock, Widget,
+};
+
+const MAX_WIDTH: u16 = 100;
+// Updates between two refreshes, so the overlay doesn't redraw every tick
+const REFRESH_TICKS: u32 = 10;
+// Name and the four durations before the sparkline
+const NAME_WIDTH: usize = 18;
+const STATS_WIDTH: usize = NAME_WIDTH + 4 * 10;
+
+const SPARKS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+
+const TITLE_COLOR: Color = Color::Rgb {
+    r: 130,
+    g: 170,
+    b: 255,
+};
+const TEXT_COLOR: Color = Color::Rgb {
+    r: 220,
+    g: 220,
+    b: 220,
+};
+const DIM_COLOR: Color = Color::Rgb {
+    r: 140,
+    g: 140,
+    b: 150,
+};
+const PANEL_COLOR: Color = Color::Rgb {
+    r: 20,
+    g: 20,
+    b: 28,
+};
+
+/// A panel at the bottom of the screen with the average and the percentiles
+/// of every phase the profiler timed, and a sparkline of their last samples.
+/// Opening it starts the profiler, closing it leaves the profiler recording.
+pub struct ProfilerOverlay {
+    is_open: bool,
+    screen: (u16, u16),
+    rows: usize,
+    ticks: u32,
+    border: Option<Handle<Border>>,
+    text: Option<Handle<TextBlock>>,
+}
+
+impl Default for ProfilerOverlay {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ProfilerOverlay {
+    pub fn new() -> Self {
+        Self {
+            is_open: false,
+            screen: (80, 24),
+            rows: 0,
+            ticks: 0,
+            border: None,
+            text: None,
+        }
+    }
+
+    pub fn is_open(&self) -> bool {
+        self.is_open
+    }
+
+    pub fn open(&mut self, scene: &mut Scene) {
+        if self.is_open {
+            return;
+        }
+        self.is_open = true;
+        scene.resources.get_or_insert_with(Profiler::new).start();
+
+        self.border = scene.attach_typed(
+            |id| {
+                Border::new(id, Rect::default())
+                    .title("Profiler")
+                    .style(Style::new(Some(TITLE_COLOR), Some(PANEL_COLOR)))
+                    .fill(Style::new(None, Some(PANEL_COLOR)))
+                    .z_index(OVERLAY_Z_INDEX)
+            },
+            Conflict::Ignore,
+        );
+        self.text = scene.attach_typed(
+            |id| TextBlock::new(id, Rect::default()).z_index(OVERLAY_Z_INDEX),
+            Conflict::Ignore,
+        );
+        self.refresh(scene);
+        self.place(scene);
+    }
+
+    pub fn close(&mut self, scene: &mut Scene) {
+        if !self.is_open {
+            return;
+        }
+        self.is_open = false;
+
+        let ids = [
+            self.border.take().map(|handle| handle.id()),
+            self.text.take().map(|handle| handle.id()),
+        ];
+        for id in ids.into_iter().flatten() {
+            scene.commands.despawn(id);
+        }
+    }
+
+    pub fn toggle(&mut self, scene: &mut Scene) {
+        match self.is_open {
+            true => self.close(scene),
+            false => self.open(scene),
+        }
+    }
+
+    /// Attaches an open overlay to a new scene, after the stage's scene was replaced
+    pub fn reattach(&mut self, scene: &mut Scene) {
+        if self.is_open {
+            self.is_open = false;
+            (self.border, self.text) = (None, None);
+            self.open(scene);
+        }
+    }
+
+    /// The space the overlay can take up, the console leaves out the inspector
+    pub fn resize(&mut self, scene: &mut Scene, width: u16, height: u16) {
+        self.screen = (width, height);
+        self.refresh(scene);
+        self.place(scene);
+    }
+
+    /// Refreshes the panel every few calls, call it every tick
+    pub fn update(&mut self, scene: &mut Scene) {
+        if !self.is_open {
+            return;
+        }
+        self.ticks = (self.ticks + 1) % REFRESH_TICKS;
+        if self.ticks == 0 {
+            self.refresh(scene);
+        }
+    }
+
+    fn refresh(&mut self, scene: &mut Scene) {
+        if !self.is_open {
+            return;
+        }
+
+        let width = self.screen.0.min(MAX_WIDTH).saturating_sub(2) as usize;
+        let (title, lines) = match scene.resources.get::<Profiler>() {
+            Some(profiler) => {
+                let title = match profiler.is_recording() {
+                    true => "Profiler",
+                    false => "Profiler (stopped)",
+                };
+                (title, describe(profiler, width))
+            }
+            None => ("Profiler", Vec::new()),
+        };
+
+        if let Some(border) = self
+            .border
+            .and_then(|handle| handle.get_mut(&mut scene.objects))
+        {
+            border.set_title(Some(title.to_string()));
+        }
+        let rows = lines.len();
+        if let Some(text) = self
+            .text
+            .and_then(|handle| handle.get_mut(&mut scene.objects))
+        {
+            text.set_lines(lines);
+        }
+
+        // Grows with the phases recorded
+        if rows != self.rows {
+            self.rows = rows;
+            self.place(scene);
+        }
+    }
+
+    fn place(&mut self, scene: &mut Scene) {
+        let (width, height) = self.screen;
+        let panel_height = (self.rows as u16 + 2).min(height);
+        let frame = Rect::new(0, height - panel_height, width.min(MAX_WIDTH), panel_height);
+
+        if let Some(border) = self
+            .border
+            .and_then(|handle| handle.get_mut(&mut scene.objects))
+        {
+            border.set_rect(frame);
+        }
+        if let Some(text) = self
+            .text
+            .and_then(|handle| handle.get_mut(&mut scene.objects))
+        {
+            text.set_rect(frame.inner(1));
+        }
+    }
+}
+
+fn describe(profiler: &Profiler, width: usize) -> Vec<(String, Style)> {
+    let phases = profiler.phases();
+    if phases.is_empty() {
+        return vec![(
+            "No samples yet, the scene has to tick".to_string(),
+            Style::fg(DIM_COLOR),
+        )];
+    }
+
+    let spark_width = width.saturating_sub(STATS_WIDTH + 1).min(WINDOW);
+    let header = format!(
+        "{:<NAME_WIDTH$}{:>10}{:>10}{:>10}{:>10} last {spark_width}",
+        "phase", "avg", "p50", "p95", "p99",
+    );
+    let mut lines = vec![(header, Style::fg(TITLE_COLOR))];
+    lines.extend(phases.into_iter().map(|phase| {
+        let style = match phase.is_event() {
+            true => Style::fg(DIM_COLOR),
+            false => Style::fg(TEXT_COLOR),
+        };
+        (describe_phase(phase, spark_width), style)
+    }));
+    lines
+}
+
+fn describe_phase(phase: &Phase, spark_width: usize) -> String {
+    // Event types are nested under dispatch
+    let name = match phase.is_event() {
+        true => format!("  {}", phase.name()),
+        false => phase.name().to_string(),
+    };
+    let name: String = name.chars().take(NAME_WIDTH - 1).collect();
+    let stats = phase.stats();
+    let samples: Vec<Duration> = phase.samples().iter().copied().collect();
+    let recent = &samples[samples.len().saturating_sub(spark_width)..];
+
+    format!(
+        "{name:<NAME_WIDTH$}{:>10}{:>10}{:>10}{:>10} {}",
+        format_duration(stats.average),
+        format_duration(stats.p50),
+        format_duration(stats.p95),
+        format_duration(stats.p99),
+        sparkline(recent),
+    )
+}
+
+/// One bar per sample, scaled to the longest of them
+fn sparkline(samples: &[Duration]) -> String {
+    let max = samples.iter().max().copied().unwrap_or_default();
+    if max.is_zero() {
+        return SPARKS[0].to_string().repeat(samples.len());
+    }
+
+    let top = (SPARKS.len() - 1) as f64;
+    samples
+        .iter()
+        .map(|sample| {
+            let level = sample.as_secs_f64() / max.as_secs_f64() * top;
+            SPARKS[level.round() as usize]
+        })
+        .collect()
+}
@@ -0,0 +1,124 @@
+use rustc_hash::FxHashMap;
+use std::any::{Any, TypeId};
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// A resource which is written to the save directory, one plain-text file per resource
+pub trait Persistent: Any + Send + Sized {
+    /// File name of the resource in the save directory (without extension)
+    const KEY: &'static str;
+
+    fn save(&self) -> String;
+    fn load(source: &str) -> Option<Self>;
+}
+
+type SaveFn = fn(&dyn Any) -> Option<String>;
+
+/// Values shared by every stage, stored by type.
+/// The `RuntimeManager` owns them and lends them to the running stage's scene
+/// (`scene.resources`), so they survive stage switches and logic swaps.
+#[derive(Default)]
+pub struct Resources {
+    values: FxHashMap<TypeId, Box<dyn Any + Send>>,
+    persistent: FxHashMap<TypeId, (&'static str, SaveFn)>,
+    save_dir: Option<PathBuf>,
+}
+
+impl Resources {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    /// Returns the resource of the same type it replaced
+    pub fn insert<T: Any + Send>(&mut self, value: T) -> Option<T> {
+        self.values
+            .insert(TypeId::of::<T>(), Box::new(value))
+            .and_then(|old| old.downcast().ok())
+            .map(|old| *old)
+    }
+
+    pub fn get<T: Any + Send>(&self) -> Option<&T> {
+        self.values.get(&TypeId::of::<T>())?.downcast_ref()
+    }
+
+    pub fn get_mut<T: Any + Send>(&mut self) -> Option<&mut T> {
+        self.values.get_mut(&TypeId::of::<T>())?.downcast_mut()
+    }
+
+    pub fn get_or_insert_with<T: Any + Send>(&mut self, create_fn: impl FnOnce() -> T) -> &mut T {
+        self.values
+            .entry(TypeId::of::<T>())
+            .or_insert_with(|| Box::new(create_fn()))
+            .downcast_mut()
+            .expect("Resource stored under a foreign type id!")
+    }
+
+    pub fn contains<T: Any + Send>(&self) -> bool {
+        self.values.contains_key(&TypeId::of::<T>())
+    }
+
+    /// Also stops persisting the resource
+    pub fn remove<T: Any + Send>(&mut self) -> Option<T> {
+        self.persistent.remove(&TypeId::of::<T>());
+        self.values
+            .remove(&TypeId::of::<T>())
+            .and_then(|value| value.downcast().ok())
+            .map(|value| *value)
+    }
+
+    pub fn set_save_dir(&mut self, dir: impl Into<PathBuf>) {
+        self.save_dir = Some(dir.into());
+    }
+
+    pub fn save_dir(&self) -> Option<&Path> {
+        self.save_dir.as_deref()
+    }
+
+    /// Inserts a resource which is saved with `save`. A previously saved
+    /// version in the save directory is loaded instead of the default if it parses.
+    pub fn insert_persistent<T: Persistent>(&mut self, default: T) -> &mut T {
+        let saved = self
+            .save_path(T::KEY)
+            .and_then(|path| std::fs::read_to_string(path).ok())
+            .and_then(|source| T::load(&source));
+
+        let save_fn: SaveFn = |value| value.downcast_ref::<T>().map(T::save);
+        self.persistent.insert(TypeId::of::<T>(), (T::KEY, save_fn));
+        self.insert(saved.unwrap_or(default));
+        self.get_mut::<T>().expect("Resource was just inserted!")
+    }
+
+    /// Writes every persistent resource to the save directory, does nothing without one
+    pub fn save(&self) -> io::Result<()> {
+        let Some(dir) = &self.save_dir else {
+            return Ok(());
+        };
+        std::fs::create_dir_all(dir)?;
+
+        for (type_id, (key, save_fn)) in &self.persistent {
+            let Some(source) = self
+                .values
+                .get(type_id)
+                .and_then(|value| save_fn(value.as_ref()))
+            else {
+                continue;
+            };
+            std::fs::write(dir.join(format!("{}.txt", key)), source)?;
+        }
+        Ok(())
+    }
+
+    fn save_path(&self, key: &str) -> Option<PathBuf> {
+        self.save_dir
+            .as_ref()
+            .map(|dir| dir.join(format!("{}.txt", key)))
+    }
+}
@@ -5,26 +5,52 @@ pub mod renderer;
 pub mod stage;
 
 use super::ManagerDirective;
-use crate::prelude::{Logic, ObjectIndex, Scene, Stage};
+use crate::core::profiler;
+use crate::prelude::{Logic, ObjectIndex, Scene, SceneLoader, Span, Stage};
 use renderer::Renderer;
 
 pub enum RuntimeCommand<K: Eq + Hash + Clone> {
     ReplaceScene(Box<Scene>),
     ReplaceLogic(Box<dyn Logic<K>>),
+    /// Also exits the stages suspended under the current one, the stack starts over
     SwitchStage(K),
+    PushStage(K),
+    PopStage,
+    /// Pushes the loading stage while the loader prepares a scene for the current stage,
+    /// which replaces its scene once the loading stage pops. Popping early cancels the load.
+    LoadScene {
+        loader: SceneLoader,
+        loading_stage: K,
+    },
     SetTickRate(Duration),
+    SetTimeScale(f32),
+    /// Runs this many ticks of a paused scene, one per update
+    Step(u32),
     Refresh,
+    /// Renders the whole frame again after this update, e.g. after the terminal was resized
+    Redraw,
     Reset,
     Skip,
     Kill,
     None,
 }
 
+/// How the runtime got to run a stage, decides which `Logic` hook is called
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StageEntry {
+    Enter,
+    Resume,
+    Refresh,
+}
+
 pub struct Runtime {
     pub tick_rate: Duration,
     last_update: Instant,
     pub renderer: Renderer,
     skip_tick: bool,
+    redraw: bool,
+    // Ticks left to run while the scene is paused
+    steps: u32,
 }
 
 impl Runtime {
@@ -34,48 +60,111 @@ impl Runtime {
             last_update: Instant::now(),
             renderer: Renderer::new(),
             skip_tick: false,
+            redraw: false,
+            steps: 0,
         }
     }
 
-    pub fn run<K: Eq + Hash + Clone>(&mut self, stage: &mut Stage<K>) -> ManagerDirective<K> {
-        if !stage.is_init {
-            self.initialize(stage);
-        } else {
-            self.refresh(stage);
+    pub fn run<K: Eq + Hash + Clone>(
+        &mut self,
+        stage: &mut Stage<K>,
+        entry: StageEntry,
+    ) -> ManagerDirective<K> {
+        if let Some(tick_rate) = stage.tick_rate {
+            self.tick_rate = tick_rate;
         }
+        self.steps = 0;
+        self.enter(stage, entry);
 
         self.last_update = Instant::now();
         loop {
             let now = Instant::now();
             let delta = now.duration_since(self.last_update);
 
-            if delta >= self.tick_rate {
+            if delta >= self.interval(&stage.scene) {
                 self.last_update = now;
+                let frame = Span::start(&stage.scene.resources);
+                let span = Span::start(&stage.scene.resources);
                 let command = stage.logic.update(&mut stage.scene);
-                if let Some(directive) = self.execute_command(command, stage) {
+                let directive = self.execute_command(command, stage);
+                span.end(&mut stage.scene.resources, profiler::UPDATE);
+                if let Some(directive) = directive {
                     return directive;
                 } else if self.skip_tick {
                     self.skip_tick = false;
                     continue;
                 }
 
-                self.tick(stage);
+                // A paused scene keeps taking input and rendering what the logic changed
+                if !stage.scene.is_paused() {
+                    self.tick(stage);
+                } else if self.steps > 0 {
+                    self.steps -= 1;
+                    self.tick(stage);
+                } else {
+                    Self::apply_commands(&mut stage.scene);
+                }
+
+                let span = Span::start(&stage.scene.resources);
                 stage.scene.sync();
-                self.renderer.partial_render(&mut stage.scene);
+                span.end(&mut stage.scene.resources, profiler::SYNC);
+
+                let span = Span::start(&stage.scene.resources);
+                if std::mem::take(&mut self.redraw) {
+                    self.renderer.full_render(&stage.scene);
+                } else {
+                    self.renderer.partial_render(&mut stage.scene);
+                }
+                span.end(&mut stage.scene.resources, profiler::RENDER);
+                frame.end(&mut stage.scene.resources, profiler::FRAME);
             }
             std::thread::sleep(Duration::from_millis(1));
         }
     }
 
-    fn initialize<K: Eq + Hash + Clone>(&mut self, stage: &mut Stage<K>) {
-        stage.logic.init(&mut stage.scene);
+    /// Real time between two updates, a scaled scene ticks slower or faster
+    /// while its timers still advance by the full tick rate of game time
+    fn interval(&self, scene: &Scene) -> Duration {
+        let time_scale = scene.time_scale();
+        if time_scale > 0.0 {
+            Duration::try_from_secs_f64(self.tick_rate.as_secs_f64() / time_scale as f64)
+                .unwrap_or(Duration::MAX)
+        } else {
+            self.tick_rate
+        }
+    }
+
+    fn enter<K: Eq + Hash + Clone>(&mut self, stage: &mut Stage<K>, entry: StageEntry) {
+        let was_init = stage.is_init;
+        if was_init {
+            stage.logic.refresh(&mut stage.scene);
+        } else {
+            stage.logic.init(&mut stage.scene);
+            stage.is_init = true;
+        }
+
+        match entry {
+            StageEntry::Enter => stage.logic.on_enter(&mut stage.scene),
+            StageEntry::Resume => stage.logic.on_resume(&mut stage.scene),
+            StageEntry::Refresh => {}
+        }
+
+        Self::apply_commands(&mut stage.scene);
         stage.scene.sync();
+        if was_init {
+            stage.scene.resync_grid();
+        }
+
+        match &stage.transition {
+            Some(transition) => self.renderer.play_transition(transition, &stage.scene),
+            None => self.renderer.discard_outgoing(),
+        }
         self.renderer.full_render(&mut stage.scene);
-        stage.is_init = true;
     }
 
     fn refresh<K: Eq + Hash + Clone>(&mut self, stage: &mut Stage<K>) {
         stage.logic.refresh(&mut stage.scene);
+        Self::apply_commands(&mut stage.scene);
         stage.scene.sync();
         stage.scene.resync_grid();
         self.renderer.full_render(&mut stage.scene);
@@ -83,38 +172,30 @@ impl Runtime {
 
     fn tick<K: Eq + Hash + Clone>(&mut self, stage: &mut Stage<K>) {
         // Gets events from movables (collisions)
-        if let Some(grid) = &mut stage.scene.spatial_grid {
-            if let Some(movable_ids) = stage.scene.indexes.get(&ObjectIndex::Movable) {
-                let future_moves = movable_ids
-                    .iter()
-                    .filter_map(|id| {
-                        stage
-                            .scene
-                            .objects
-                            .get(id)
-                            .and_then(|obj| obj.as_movable())
-                            .map(|movable| (*id, movable))
-                    })
-                    .flat_map(|(id, movable)| movable.probe_move().map(move |pos| (id, pos)));
-
-                let mut probe_map = grid.probe_moves(future_moves);
-
-                for id in movable_ids {
-                    let probe = probe_map.remove(id);
-                    if let Some(object) = stage.scene.objects.get_mut(id) {
-                        if let Some(movable) = object.as_movable_mut() {
-                            stage.scene.event_bus.extend(movable.make_move(probe));
-                        }
-                    }
+        let move_events = stage.scene.move_objects();
+        stage.scene.event_bus.extend(move_events);
+
+        // Gets events and commands from active objects
+        let scene = &mut stage.scene;
+        let span = Span::start(&scene.resources);
+        if let Some(active_ids) = scene.indexes.get(&ObjectIndex::Active) {
+            for id in active_ids {
+                if let Some(active) = scene
+                    .objects
+                    .get_mut(id)
+                    .and_then(|obj| obj.as_active_mut())
+                {
+                    scene.event_bus.extend(active.update(&mut scene.commands));
                 }
             }
         }
+        span.end(&mut scene.resources, profiler::ACTIVE);
 
-        // Gets events from active objects
-        let active_events = stage
+        // Plays animations of animated objects
+        let animation_events = stage
             .scene
             .indexes
-            .get(&ObjectIndex::Active)
+            .get(&ObjectIndex::Animated)
             .into_iter()
             .flat_map(|hash_set| hash_set.iter())
             .filter_map(|id| {
@@ -122,14 +203,27 @@ impl Runtime {
                     .scene
                     .objects
                     .get_mut(id)
-                    .and_then(|obj| obj.as_active_mut())
-                    .map(|active| active.update())
+                    .and_then(|obj| obj.as_animated_mut())
+                    .map(|animated| animated.animate())
             })
             .flatten();
 
-        stage.scene.event_bus.extend(active_events);
+        stage.scene.event_bus.extend(animation_events);
+
+        stage.scene.particles.update();
+        stage.scene.update_timers(self.tick_rate);
 
+        let span = Span::start(&stage.scene.resources);
         stage.logic.dispatch_events(&mut stage.scene);
+        span.end(&mut stage.scene.resources, profiler::DISPATCH);
+        Self::apply_commands(&mut stage.scene);
+    }
+
+    // Conflicting commands are announced as events on the next dispatch
+    fn apply_commands(scene: &mut Scene) {
+        for conflict in scene.apply_commands() {
+            scene.push_event(conflict);
+        }
     }
 
     fn execute_command<K: Eq + Hash + Clone>(
@@ -139,7 +233,8 @@ impl Runtime {
     ) -> Option<ManagerDirective<K>> {
         match command {
             RuntimeCommand::ReplaceScene(scene) => {
-                let old_scene = stage.replace_scene(scene);
+                let mut old_scene = stage.replace_scene(scene);
+                std::mem::swap(&mut old_scene.resources, &mut stage.scene.resources);
                 stage.logic.collect_old_stage(Some(old_scene), None);
                 stage.logic.refresh(&mut stage.scene);
             }
@@ -149,8 +244,20 @@ impl Runtime {
                 stage.logic.refresh(&mut stage.scene);
             }
             RuntimeCommand::SwitchStage(key) => return Some(ManagerDirective::Switch(key)),
-            RuntimeCommand::SetTickRate(tick_rate) => self.tick_rate = tick_rate,
+            RuntimeCommand::PushStage(key) => return Some(ManagerDirective::Push(key)),
+            RuntimeCommand::PopStage => return Some(ManagerDirective::Pop),
+            RuntimeCommand::LoadScene {
+                loader,
+                loading_stage,
+            } => return Some(ManagerDirective::Load(loader, loading_stage)),
+            RuntimeCommand::SetTickRate(tick_rate) => {
+                self.tick_rate = tick_rate;
+                stage.tick_rate = Some(tick_rate);
+            }
+            RuntimeCommand::SetTimeScale(time_scale) => stage.scene.set_time_scale(time_scale),
+            RuntimeCommand::Step(steps) => self.steps = steps,
             RuntimeCommand::Refresh => self.refresh(stage),
+            RuntimeCommand::Redraw => self.redraw = true,
             RuntimeCommand::Reset => {
                 stage.scene.clear();
                 stage.is_init = false;
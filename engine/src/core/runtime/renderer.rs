@@ -1,17 +1,28 @@
 pub mod buffer;
+pub mod transition;
 
-use rustc_hash::FxHashSet;
-use crate::prelude::{ObjectIndex, Scene, StateChange};
+use rustc_hash::FxHashMap;
+use std::time::Duration;
+
+use crate::core::global::SlotMap;
+use crate::prelude::{Glyph, ObjectIndex, Position, Scene, StateChange};
 use buffer::{Buffer, Operation};
+use transition::{Screen, Transition};
+
+// How often a transition redraws
+const TRANSITION_FRAME: Duration = Duration::from_millis(30);
 
 pub struct Renderer {
     buffer: Buffer,
+    // The last frame of the stage that was left, the start of the next transition
+    outgoing: Option<Screen>,
 }
 
 impl Renderer {
     pub fn new() -> Self {
         Self {
             buffer: Buffer::new(),
+            outgoing: None,
         }
     }
 
@@ -21,18 +32,89 @@ impl Renderer {
 
     pub fn full_render(&mut self, scene: &Scene) {
         self.buffer.clear();
+        Self::draw_scene(scene, |pos, glyph, z_index| {
+            self.buffer.upsert(pos, Operation::Draw { glyph, z_index });
+        });
+        self.draw_particles(scene);
+        self.buffer.fill_backdrop();
+        self.buffer.flush();
+    }
+
+    /// Freezes the given scenes (bottom first) behind the rendered one,
+    /// an upper scene covers the scenes below it
+    pub fn set_backdrop<'a>(&mut self, scenes: impl IntoIterator<Item = &'a Scene>) {
+        let mut backdrop = Screen::default();
+        for scene in scenes {
+            backdrop.extend(Self::screen_of(scene));
+        }
+        self.buffer.set_backdrop(backdrop);
+    }
+
+    pub fn clear_backdrop(&mut self) {
+        self.buffer.set_backdrop(Screen::default());
+    }
+
+    /// What a full render of the scene shows (without particles), including the backdrop
+    pub fn capture(&self, scene: &Scene) -> Screen {
+        let mut screen = self.buffer.backdrop().clone();
+        screen.extend(Self::screen_of(scene));
+        screen
+    }
+
+    /// Keeps the frame of a stage which is being left for the next transition
+    pub fn capture_outgoing(&mut self, scene: &Scene) {
+        self.outgoing = Some(self.capture(scene));
+    }
 
+    /// Plays the transition from the captured outgoing frame to the scene's,
+    /// only blocks if there is an outgoing frame
+    pub fn play_transition(&mut self, transition: &Transition, scene: &Scene) {
+        let Some(from) = self.outgoing.take() else {
+            return;
+        };
+        let to = self.capture(scene);
+
+        let frames = (transition.duration.as_millis() / TRANSITION_FRAME.as_millis()).max(1);
+        for frame in 1..=frames {
+            let progress = frame as f32 / frames as f32;
+            for (pos, glyph) in transition.compose(&from, &to, progress) {
+                let operation = match glyph {
+                    Some(glyph) => Operation::Draw { glyph, z_index: 0 },
+                    None => Operation::Clear,
+                };
+                self.buffer.upsert(pos, operation);
+            }
+            self.buffer.flush();
+            std::thread::sleep(TRANSITION_FRAME);
+        }
+    }
+
+    /// Drops the outgoing frame if the entered stage has no transition
+    pub fn discard_outgoing(&mut self) {
+        self.outgoing = None;
+    }
+
+    fn screen_of(scene: &Scene) -> Screen {
+        let mut layer: FxHashMap<Position, (Glyph, u8)> = FxHashMap::default();
+        Self::draw_scene(scene, |pos, glyph, z_index| {
+            let top = layer.entry(pos).or_insert((glyph, z_index));
+            if z_index > top.1 {
+                *top = (glyph, z_index);
+            }
+        });
+        layer
+            .into_iter()
+            .map(|(pos, (glyph, _))| (pos, glyph))
+            .collect()
+    }
+
+    // Passes everything a full render draws (without particles) to `draw`
+    fn draw_scene(scene: &Scene, mut draw: impl FnMut(Position, Glyph, u8)) {
         // Draws grid and spatial objects
         if let Some(grid) = &scene.spatial_grid {
             // Draws border
             for (pos, glyph) in grid.get_border() {
-                self.buffer.upsert(
-                    pos,
-                    Operation::Draw {
-                        glyph,
-                        z_index: 255,
-                    },
-                );
+                draw(pos, glyph, 255);
             }
 
             // Draws grid cells and their occupants
@@ -42,20 +124,14 @@ impl Renderer {
                     let world_pos = grid.pos_to_world(grid_pos);
                     if let Some(cell) = grid.get_cell(&world_pos) {
                         let (glyph, z_index) = cell.top_glyph_and_z();
-                        self.buffer.upsert(
-                            world_pos,
-                            Operation::Draw {
-                                glyph: *glyph,
-                                z_index,
-                            },
-                        );
+                        draw(world_pos, *glyph, z_index);
                     }
                 }
             }
         }
 
         // Draws non-spatial objects (like UI)
-        let empty_set = FxHashSet::default();
+        let empty_set = SlotMap::new();
         let spatial_ids = scene
             .indexes
             .get(&ObjectIndex::Spatial)
@@ -66,17 +142,9 @@ impl Renderer {
             }
 
             for t_cell in object.t_cells() {
-                self.buffer.upsert(
-                    t_cell.pos,
-                    Operation::Draw {
-                        glyph: t_cell.style,
-                        z_index: t_cell.z_index,
-                    },
-                );
+                draw(t_cell.pos, t_cell.style, t_cell.z_index);
             }
         }
-
-        self.buffer.flush();
     }
 
     pub fn partial_render(&mut self, scene: &Scene) {
@@ -172,6 +240,55 @@ impl Renderer {
             }
         }
 
+        // Restores the cells left behind by particles
+        for pos in scene.particles.vacated() {
+            self.restore_cell(scene, *pos);
+        }
+        self.draw_particles(scene);
+
         self.buffer.flush();
     }
+
+    /// Redraws a cell a particle covered. With a grid, particles are only drawn
+    /// inside of it, so positions outside of it were never drawn and are left alone.
+    /// Without a grid there's nothing below the particle and the position is cleared.
+    fn restore_cell(&mut self, scene: &Scene, pos: Position) {
+        let Some(grid) = &scene.spatial_grid else {
+            self.buffer.upsert(pos, Operation::Clear);
+            return;
+        };
+
+        if let Some(cell) = grid.get_cell(&pos) {
+            let (glyph, z_index) = cell.top_glyph_and_z();
+            self.buffer.upsert(
+                pos,
+                Operation::Draw {
+                    glyph: *glyph,
+                    z_index,
+                },
+            );
+        }
+    }
+
+    // Particles are redrawn every tick as the grid doesn't know about them.
+    // With a grid they are clipped to it and hide behind higher cells.
+    fn draw_particles(&mut self, scene: &Scene) {
+        for (pos, glyph, z_index) in scene.particles.frame() {
+            if let Some(grid) = &scene.spatial_grid {
+                let Some(cell) = grid.get_cell(&pos) else {
+                    continue;
+                };
+                let (top_glyph, top_z) = cell.top_glyph_and_z();
+                self.buffer.upsert(
+                    pos,
+                    Operation::Draw {
+                        glyph: *top_glyph,
+                        z_index: top_z,
+                    },
+                );
+            }
+
+            self.buffer.upsert(pos, Operation::Draw { glyph, z_index });
+        }
+    }
 }
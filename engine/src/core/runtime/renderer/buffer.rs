@@ -16,6 +16,8 @@ pub enum Operation {
 pub struct Buffer {
     stdout: Stdout,
     frame_buffer: FxHashMap<Position, Operation>,
+    // Frozen glyphs of the stages below, shown wherever the top stage clears or draws nothing
+    backdrop: FxHashMap<Position, Glyph>,
 }
 
 impl Buffer {
@@ -28,6 +30,7 @@ impl Buffer {
         Self {
             stdout,
             frame_buffer: FxHashMap::default(),
+            backdrop: FxHashMap::default(),
         }
     }
 
@@ -57,9 +60,28 @@ impl Buffer {
         }
     }
 
+    pub fn set_backdrop(&mut self, backdrop: FxHashMap<Position, Glyph>) {
+        self.backdrop = backdrop;
+    }
+
+    pub fn backdrop(&self) -> &FxHashMap<Position, Glyph> {
+        &self.backdrop
+    }
+
+    /// Queues the whole backdrop below what was already drawn, used by full renders
+    pub fn fill_backdrop(&mut self) {
+        for (pos, glyph) in &self.backdrop {
+            self.frame_buffer.entry(*pos).or_insert(Operation::Draw {
+                glyph: *glyph,
+                z_index: 0,
+            });
+        }
+    }
+
     pub fn kill(&mut self) {
         terminal::disable_raw_mode().unwrap();
-        execute!(self.stdout, cursor::Show).unwrap();
+        // Captured by debug overlays like the inspector
+        execute!(self.stdout, crossterm::event::DisableMouseCapture, cursor::Show).unwrap();
     }
 
     pub fn clear(&mut self) {
@@ -72,7 +94,10 @@ impl Buffer {
     pub fn flush(&mut self) {
         for (pos, operation) in self.frame_buffer.drain() {
             match operation {
-                Operation::Clear => Self::clear_glyph(&mut self.stdout, pos),
+                Operation::Clear => match self.backdrop.get(&pos) {
+                    Some(glyph) => Self::draw_glyph(&mut self.stdout, *glyph, pos),
+                    None => Self::clear_glyph(&mut self.stdout, pos),
+                },
                 Operation::Draw { glyph, .. } => Self::draw_glyph(&mut self.stdout, glyph, pos),
             };
         }
@@ -0,0 +1,118 @@
+use crossterm::style::Color;
+use rustc_hash::{FxHashMap, FxHasher};
+use std::hash::{Hash, Hasher};
+use std::time::Duration;
+
+use crate::prelude::{Easing, Glyph, Position, Tweenable};
+
+/// Everything drawn on screen, by position
+pub type Screen = FxHashMap<Position, Glyph>;
+
+// Stand-in for a missing glyph or colour, so fades have something to blend with
+const BLANK: Color = Color::Rgb { r: 0, g: 0, b: 0 };
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TransitionEffect {
+    /// Blends the colours of both frames, the symbols switch halfway
+    Fade,
+    /// Reveals the incoming frame column by column from the left
+    Wipe,
+    /// Reveals the incoming frame cell by cell in a scattered order
+    Dissolve,
+}
+
+/// Played when a stage is entered or resumed, from the outgoing stage's frame
+/// to the incoming one's. The runtime blocks for its duration.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Transition {
+    pub effect: TransitionEffect,
+    pub duration: Duration,
+    pub easing: Easing,
+}
+
+impl Transition {
+    pub fn new(effect: TransitionEffect, duration: Duration) -> Self {
+        Self {
+            effect,
+            duration,
+            easing: Easing::Linear,
+        }
+    }
+
+    pub fn fade(duration: Duration) -> Self {
+        Self::new(TransitionEffect::Fade, duration)
+    }
+
+    pub fn wipe(duration: Duration) -> Self {
+        Self::new(TransitionEffect::Wipe, duration)
+    }
+
+    pub fn dissolve(duration: Duration) -> Self {
+        Self::new(TransitionEffect::Dissolve, duration)
+    }
+
+    pub fn easing(mut self, easing: Easing) -> Self {
+        self.easing = easing;
+        self
+    }
+
+    /// The composed frame at `progress` (`0.0..=1.0`), `None` clears the position
+    pub fn compose(
+        &self,
+        from: &Screen,
+        to: &Screen,
+        progress: f32,
+    ) -> Vec<(Position, Option<Glyph>)> {
+        let t = self.easing.apply(progress);
+        let (min_x, max_x) = from
+            .keys()
+            .chain(to.keys())
+            .fold((u16::MAX, 0), |(min, max), pos| (min.min(pos.x), max.max(pos.x)));
+        let width = max_x.saturating_sub(min_x) as f32 + 1.0;
+
+        let mut positions: Vec<Position> = from.keys().chain(to.keys()).copied().collect();
+        positions.sort_unstable_by_key(|pos| (pos.y, pos.x));
+        positions.dedup();
+
+        positions
+            .into_iter()
+            .map(|pos| {
+                let (old, new) = (from.get(&pos), to.get(&pos));
+                let glyph = match self.effect {
+                    TransitionEffect::Fade => Some(fade(old, new, t)),
+                    TransitionEffect::Wipe => {
+                        let column = (pos.x - min_x) as f32 + 1.0;
+                        if column <= t * width { new } else { old }.copied()
+                    }
+                    TransitionEffect::Dissolve => {
+                        if threshold(pos) < t { new } else { old }.copied()
+                    }
+                };
+                (pos, glyph)
+            })
+            .collect()
+    }
+}
+
+fn fade(old: Option<&Glyph>, new: Option<&Glyph>, t: f32) -> Glyph {
+    let blank = Glyph::new(None, None, ' ');
+    let (old, new) = (old.unwrap_or(&blank), new.unwrap_or(&blank));
+
+    let blend = |from: Option<Color>, to: Option<Color>| match (from, to) {
+        (None, None) => None,
+        _ => Some(from.unwrap_or(BLANK).lerp(&to.unwrap_or(BLANK), t)),
+    };
+
+    Glyph::new(
+        blend(old.fg_clr, new.fg_clr),
+        blend(old.bg_clr, new.bg_clr),
+        if t < 0.5 { old.symbol } else { new.symbol },
+    )
+}
+
+// Stable per position, so a cell flips exactly once during a dissolve
+fn threshold(pos: Position) -> f32 {
+    let mut hasher = FxHasher::default();
+    pos.hash(&mut hasher);
+    (hasher.finish() % 1024) as f32 / 1024.0
+}
@@ -1,5 +1,9 @@
 use std::hash::Hash;
+use std::time::Duration;
 
+use super::renderer::transition::Transition;
+
+pub mod loader;
 pub mod logic;
 pub mod scene;
 
@@ -10,6 +14,12 @@ pub struct Stage<K: Eq + Hash + Clone> {
     pub logic: Box<dyn Logic<K>>,
     pub scene: Box<Scene>,
     pub is_init: bool,
+    /// Kept across stage switches, `None` keeps the runtime's current tick rate
+    pub tick_rate: Option<Duration>,
+    /// Stays rendered (frozen) underneath the stages pushed on top of it
+    pub keep_rendering: bool,
+    /// Played from the previous stage's frame whenever this stage is entered or resumed
+    pub transition: Option<Transition>,
 }
 
 impl<K: Eq + Hash + Clone> Stage<K> {
@@ -18,9 +28,27 @@ impl<K: Eq + Hash + Clone> Stage<K> {
             logic,
             scene: Box::new(Scene::new()),
             is_init: false,
+            tick_rate: None,
+            keep_rendering: false,
+            transition: None,
         }
     }
 
+    pub fn with_tick_rate(mut self, tick_rate: Duration) -> Self {
+        self.tick_rate = Some(tick_rate);
+        self
+    }
+
+    pub fn with_keep_rendering(mut self, keep_rendering: bool) -> Self {
+        self.keep_rendering = keep_rendering;
+        self
+    }
+
+    pub fn with_transition(mut self, transition: Transition) -> Self {
+        self.transition = Some(transition);
+        self
+    }
+
     pub fn replace_scene(&mut self, scene: Box<Scene>) -> Box<Scene> {
         let old_scene = std::mem::replace(&mut self.scene, scene);
         old_scene
@@ -0,0 +1,97 @@
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, AtomicU32, Ordering};
+use std::sync::mpsc::{self, Receiver};
+use std::thread;
+
+use crate::prelude::Scene;
+
+/// Progress of a load, shared between the loading thread and the stages.
+/// Cloning it is cheap, every clone refers to the same load.
+#[derive(Debug, Clone, Default)]
+pub struct LoadStatus {
+    // f32 bits of `0.0..=1.0`
+    progress: Arc<AtomicU32>,
+    is_cancelled: Arc<AtomicBool>,
+    is_finished: Arc<AtomicBool>,
+}
+
+impl LoadStatus {
+    pub fn progress(&self) -> f32 {
+        f32::from_bits(self.progress.load(Ordering::Relaxed))
+    }
+
+    /// Called by the build function, clamped to `0.0..=1.0`
+    pub fn set_progress(&self, progress: f32) {
+        let progress = progress.clamp(0.0, 1.0);
+        self.progress.store(progress.to_bits(), Ordering::Relaxed);
+    }
+
+    /// Asks the build function to stop, its scene is dropped either way
+    pub fn cancel(&self) {
+        self.is_cancelled.store(true, Ordering::Relaxed);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.is_cancelled.load(Ordering::Relaxed)
+    }
+
+    /// The build function returned (or panicked)
+    pub fn is_finished(&self) -> bool {
+        self.is_finished.load(Ordering::Acquire)
+    }
+}
+
+/// Prepares a scene on a background thread, the build function is given a
+/// fresh scene and the status to report its progress and check for cancellation.
+pub struct SceneLoader {
+    status: LoadStatus,
+    receiver: Receiver<Box<Scene>>,
+}
+
+impl SceneLoader {
+    pub fn spawn<F>(build_fn: F) -> Self
+    where
+        F: FnOnce(&mut Scene, &LoadStatus) + Send + 'static,
+    {
+        let status = LoadStatus::default();
+        let (sender, receiver) = mpsc::channel();
+
+        let thread_status = status.clone();
+        thread::spawn(move || {
+            // Marks the load as finished even if the build function panics
+            struct Finish(LoadStatus);
+            impl Drop for Finish {
+                fn drop(&mut self) {
+                    self.0.is_finished.store(true, Ordering::Release);
+                }
+            }
+            let finish = Finish(thread_status);
+
+            let mut scene = Box::new(Scene::new());
+            build_fn(&mut scene, &finish.0);
+            if !finish.0.is_cancelled() {
+                finish.0.set_progress(1.0);
+                let _ = sender.send(scene);
+            }
+        });
+
+        Self { status, receiver }
+    }
+
+    pub fn status(&self) -> &LoadStatus {
+        &self.status
+    }
+
+    pub fn cancel(&self) {
+        self.status.cancel();
+    }
+
+    /// Takes the prepared scene once it's ready, cancelled loads never return one
+    pub fn poll(&mut self) -> Option<Box<Scene>> {
+        if self.status.is_cancelled() {
+            return None;
+        }
+
+        self.receiver.try_recv().ok()
+    }
+}
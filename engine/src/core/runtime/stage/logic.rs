@@ -6,6 +6,19 @@ pub trait Logic<K: Eq + Hash + Clone> {
     fn init(&mut self, scene: &mut Scene);
     fn update(&mut self, scene: &mut Scene) -> RuntimeCommand<K>;
     fn refresh(&mut self, _scene: &mut Scene) {}
+
+    /// Called once the stage became the active one through a switch or a push (after `init` or `refresh`)
+    fn on_enter(&mut self, _scene: &mut Scene) {}
+
+    /// Called when the stage is left through a switch, a pop or a kill
+    fn on_exit(&mut self, _scene: &mut Scene) {}
+
+    /// Called when another stage is pushed on top of this one
+    fn on_suspend(&mut self, _scene: &mut Scene) {}
+
+    /// Called once the stage on top was popped and this one is active again (after `refresh`)
+    fn on_resume(&mut self, _scene: &mut Scene) {}
+
     fn collect_old_stage(
         &mut self,
         _old_scene: Option<Box<Scene>>,
@@ -1,12 +1,32 @@
 use rustc_hash::{FxHashMap, FxHashSet};
+use std::any::TypeId;
+use std::time::Duration;
 
+pub mod commands;
 pub mod global_state;
 pub mod grid;
+pub mod handle;
 pub mod object;
+pub mod particles;
+pub mod snapshot;
+#[cfg(feature = "parallel")]
+mod parallel;
+pub mod store;
+pub mod timers;
 
+use commands::{Command, CommandConflict, Commands};
 use global_state::GlobalState;
+use handle::Handle;
+use particles::ParticleSystem;
+use snapshot::Snapshots;
+use store::ObjectStore;
+use timers::{Scheduler, TimerAction};
 
-use crate::prelude::{Event, Id, IdCounter, Object, SpatialGrid, StateChange};
+use crate::core::global::SlotMap;
+use crate::core::profiler;
+use crate::prelude::{
+    Event, Id, IdCounter, Object, ObjectExt, Resources, SpatialGrid, Span, State, StateChange,
+};
 
 #[derive(Debug, Eq, PartialEq, Hash, Clone, Copy)]
 pub enum ObjectIndex {
@@ -15,6 +35,8 @@ pub enum ObjectIndex {
     Active,
     Spatial,
     Movable,
+    Animated,
+    Snapshot,
     StatefulSpatial,
 }
 
@@ -26,24 +48,41 @@ pub enum Conflict {
 
 pub struct Scene {
     pub id_counter: IdCounter,
-    pub objects: FxHashMap<Id, Box<dyn Object>>,
-    pub indexes: FxHashMap<ObjectIndex, FxHashSet<Id>>,
+    pub objects: ObjectStore,
+    pub indexes: FxHashMap<ObjectIndex, SlotMap<Id>>,
+    pub type_indexes: FxHashMap<TypeId, SlotMap<Id>>,
     pub protected_ids: FxHashSet<Id>,
     pub spatial_grid: Option<SpatialGrid>,
     pub global_state: GlobalState,
+    pub snapshots: Snapshots,
     pub event_bus: Vec<Box<dyn Event>>,
+    pub commands: Commands,
+    pub timers: Scheduler,
+    /// Shared with every stage, only present while the scene's stage runs
+    pub resources: Resources,
+    pub particles: ParticleSystem,
+    parallel: bool,
+    time_scale: f32,
 }
 
 impl Scene {
     pub fn new() -> Self {
         Self {
             id_counter: IdCounter::new(),
-            objects: FxHashMap::default(),
+            objects: ObjectStore::new(),
             indexes: FxHashMap::default(),
+            type_indexes: FxHashMap::default(),
             protected_ids: FxHashSet::default(),
             spatial_grid: None,
             global_state: GlobalState::new(),
+            snapshots: Snapshots::new(),
             event_bus: Vec::new(),
+            commands: Commands::new(),
+            timers: Scheduler::new(),
+            resources: Resources::new(),
+            particles: ParticleSystem::new(),
+            parallel: false,
+            time_scale: 1.0,
         }
     }
 
@@ -51,10 +90,44 @@ impl Scene {
         self.id_counter.reset();
         self.objects.clear();
         self.indexes.clear();
+        self.type_indexes.clear();
         self.protected_ids.clear();
         self.spatial_grid = None;
         self.global_state.clear();
+        self.snapshots.clear();
         self.event_bus.clear();
+        self.commands.clear();
+        self.timers.clear();
+        self.particles.clear();
+    }
+
+    /// Whether moves and syncs are processed across threads,
+    /// always false without the `parallel` feature
+    pub fn is_parallel(&self) -> bool {
+        cfg!(feature = "parallel") && self.parallel
+    }
+
+    /// Turns the parallel processing of the scene on or off
+    pub fn set_parallel(&mut self, parallel: bool) {
+        self.parallel = parallel;
+    }
+
+    pub fn time_scale(&self) -> f32 {
+        self.time_scale
+    }
+
+    /// Scales how fast the scene ticks: 0 pauses it, 0.5 is slow motion and 2 fast-forward.
+    /// A paused scene still gets its logic updates (input) and is rendered.
+    pub fn set_time_scale(&mut self, time_scale: f32) {
+        self.time_scale = if time_scale.is_finite() {
+            time_scale.max(0.0)
+        } else {
+            1.0
+        };
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.time_scale == 0.0
     }
 
     pub fn attach_grid(&mut self, grid: SpatialGrid) {
@@ -66,7 +139,7 @@ impl Scene {
     where
         F: FnOnce(Id) -> Box<dyn Object>,
     {
-        let new_id = self.id_counter.next();
+        let new_id = self.objects.next_id();
         let new_object = create_fn(new_id);
 
         // Special logic for grid objects <3
@@ -74,7 +147,7 @@ impl Scene {
             // Probes grid for conflicts
             let mut collisions: FxHashSet<Id> = FxHashSet::default();
             if let Some(grid) = &self.spatial_grid {
-                if !grid.check_bounds(&new_object) {
+                if !grid.check_bounds(&new_object) || grid.is_obstructed(new_object.as_ref()) {
                     return None;
                 }
                 collisions = grid.probe_object(&new_object);
@@ -103,54 +176,276 @@ impl Scene {
             }
 
             if let Some(grid) = &mut self.spatial_grid {
-                grid.add_object(&new_object);
+                grid.add_object(new_object.as_ref());
             }
         }
 
         self.index_object(&new_object, true);
         self.global_state.state.changes.extend(new_object.init());
+        if new_object.as_snapshot().is_some() {
+            self.snapshots.record(new_object.as_ref());
+        }
         self.objects.insert(new_id, new_object);
         Some(new_id)
     }
 
+    /// Attaches an object and returns a typed handle to it
+    pub fn attach_typed<T, F>(&mut self, create_fn: F, on_conflict: Conflict) -> Option<Handle<T>>
+    where
+        T: Object + 'static,
+        F: FnOnce(Id) -> T,
+    {
+        self.attach_object(|id| Box::new(create_fn(id)), on_conflict)
+            .map(Handle::new)
+    }
+
     pub fn remove_object(&mut self, id: &Id) {
         if let Some(mut object) = self.objects.remove(id) {
             self.protected_ids.remove(&id);
-            if let Some(destructable) = object.as_destructible_mut() {
+            // A snapshot holds what was last synced, which is what has to be deleted
+            if let Some(deletes) = self.snapshots.remove(id) {
+                self.global_state.state.changes.extend(deletes);
+            } else if let Some(destructable) = object.as_destructible_mut() {
                 self.global_state.state.changes.extend(destructable.kill());
             }
             self.index_object(&object, false);
         }
     }
 
+    /// Whether the object is of type `T`, answered by the type index
+    pub fn is<T: Object + 'static>(&self, id: &Id) -> bool {
+        self.type_indexes
+            .get(&TypeId::of::<T>())
+            .is_some_and(|ids| ids.contains(id))
+    }
+
+    /// A typed handle for an id, if the object is of type `T`
+    pub fn handle<T: Object + 'static>(&self, id: Id) -> Option<Handle<T>> {
+        self.is::<T>(&id).then(|| Handle::new(id))
+    }
+
+    pub fn get<T: Object + 'static>(&self, handle: Handle<T>) -> Option<&T> {
+        handle.get(&self.objects)
+    }
+
+    pub fn get_mut<T: Object + 'static>(&mut self, handle: Handle<T>) -> Option<&mut T> {
+        handle.get_mut(&mut self.objects)
+    }
+
+    /// Two different objects mutably at once
+    pub fn get_pair_mut<A, B>(&mut self, a: Handle<A>, b: Handle<B>) -> Option<(&mut A, &mut B)>
+    where
+        A: Object + 'static,
+        B: Object + 'static,
+    {
+        let (obj_a, obj_b) = self.objects.get_pair_mut(&a.id(), &b.id())?;
+        Some((obj_a.get_mut::<A>()?, obj_b.get_mut::<B>()?))
+    }
+
+    pub fn count<T: Object + 'static>(&self) -> usize {
+        self.type_indexes
+            .get(&TypeId::of::<T>())
+            .map_or(0, |ids| ids.len())
+    }
+
+    /// Iterates all objects of type `T`
+    pub fn query<T: Object + 'static>(&self) -> impl Iterator<Item = (Handle<T>, &T)> {
+        self.type_indexes
+            .get(&TypeId::of::<T>())
+            .into_iter()
+            .flatten()
+            .filter_map(|id| {
+                let object = self.objects.get(id)?.get::<T>()?;
+                Some((Handle::new(*id), object))
+            })
+    }
+
+    /// Iterates all objects of type `T` mutably
+    pub fn query_mut<T: Object + 'static>(&mut self) -> impl Iterator<Item = (Handle<T>, &mut T)> {
+        let ids = self
+            .type_indexes
+            .get(&TypeId::of::<T>())
+            .into_iter()
+            .flatten();
+        self.objects
+            .get_many_mut(ids)
+            .into_iter()
+            .filter_map(|(id, object)| Some((Handle::new(id), object.get_mut::<T>()?)))
+    }
+
+    /// Iterates the objects of type `T` which also have a capability
+    pub fn query_with<T: Object + 'static>(
+        &self,
+        index: ObjectIndex,
+    ) -> impl Iterator<Item = (Handle<T>, &T)> {
+        let capable = self.indexes.get(&index);
+        self.query::<T>()
+            .filter(move |(handle, _)| capable.is_some_and(|ids| ids.contains(&handle.id())))
+    }
+
+    /// Iterates the objects of type `T` which also have a capability mutably
+    pub fn query_with_mut<T: Object + 'static>(
+        &mut self,
+        index: ObjectIndex,
+    ) -> impl Iterator<Item = (Handle<T>, &mut T)> {
+        let capable = self.indexes.get(&index);
+        let ids = self
+            .type_indexes
+            .get(&TypeId::of::<T>())
+            .into_iter()
+            .flatten()
+            .filter(move |id| capable.is_some_and(|ids| ids.contains(id)));
+        self.objects
+            .get_many_mut(ids)
+            .into_iter()
+            .filter_map(|(id, object)| Some((Handle::new(id), object.get_mut::<T>()?)))
+    }
+
     pub fn push_event<E: Event>(&mut self, event: E) {
         self.event_bus.push(Box::new(event));
     }
 
+    /// Advances the timers by one tick and fires the due ones
+    pub fn update_timers(&mut self, delta: Duration) {
+        for handle in self.timers.advance(delta) {
+            let Some(mut action) = self.timers.take_action(handle) else {
+                continue;
+            };
+
+            match &mut action {
+                TimerAction::Event(make_event) => self.event_bus.push(make_event()),
+                TimerAction::Callback(callback) => callback(self),
+            }
+            self.timers.finish(handle, action);
+        }
+    }
+
+    /// Applies the queued commands in order and returns the ones which couldn't be applied
+    pub fn apply_commands(&mut self) -> Vec<CommandConflict> {
+        let mut conflicts = Vec::new();
+        let mut despawned: FxHashSet<Id> = FxHashSet::default();
+
+        let commands: Vec<Command> = self.commands.drain().collect();
+        for command in commands {
+            match command {
+                Command::Spawn {
+                    create,
+                    on_conflict,
+                } => {
+                    if self.attach_object(create, on_conflict).is_none() {
+                        conflicts.push(CommandConflict::SpawnBlocked);
+                    }
+                }
+                Command::Despawn(id) => {
+                    if despawned.contains(&id) {
+                        conflicts.push(CommandConflict::AlreadyDespawned(id));
+                    } else if !self.objects.contains_key(&id) {
+                        conflicts.push(CommandConflict::Missing(id));
+                    } else {
+                        self.remove_object(&id);
+                        despawned.insert(id);
+                    }
+                }
+                Command::AttachGrid(grid) => self.attach_grid(grid),
+                Command::SetProtected { id, is_protected } => {
+                    if self.objects.contains_key(&id) {
+                        self.set_overwrite_exemption(id, is_protected);
+                    } else {
+                        conflicts.push(CommandConflict::Missing(id));
+                    }
+                }
+            }
+        }
+        conflicts
+    }
+
+    /// Probes every movable against the grid and makes its move.
+    /// Returns the events of the moves in index order.
+    pub fn move_objects(&mut self) -> Vec<Box<dyn Event>> {
+        #[cfg(feature = "parallel")]
+        if self.parallel {
+            return parallel::move_objects(self);
+        }
+
+        let mut events = Vec::new();
+        let (Some(grid), Some(movable_ids)) =
+            (&self.spatial_grid, self.indexes.get(&ObjectIndex::Movable))
+        else {
+            return events;
+        };
+
+        let future_moves = movable_ids
+            .iter()
+            .filter_map(|id| {
+                self.objects
+                    .get(id)
+                    .and_then(|obj| obj.as_movable())
+                    .map(|movable| (*id, movable))
+            })
+            .flat_map(|(id, movable)| movable.probe_move().map(move |pos| (id, pos)));
+
+        let span = Span::start(&self.resources);
+        let mut probe_map = grid.probe_moves(future_moves);
+        span.end(&mut self.resources, profiler::PROBE);
+
+        let span = Span::start(&self.resources);
+        for id in movable_ids {
+            let probe = probe_map.remove(id);
+            if let Some(movable) = self
+                .objects
+                .get_mut(id)
+                .and_then(|obj| obj.as_movable_mut())
+            {
+                events.extend(movable.make_move(probe));
+            }
+        }
+        span.end(&mut self.resources, profiler::MAKE_MOVE);
+        events
+    }
+
     pub fn sync(&mut self) {
         self.global_state.filtered.clear();
+        let parallel = self.is_parallel();
 
         let stateful_ids = self.indexes.get(&ObjectIndex::Stateful);
         let spatial_ids = self.indexes.get(&ObjectIndex::StatefulSpatial);
+        let snapshot_ids = self.indexes.get(&ObjectIndex::Snapshot);
+        let is_spatial = |id: &Id| {
+            self.indexes
+                .get(&ObjectIndex::Spatial)
+                .is_some_and(|spatial| spatial.contains(id))
+        };
 
         // Spatial states are processed first to protect
         // and filter the grid from non-spatial updates
         if let Some(ids) = spatial_ids {
-            for id in ids {
-                if let Some(object) = self.objects.get_mut(id) {
-                    if let Some(stateful) = object.as_stateful_mut() {
-                        self.global_state
-                            .state
-                            .changes
-                            .extend(stateful.state_mut().drain_changes());
-                    }
-                }
-            }
+            drain_states(
+                &mut self.objects,
+                &mut self.global_state.state,
+                ids.iter(),
+                parallel,
+            );
+        }
 
+        let mut spatial_snapshots = snapshot_ids
+            .into_iter()
+            .flatten()
+            .filter(|id| is_spatial(id))
+            .peekable();
+        let has_spatial_snapshots = spatial_snapshots.peek().is_some();
+        diff_snapshots(
+            &mut self.snapshots,
+            &self.objects,
+            &mut self.global_state.state,
+            spatial_snapshots,
+        );
+
+        if spatial_ids.is_some() || has_spatial_snapshots {
             // Process spatial states
             self.global_state.process(true);
 
             if let Some(grid) = &mut self.spatial_grid {
+                let span = Span::start(&self.resources);
                 // Keeps only valid grid changes
                 self.global_state
                     .filtered
@@ -171,38 +466,29 @@ impl Scene {
                             }
                         }
                     });
+                span.end(&mut self.resources, profiler::GRID_SYNC);
             }
         }
 
         // Non-spatial states
-        match (stateful_ids, spatial_ids) {
-            (Some(stateful), Some(spatial)) => {
-                for id in stateful.difference(spatial) {
-                    if let Some(object) = self.objects.get_mut(id) {
-                        if let Some(stateful) = object.as_stateful_mut() {
-                            self.global_state
-                                .state
-                                .changes
-                                .extend(stateful.state_mut().drain_changes());
-                        }
-                    }
-                }
-            }
-            (Some(stateful), None) => {
-                for id in stateful {
-                    if let Some(object) = self.objects.get_mut(id) {
-                        if let Some(stateful) = object.as_stateful_mut() {
-                            self.global_state
-                                .state
-                                .changes
-                                .extend(stateful.state_mut().drain_changes());
-                        }
-                    }
-                }
-            }
-            _ => (),
+        if let Some(stateful) = stateful_ids {
+            drain_states(
+                &mut self.objects,
+                &mut self.global_state.state,
+                stateful
+                    .iter()
+                    .filter(|id| spatial_ids.is_none_or(|spatial| !spatial.contains(id))),
+                parallel,
+            );
         }
 
+        diff_snapshots(
+            &mut self.snapshots,
+            &self.objects,
+            &mut self.global_state.state,
+            snapshot_ids.into_iter().flatten().filter(|id| !is_spatial(id)),
+        );
+
         self.global_state.process(false);
     }
 
@@ -241,22 +527,66 @@ impl Scene {
             (object.as_active().is_some(), ObjectIndex::Active),
             (object.as_spatial().is_some(), ObjectIndex::Spatial),
             (object.as_movable().is_some(), ObjectIndex::Movable),
+            (object.as_animated().is_some(), ObjectIndex::Animated),
+            (object.as_snapshot().is_some(), ObjectIndex::Snapshot),
             (
                 object.as_stateful().is_some() && object.as_spatial().is_some(),
                 ObjectIndex::StatefulSpatial,
             ),
         ];
 
+        let type_id = object.as_any().type_id();
+        if is_insert {
+            self.type_indexes.entry(type_id).or_default().insert(id);
+        } else if let Some(slot_map) = self.type_indexes.get_mut(&type_id) {
+            slot_map.remove(&id);
+        }
+
         for (has_trait, index) in checks {
             if has_trait {
                 if is_insert {
                     self.indexes.entry(index).or_default().insert(id);
                 } else {
-                    if let Some(hash_set) = self.indexes.get_mut(&index) {
-                        hash_set.remove(&id);
+                    if let Some(slot_map) = self.indexes.get_mut(&index) {
+                        slot_map.remove(&id);
                     }
                 }
             }
         }
     }
 }
+
+// Moves the changes of stateful objects into the global state, in index order
+fn drain_states<'a>(
+    objects: &mut ObjectStore,
+    global: &mut State,
+    ids: impl Iterator<Item = &'a Id>,
+    parallel: bool,
+) {
+    #[cfg(feature = "parallel")]
+    if parallel {
+        return parallel::drain_states(objects, global, ids);
+    }
+    #[cfg(not(feature = "parallel"))]
+    let _ = parallel;
+
+    for id in ids {
+        if let Some(stateful) = objects.get_mut(id).and_then(|obj| obj.as_stateful_mut()) {
+            global.changes.extend(stateful.state_mut().drain_changes());
+        }
+    }
+}
+
+// Records the changes of snapshot objects since their last sync
+fn diff_snapshots<'a>(
+    snapshots: &mut Snapshots,
+    objects: &ObjectStore,
+    global: &mut State,
+    ids: impl Iterator<Item = &'a Id>,
+) {
+    for id in ids {
+        if let Some(object) = objects.get(id) {
+            snapshots.diff(object, global);
+        }
+    }
+}
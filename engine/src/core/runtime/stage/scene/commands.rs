@@ -0,0 +1,92 @@
+use std::any::Any;
+
+use crate::prelude::{Conflict, Event, Id, Object, SpatialGrid};
+
+type SpawnFn = Box<dyn FnOnce(Id) -> Box<dyn Object> + Send>;
+
+pub enum Command {
+    Spawn { create: SpawnFn, on_conflict: Conflict },
+    Despawn(Id),
+    AttachGrid(SpatialGrid),
+    SetProtected { id: Id, is_protected: bool },
+}
+
+/// Scene mutations queued while the scene is being processed.
+/// Event handlers and active objects queue them here instead of mutating
+/// the scene mid-dispatch, the runtime applies them in order after the
+/// events of a tick are dispatched.
+#[derive(Default)]
+pub struct Commands {
+    queue: Vec<Command>,
+}
+
+impl Commands {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn len(&self) -> usize {
+        self.queue.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.queue.is_empty()
+    }
+
+    pub fn spawn<F>(&mut self, create_fn: F, on_conflict: Conflict)
+    where
+        F: FnOnce(Id) -> Box<dyn Object> + Send + 'static,
+    {
+        self.queue.push(Command::Spawn {
+            create: Box::new(create_fn),
+            on_conflict,
+        });
+    }
+
+    pub fn despawn(&mut self, id: Id) {
+        self.queue.push(Command::Despawn(id));
+    }
+
+    pub fn attach_grid(&mut self, grid: SpatialGrid) {
+        self.queue.push(Command::AttachGrid(grid));
+    }
+
+    pub fn set_protected(&mut self, id: Id, is_protected: bool) {
+        self.queue.push(Command::SetProtected { id, is_protected });
+    }
+
+    pub fn drain(&mut self) -> std::vec::Drain<'_, Command> {
+        self.queue.drain(..)
+    }
+
+    pub fn clear(&mut self) {
+        self.queue.clear();
+    }
+}
+
+/// A queued command which could not be applied, pushed as an event
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CommandConflict {
+    /// The object was already despawned by an earlier command of the same batch
+    AlreadyDespawned(Id),
+    /// The object isn't in the scene
+    Missing(Id),
+    /// The spawned object collided or was out of bounds
+    SpawnBlocked,
+}
+
+impl Event for CommandConflict {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn log_message(&self) -> String {
+        match self {
+            CommandConflict::AlreadyDespawned(id) => {
+                format!("[COMMAND]: {} already despawned", id.value)
+            }
+            CommandConflict::Missing(id) => format!("[COMMAND]: {} missing", id.value),
+            CommandConflict::SpawnBlocked => "[COMMAND]: spawn blocked".to_string(),
+        }
+    }
+}
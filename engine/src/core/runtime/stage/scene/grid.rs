@@ -30,16 +30,19 @@ impl SpatialGrid {
     where
         F: FnMut(Position) -> Terrain,
     {
-        let size = height * width;
-        let mut cells = Vec::with_capacity(size as usize);
+        // A 256x256 grid already overflows u16
+        let size = height as usize * width as usize;
+        let mut cells = Vec::with_capacity(size);
         let mut empty_cells = SlotMap::new();
 
         for y in 0..height {
             for x in 0..width {
                 let terrain = terrain_generator(Position::new(x, y));
+                if !terrain.is_collidable {
+                    let index = y as usize * width as usize + x as usize;
+                    empty_cells.insert(index);
+                }
                 cells.push(Cell::new(terrain));
-                let index = (y * width + x) as usize;
-                empty_cells.insert(index);
             }
         }
 
@@ -107,7 +110,7 @@ impl SpatialGrid {
 
     pub fn get_index(&self, grid_pos: &Position) -> Option<usize> {
         if grid_pos.x < self.width && grid_pos.y < self.height {
-            Some((grid_pos.y * self.width + grid_pos.x) as usize)
+            Some(grid_pos.y as usize * self.width as usize + grid_pos.x as usize)
         } else {
             None
         }
@@ -147,6 +150,14 @@ impl SpatialGrid {
         true
     }
 
+    /// Checks if any of the object's cells lands on collidable terrain
+    pub fn is_obstructed(&self, object: &dyn Object) -> bool {
+        object.t_cells().any(|t_cell| {
+            self.get_cell(&t_cell.pos)
+                .is_some_and(|cell| cell.terrain.is_collidable)
+        })
+    }
+
     pub fn probe_moves<'a>(
         &'a self,
         moves: impl Iterator<Item = (Id, Position)>,
@@ -176,7 +187,7 @@ impl SpatialGrid {
         collision_ids
     }
 
-    pub fn add_object(&mut self, object: &Box<dyn Object>) {
+    pub fn add_object(&mut self, object: &dyn Object) {
         for t_cell in object.t_cells() {
             self.add_cell_occ(t_cell);
         }
@@ -188,7 +199,9 @@ impl SpatialGrid {
                 if let Some(t_cell) = self.cells[index].occ_by {
                     if occ == t_cell.occ {
                         self.cells[index].occ_by = None;
-                        self.empty_cells.insert(index);
+                        if !self.cells[index].terrain.is_collidable {
+                            self.empty_cells.insert(index);
+                        }
                         return true;
                     }
                 }
@@ -223,7 +236,25 @@ impl SpatialGrid {
 
         for (index, cell) in self.cells.iter_mut().enumerate() {
             cell.occ_by = None;
-            self.empty_cells.insert(index);
+            if !cell.terrain.is_collidable {
+                self.empty_cells.insert(index);
+            }
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn grids_larger_than_u16_cells() {
+        let terrain = Terrain::new(Glyph::new(None, None, '.'), 0);
+        let grid = SpatialGrid::new(300, 300, None, Position::new(0, 0), |_| terrain);
+
+        let last = Position::new(299, 299);
+        assert_eq!(grid.get_index(&last), Some(300 * 300 - 1));
+        assert_eq!(grid.get_pos_from_index(300 * 300 - 1), Some(last));
+        assert_eq!(grid.get_index(&Position::new(300, 0)), None);
+    }
+}
@@ -4,11 +4,24 @@ use crate::prelude::Glyph;
 pub struct Terrain {
     pub style: Glyph,
     pub z_index: u8,
-    // TODO: pub is_collidable: bool - (leaving out for now)
+    pub is_collidable: bool,
 }
 
 impl Terrain {
     pub fn new(style: Glyph, z_index: u8) -> Self {
-        Self { style, z_index }
+        Self {
+            style,
+            z_index,
+            is_collidable: false,
+        }
+    }
+
+    /// Terrain which blocks spawning and is reported through probes (e.g. walls)
+    pub fn solid(style: Glyph, z_index: u8) -> Self {
+        Self {
+            style,
+            z_index,
+            is_collidable: true,
+        }
     }
 }
@@ -0,0 +1,76 @@
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::marker::PhantomData;
+
+use super::store::ObjectStore;
+use crate::prelude::{Id, Object, ObjectExt};
+
+/// A typed id of an object in the scene.
+/// Resolving a handle checks the id's generation and downcasts to `T`,
+/// so a handle to a removed object (or one from before a reset) resolves to `None`.
+pub struct Handle<T> {
+    id: Id,
+    _marker: PhantomData<fn() -> T>,
+}
+
+impl<T: Object + 'static> Handle<T> {
+    pub fn new(id: Id) -> Self {
+        Self {
+            id,
+            _marker: PhantomData,
+        }
+    }
+
+    pub fn id(&self) -> Id {
+        self.id
+    }
+
+    pub fn get<'a>(&self, objects: &'a ObjectStore) -> Option<&'a T> {
+        objects.get(&self.id).and_then(|object| object.get::<T>())
+    }
+
+    pub fn get_mut<'a>(&self, objects: &'a mut ObjectStore) -> Option<&'a mut T> {
+        objects
+            .get_mut(&self.id)
+            .and_then(|object| object.get_mut::<T>())
+    }
+
+    pub fn is_alive(&self, objects: &ObjectStore) -> bool {
+        self.get(objects).is_some()
+    }
+}
+
+// Implemented by hand, derives would require `T` to implement the traits
+impl<T> Clone for Handle<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for Handle<T> {}
+
+impl<T> PartialEq for Handle<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.id == other.id
+    }
+}
+
+impl<T> Eq for Handle<T> {}
+
+impl<T> Hash for Handle<T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.id.hash(state);
+    }
+}
+
+impl<T> fmt::Debug for Handle<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Handle<{}>({}:{})",
+            std::any::type_name::<T>(),
+            self.id.value,
+            self.id.generation
+        )
+    }
+}
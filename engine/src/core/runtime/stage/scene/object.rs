@@ -2,13 +2,29 @@ use std::any::Any;
 use rustc_hash::FxHashMap;
 use std::fmt::Debug;
 
+pub mod animation;
+pub mod sprite;
 pub mod state;
 pub mod t_cell;
+pub mod tween;
 
-use crate::prelude::{CellRef, Event, Id, Position};
+use crate::prelude::{CellRef, Commands, Event, Id, Position};
+use animation::{AnimationFinished, Animator};
 use state::{State, StateChange};
 use t_cell::TCell;
 
+/// `Sync` with the `parallel` feature, the threads share the objects while
+/// probing the grid. Without it any type is `MaybeSync`.
+#[cfg(feature = "parallel")]
+pub trait MaybeSync: Sync {}
+#[cfg(feature = "parallel")]
+impl<T: Sync + ?Sized> MaybeSync for T {}
+
+#[cfg(not(feature = "parallel"))]
+pub trait MaybeSync {}
+#[cfg(not(feature = "parallel"))]
+impl<T: ?Sized> MaybeSync for T {}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub struct Occupant {
     pub obj_id: Id,
@@ -25,7 +41,9 @@ impl Occupant {
 /// An object which only implements this trait only briefly announces its state
 /// upon creation. After the breif state the object will remain static/silent
 /// unless other object traits are added.
-pub trait Object: Debug {
+/// Objects are `Send` so a scene can be prepared on a loading thread,
+/// and `Sync` with the `parallel` feature, see `MaybeSync`.
+pub trait Object: Debug + Send + MaybeSync {
     fn id(&self) -> Id;
     fn t_cells(&self) -> Box<dyn Iterator<Item = &TCell> + '_>;
 
@@ -74,6 +92,18 @@ pub trait Object: Debug {
     fn as_movable_mut(&mut self) -> Option<&mut dyn Movable> {
         None
     }
+    fn as_animated(&self) -> Option<&dyn Animated> {
+        None
+    }
+    fn as_animated_mut(&mut self) -> Option<&mut dyn Animated> {
+        None
+    }
+    fn as_snapshot(&self) -> Option<&dyn Snapshot> {
+        None
+    }
+    fn as_snapshot_mut(&mut self) -> Option<&mut dyn Snapshot> {
+        None
+    }
 }
 
 /// The `Stateful` trait is reactive.
@@ -116,9 +146,10 @@ pub trait Destructible: Object {
 }
 
 /// The `Active` trait is an initiator.
-/// Objects with this trait can trigger events at each tick.
+/// Objects with this trait can trigger events at each tick
+/// and queue changes to the scene through `Commands`.
 pub trait Active: Object {
-    fn update(&mut self) -> Vec<Box<dyn Event>>;
+    fn update(&mut self, commands: &mut Commands) -> Vec<Box<dyn Event>>;
 }
 
 /// Ties an object to a SpatialGrid
@@ -137,6 +168,51 @@ pub trait Movable: Object + Stateful + Spatial {
     fn make_move(&mut self, probe: Option<Vec<CellRef>>) -> Vec<Box<dyn Event>>;
 }
 
+/// The `Animated` trait lets the engine play glyph animations on an object.
+/// Each tick the engine applies the `Animator`'s keyframes to the object's
+/// t_cells and records the resulting updates in its state.
+pub trait Animated: Stateful {
+    fn animator(&self) -> &Animator;
+    fn animator_mut(&mut self) -> &mut Animator;
+    fn t_cells_mut(&mut self) -> Box<dyn Iterator<Item = &mut TCell> + '_>;
+
+    /// Moves the object to the next position of a slide,
+    /// objects which can't be slid ignore it.
+    fn slide_to(&mut self, _pos: Position) {}
+
+    /// Advances the animations and announces the finished ones
+    fn animate(&mut self) -> Vec<Box<dyn Event>> {
+        if self.animator().is_idle() {
+            return Vec::new();
+        }
+
+        let obj_id = self.id();
+        let mut animator = std::mem::take(self.animator_mut());
+        let (changes, finished) = animator.tick(self.t_cells_mut());
+        let slide = animator.tick_slide();
+        *self.animator_mut() = animator;
+
+        for change in changes {
+            self.state_mut().upsert_change(change);
+        }
+
+        if let Some(pos) = slide {
+            self.slide_to(pos);
+        }
+
+        finished
+            .into_iter()
+            .map(|name| Box::new(AnimationFinished { obj_id, name }) as Box<dyn Event>)
+            .collect()
+    }
+}
+
+/// The `Snapshot` trait is an alternative to `Stateful`.
+/// The object only keeps its current t_cells, the engine compares them with
+/// the ones it synced last and records the creates, updates and deletes.
+/// Suits objects which redraw themselves as a whole, like text.
+pub trait Snapshot: Object {}
+
 pub trait ObjectExt {
     fn get<T: 'static>(&self) -> Option<&T>;
     fn get_mut<T: 'static>(&mut self) -> Option<&mut T>;
@@ -182,6 +258,18 @@ macro_rules! define_object {
         $crate::define_object!(@as_trait_impls $($tail)*);
     };
 
+    (@as_trait_impls Animated { $($body:tt)* } $($tail:tt)*) => {
+        fn as_animated(&self) -> Option<&dyn $crate::prelude::Animated> { Some(self) }
+        fn as_animated_mut(&mut self) -> Option<&mut dyn $crate::prelude::Animated> { Some(self) }
+        $crate::define_object!(@as_trait_impls $($tail)*);
+    };
+
+    (@as_trait_impls Snapshot { $($body:tt)* } $($tail:tt)*) => {
+        fn as_snapshot(&self) -> Option<&dyn $crate::prelude::Snapshot> { Some(self) }
+        fn as_snapshot_mut(&mut self) -> Option<&mut dyn $crate::prelude::Snapshot> { Some(self) }
+        $crate::define_object!(@as_trait_impls $($tail)*);
+    };
+
     (@as_trait_impls Movable { $($body:tt)* } $($tail:tt)*) => {
         fn as_movable(&self) -> Option<&dyn $crate::prelude::Movable> { Some(self) }
         fn as_movable_mut(&mut self) -> Option<&mut dyn $crate::prelude::Movable> { Some(self) }
@@ -208,6 +296,11 @@ macro_rules! define_object {
         $crate::define_object!(@trait_impls $struct, $($tail)*);
     };
 
+    (@trait_impls $struct:ty, Snapshot { } $($tail:tt)*) => {
+        impl $crate::prelude::Snapshot for $struct {}
+        $crate::define_object!(@trait_impls $struct, $($tail)*);
+    };
+
     (@trait_impls $struct:ty, Active { impl { $($body:tt)* } } $($tail:tt)*) => {
         impl $crate::prelude::Active for $struct {
             $($body)*
@@ -215,6 +308,17 @@ macro_rules! define_object {
         $crate::define_object!(@trait_impls $struct, $($tail)*);
     };
 
+    (@trait_impls $struct:ty, Animated { animator_field: $animator_field:ident, t_cells_mut: $func_name:ident } $($tail:tt)*) => {
+        impl $crate::prelude::Animated for $struct {
+            fn animator(&self) -> &$crate::prelude::Animator { &self.$animator_field }
+            fn animator_mut(&mut self) -> &mut $crate::prelude::Animator { &mut self.$animator_field }
+            fn t_cells_mut(&mut self) -> Box<dyn Iterator<Item = &mut $crate::prelude::TCell> + '_> {
+                self.$func_name()
+            }
+        }
+        $crate::define_object!(@trait_impls $struct, $($tail)*);
+    };
+
     (@trait_impls $struct:ty, Movable { impl { $($body:tt)* } } $($tail:tt)*) => {
         impl $crate::prelude::Movable for $struct {
             $($body)*
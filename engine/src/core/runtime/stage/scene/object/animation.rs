@@ -0,0 +1,445 @@
+use crossterm::style::Color;
+use rustc_hash::{FxHashMap, FxHashSet};
+
+use super::tween::{GlyphTween, Tween};
+use crate::prelude::{Event, Glyph, Id, Occupant, Position, StateChange, TCell};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PlayMode {
+    Once,
+    Loop,
+    PingPong,
+}
+
+/// Which t_cells of an object an animation applies to
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AnimationTarget {
+    All,
+    Cells(FxHashSet<Id>),
+}
+
+impl AnimationTarget {
+    pub(super) fn contains(&self, t_cell: &TCell) -> bool {
+        match self {
+            AnimationTarget::All => true,
+            AnimationTarget::Cells(ids) => ids.contains(&t_cell.occ.t_cell_id),
+        }
+    }
+}
+
+/// Overrides parts of a glyph, `None` keeps the original value
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct GlyphPatch {
+    pub symbol: Option<char>,
+    pub fg_clr: Option<Color>,
+    pub bg_clr: Option<Color>,
+}
+
+impl GlyphPatch {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn symbol(mut self, symbol: char) -> Self {
+        self.symbol = Some(symbol);
+        self
+    }
+
+    pub fn fg(mut self, color: Color) -> Self {
+        self.fg_clr = Some(color);
+        self
+    }
+
+    pub fn bg(mut self, color: Color) -> Self {
+        self.bg_clr = Some(color);
+        self
+    }
+
+    pub fn apply(&self, glyph: Glyph) -> Glyph {
+        Glyph {
+            symbol: self.symbol.unwrap_or(glyph.symbol),
+            fg_clr: self.fg_clr.or(glyph.fg_clr),
+            bg_clr: self.bg_clr.or(glyph.bg_clr),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Keyframe {
+    pub ticks: u32,
+    pub patch: GlyphPatch,
+}
+
+/// A named sequence of glyph keyframes played over ticks
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Animation {
+    pub name: &'static str,
+    keyframes: Vec<Keyframe>,
+    mode: PlayMode,
+    target: AnimationTarget,
+    frame: usize,
+    elapsed: u32,
+    remaining: Option<u32>,
+    is_reversed: bool,
+    is_finished: bool,
+}
+
+impl Animation {
+    pub fn new(name: &'static str, mode: PlayMode) -> Self {
+        Self {
+            name,
+            keyframes: Vec::new(),
+            mode,
+            target: AnimationTarget::All,
+            frame: 0,
+            elapsed: 0,
+            remaining: None,
+            is_reversed: false,
+            is_finished: false,
+        }
+    }
+
+    /// Adds a keyframe which is shown for `ticks` ticks
+    pub fn keyframe(mut self, ticks: u32, patch: GlyphPatch) -> Self {
+        self.keyframes.push(Keyframe {
+            ticks: ticks.max(1),
+            patch,
+        });
+        self
+    }
+
+    /// An empty keyframe, shows the original glyph
+    pub fn pause(self, ticks: u32) -> Self {
+        self.keyframe(ticks, GlyphPatch::new())
+    }
+
+    /// Finishes the animation after `ticks` ticks, regardless of the play mode
+    pub fn duration(mut self, ticks: u32) -> Self {
+        self.remaining = Some(ticks);
+        self
+    }
+
+    pub fn target(mut self, target: AnimationTarget) -> Self {
+        self.target = target;
+        self
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.is_finished || self.keyframes.is_empty()
+    }
+
+    fn current(&self) -> Option<&Keyframe> {
+        if self.is_finished() {
+            return None;
+        }
+        self.keyframes.get(self.frame)
+    }
+
+    fn advance(&mut self) {
+        let Some(ticks) = self.current().map(|keyframe| keyframe.ticks) else {
+            return;
+        };
+
+        if let Some(remaining) = &mut self.remaining {
+            *remaining = remaining.saturating_sub(1);
+            if *remaining == 0 {
+                self.is_finished = true;
+                return;
+            }
+        }
+
+        self.elapsed += 1;
+        if self.elapsed < ticks {
+            return;
+        }
+        self.elapsed = 0;
+
+        let last = self.keyframes.len() - 1;
+        match self.mode {
+            PlayMode::Once => {
+                if self.frame == last {
+                    self.is_finished = true;
+                } else {
+                    self.frame += 1;
+                }
+            }
+            PlayMode::Loop => {
+                self.frame = if self.frame == last {
+                    0
+                } else {
+                    self.frame + 1
+                }
+            }
+            PlayMode::PingPong => {
+                if last == 0 {
+                    return;
+                }
+                if self.is_reversed && self.frame == 0 {
+                    self.is_reversed = false;
+                } else if !self.is_reversed && self.frame == last {
+                    self.is_reversed = true;
+                }
+                self.frame = if self.is_reversed {
+                    self.frame - 1
+                } else {
+                    self.frame + 1
+                };
+            }
+        }
+    }
+}
+
+/// Fired once an animation has played to its end or ran out of time
+pub struct AnimationFinished {
+    pub obj_id: Id,
+    pub name: &'static str,
+}
+
+impl Event for AnimationFinished {
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn log_message(&self) -> String {
+        format!(
+            "[ANIMATION]: A:{} '{}' finished",
+            self.obj_id.value, self.name
+        )
+    }
+}
+
+/// Plays animations and glyph tweens on an object's t_cells. The animator remembers the
+/// original glyph of every patched cell and restores it once no animation
+/// targets the cell anymore. Changes made by the object itself while a
+/// cell is patched become the new original.
+#[derive(Debug, Clone, Default)]
+pub struct Animator {
+    animations: Vec<Animation>,
+    tweens: Vec<GlyphTween>,
+    slide: Option<(Tween<Position>, Position)>,
+    // Occupant -> (original glyph, applied glyph)
+    patched: FxHashMap<Occupant, (Glyph, Glyph)>,
+}
+
+impl Animator {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Plays an animation, replacing a running one with the same name
+    pub fn play(&mut self, animation: Animation) {
+        self.stop(animation.name);
+        self.animations.push(animation);
+    }
+
+    /// Plays a glyph tween, replacing a running one with the same name
+    pub fn play_tween(&mut self, tween: GlyphTween) {
+        self.stop(tween.name);
+        self.tweens.push(tween);
+    }
+
+    /// Slides the object along a position tween, see `Animated::slide_to`
+    pub fn slide(&mut self, tween: Tween<Position>) {
+        let pos = tween.value();
+        self.slide = Some((tween, pos));
+    }
+
+    pub fn stop(&mut self, name: &str) {
+        self.animations.retain(|animation| animation.name != name);
+        self.tweens.retain(|tween| tween.name != name);
+    }
+
+    pub fn stop_slide(&mut self) {
+        self.slide = None;
+    }
+
+    pub fn is_playing(&self, name: &str) -> bool {
+        self.animations
+            .iter()
+            .any(|animation| animation.name == name)
+            || self.tweens.iter().any(|tween| tween.name == name)
+    }
+
+    pub fn is_sliding(&self) -> bool {
+        self.slide.is_some()
+    }
+
+    pub fn is_idle(&self) -> bool {
+        self.animations.is_empty()
+            && self.tweens.is_empty()
+            && self.slide.is_none()
+            && self.patched.is_empty()
+    }
+
+    /// Advances the slide and returns the position once its quantised value changes
+    pub fn tick_slide(&mut self) -> Option<Position> {
+        let (tween, last) = self.slide.as_mut()?;
+        tween.advance();
+
+        let pos = tween.value();
+        let moved = (pos != *last).then_some(pos);
+        *last = pos;
+
+        if tween.is_finished() {
+            self.slide = None;
+        }
+        moved
+    }
+
+    /// Applies the current keyframes to the t_cells and advances the animations.
+    /// Tweens advance before they are applied, so the tick a tween finishes
+    /// on shows its end value. Returns the changed cells and the names of
+    /// finished animations.
+    pub fn tick<'a>(
+        &mut self,
+        t_cells: impl Iterator<Item = &'a mut TCell>,
+    ) -> (Vec<StateChange>, Vec<&'static str>) {
+        let mut changes = Vec::new();
+        let mut patched = FxHashMap::default();
+
+        let mut finished_tweens = Vec::new();
+        for tween in self.tweens.iter_mut() {
+            tween.advance();
+            if tween.is_finished() {
+                finished_tweens.push(tween.name);
+            }
+        }
+
+        for t_cell in t_cells {
+            let original = match self.patched.get(&t_cell.occ) {
+                Some((original, applied)) if *applied == t_cell.style => *original,
+                _ => t_cell.style,
+            };
+            // A held tween leaves its end value behind as the new original
+            let original = self
+                .tweens
+                .iter()
+                .filter(|tween| tween.is_held() && tween.is_finished())
+                .filter(|tween| tween.targets().contains(t_cell))
+                .fold(original, |glyph, tween| tween.patch().apply(glyph));
+
+            let glyph = self
+                .animations
+                .iter()
+                .filter(|animation| animation.target.contains(t_cell))
+                .filter_map(|animation| animation.current())
+                .fold(original, |glyph, keyframe| keyframe.patch.apply(glyph));
+
+            let glyph = self
+                .tweens
+                .iter()
+                .filter(|tween| tween.targets().contains(t_cell))
+                .fold(glyph, |glyph, tween| tween.patch().apply(glyph));
+
+            if glyph != t_cell.style {
+                t_cell.style = glyph;
+                changes.push(StateChange::Update {
+                    t_cell: *t_cell,
+                    init_pos: t_cell.pos,
+                });
+            }
+
+            if glyph != original {
+                patched.insert(t_cell.occ, (original, glyph));
+            }
+        }
+        self.patched = patched;
+
+        let mut finished = Vec::new();
+        for animation in self.animations.iter_mut() {
+            animation.advance();
+            if animation.is_finished() {
+                finished.push(animation.name);
+            }
+        }
+        self.animations.retain(|animation| !animation.is_finished());
+
+        finished.extend(finished_tweens);
+        self.tweens.retain(|tween| !tween.is_finished());
+
+        (changes, finished)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prelude::{DOT_SCALE, Easing, IdCounter, Tween};
+
+    const BLACK: Color = Color::Rgb { r: 0, g: 0, b: 0 };
+    const WHITE: Color = Color::Rgb {
+        r: 255,
+        g: 255,
+        b: 255,
+    };
+
+    fn t_cell() -> TCell {
+        let mut ids = IdCounter::new();
+        let occ = Occupant::new(ids.next(), ids.next());
+        TCell::new(occ, Glyph::new(Some(BLACK), None, 'o'), None, 0)
+    }
+
+    fn fade(ticks: u32) -> GlyphTween {
+        GlyphTween::new("fade").fg(Tween::new(BLACK, WHITE, ticks, Easing::Linear))
+    }
+
+    // The foreground after every tick and the tick the tween finished on
+    fn play(animator: &mut Animator, t_cell: &mut TCell, ticks: usize) -> (Vec<Color>, usize) {
+        let mut colors = Vec::new();
+        let mut finished_on = 0;
+        for tick in 1..=ticks {
+            let (_, finished) = animator.tick(std::iter::once(&mut *t_cell));
+            if !finished.is_empty() {
+                finished_on = tick;
+            }
+            colors.push(t_cell.style.fg_clr.unwrap());
+        }
+        (colors, finished_on)
+    }
+
+    #[test]
+    fn a_finished_tween_shows_its_end_value_then_restores() {
+        let (mut animator, mut t_cell) = (Animator::new(), t_cell());
+        animator.play_tween(fade(2));
+
+        let (colors, finished_on) = play(&mut animator, &mut t_cell, 3);
+        let half = Color::Rgb {
+            r: 128,
+            g: 128,
+            b: 128,
+        };
+        assert_eq!(colors, vec![half, WHITE, BLACK]);
+        assert_eq!(finished_on, 2);
+        assert!(animator.is_idle());
+    }
+
+    #[test]
+    fn a_held_tween_keeps_its_end_value() {
+        let (mut animator, mut t_cell) = (Animator::new(), t_cell());
+        animator.play_tween(fade(2).hold());
+
+        let (colors, finished_on) = play(&mut animator, &mut t_cell, 4);
+        assert_eq!(colors[1..], [WHITE, WHITE, WHITE]);
+        assert_eq!(finished_on, 2);
+        assert!(animator.is_idle());
+    }
+
+    #[test]
+    fn scale_pulses_step_through_the_symbols() {
+        let (mut animator, mut t_cell) = (Animator::new(), t_cell());
+        let pulse = Tween::pulse(0.0, 1.0, 3, Easing::Linear);
+        animator.play_tween(GlyphTween::new("pulse").scale(pulse, &DOT_SCALE));
+
+        let symbols: Vec<char> = (0..6)
+            .map(|_| {
+                animator.tick(std::iter::once(&mut t_cell));
+                t_cell.style.symbol
+            })
+            .collect();
+        assert_eq!(symbols, vec!['∙', '•', '●', '•', '∙', '·']);
+
+        animator.stop("pulse");
+        animator.tick(std::iter::once(&mut t_cell));
+        assert_eq!(t_cell.style.symbol, 'o');
+    }
+}
@@ -0,0 +1,420 @@
+use rustc_hash::FxHashMap;
+use std::any::Any;
+
+use super::{Animated, Destructible, Movable, Object, Spatial, Stateful};
+use crate::prelude::{
+    Animator, CellRef, Event, Id, Occupant, Position, Sprite, State, StateChange, TCell,
+};
+
+/// Keeps the `TCell`s of a multi-cell object in sync with a `Sprite`.
+/// Each opaque sprite cell owns a stable t_cell id (its index in the sprite),
+/// so moving or swapping frames only emits the changes that are needed.
+#[derive(Debug, Clone)]
+pub struct SpriteBody {
+    obj_id: Id,
+    sprite: Sprite,
+    pos: Position,
+    z_index: u8,
+    t_cells: Vec<TCell>,
+}
+
+impl SpriteBody {
+    pub fn new(obj_id: Id, sprite: Sprite, pos: Position, z_index: u8) -> Self {
+        let mut body = Self {
+            obj_id,
+            sprite,
+            pos,
+            z_index,
+            t_cells: Vec::new(),
+        };
+        body.t_cells = body.build_t_cells();
+        body
+    }
+
+    pub fn sprite(&self) -> &Sprite {
+        &self.sprite
+    }
+
+    pub fn pos(&self) -> Position {
+        self.pos
+    }
+
+    pub fn z_index(&self) -> u8 {
+        self.z_index
+    }
+
+    pub fn t_cells(&self) -> impl Iterator<Item = &TCell> {
+        self.t_cells.iter()
+    }
+
+    /// Mutable t_cells for animations, the changes are not recorded
+    pub fn t_cells_mut(&mut self) -> impl Iterator<Item = &mut TCell> {
+        self.t_cells.iter_mut()
+    }
+
+    fn build_t_cells(&self) -> Vec<TCell> {
+        self.sprite
+            .cells()
+            .map(|(offset, glyph)| {
+                let index = offset.y as u64 * self.sprite.width as u64 + offset.x as u64;
+                TCell::new(
+                    Occupant::new(self.obj_id, Id::new(index)),
+                    glyph,
+                    Some(self.sprite.world_pos(self.pos, offset)),
+                    self.z_index,
+                )
+            })
+            .collect()
+    }
+
+    /// Rebuilds the t_cells and records the difference as state changes
+    fn rebuild(&mut self, state: &mut State) {
+        let mut old_t_cells: FxHashMap<Occupant, TCell> = self
+            .t_cells
+            .drain(..)
+            .map(|t_cell| (t_cell.occ, t_cell))
+            .collect();
+
+        let new_t_cells = self.build_t_cells();
+        for t_cell in &new_t_cells {
+            match old_t_cells.remove(&t_cell.occ) {
+                Some(old) if old == *t_cell => {}
+                Some(old) => state.upsert_change(StateChange::Update {
+                    t_cell: *t_cell,
+                    init_pos: old.pos,
+                }),
+                None => state.upsert_change(StateChange::Create {
+                    new_t_cell: *t_cell,
+                }),
+            }
+        }
+
+        for (occupant, old) in old_t_cells {
+            state.upsert_change(StateChange::Delete {
+                occupant,
+                init_pos: old.pos,
+            });
+        }
+
+        self.t_cells = new_t_cells;
+    }
+
+    pub fn move_to(&mut self, pos: Position, state: &mut State) {
+        if self.pos != pos {
+            self.pos = pos;
+            self.rebuild(state);
+        }
+    }
+
+    pub fn translate(&mut self, dx: i16, dy: i16, state: &mut State) {
+        let pos = Position::new(
+            self.pos.x.saturating_add_signed(dx),
+            self.pos.y.saturating_add_signed(dy),
+        );
+        self.move_to(pos, state);
+    }
+
+    /// Swaps the sprite, e.g. for the next animation frame
+    pub fn set_sprite(&mut self, sprite: Sprite, state: &mut State) {
+        if self.sprite != sprite {
+            self.sprite = sprite;
+            self.rebuild(state);
+        }
+    }
+
+    pub fn set_z_index(&mut self, z_index: u8, state: &mut State) {
+        if self.z_index != z_index {
+            self.z_index = z_index;
+            self.rebuild(state);
+        }
+    }
+
+    pub fn rotate_cw(&mut self, state: &mut State) {
+        self.set_sprite(self.sprite.rotate_cw(), state);
+    }
+
+    pub fn rotate_ccw(&mut self, state: &mut State) {
+        self.set_sprite(self.sprite.rotate_ccw(), state);
+    }
+
+    pub fn flip_horizontal(&mut self, state: &mut State) {
+        self.set_sprite(self.sprite.flip_horizontal(), state);
+    }
+
+    pub fn flip_vertical(&mut self, state: &mut State) {
+        self.set_sprite(self.sprite.flip_vertical(), state);
+    }
+
+    /// Positions a translation would newly cover. Cells which are already
+    /// covered by the body itself are left out, so a multi-cell body
+    /// doesn't collide with itself when probing.
+    pub fn probe_translate(&self, dx: i16, dy: i16) -> impl Iterator<Item = Position> + '_ {
+        self.t_cells
+            .iter()
+            .map(move |t_cell| {
+                Position::new(
+                    t_cell.pos.x.saturating_add_signed(dx),
+                    t_cell.pos.y.saturating_add_signed(dy),
+                )
+            })
+            .filter(move |pos| !self.t_cells.iter().any(|t_cell| t_cell.pos == *pos))
+    }
+}
+
+/// A generic multi-cell object drawn from a sprite.
+/// Spatial sprites are tied to the grid and take part in collisions, they
+/// move by their velocity every tick unless the way is blocked.
+#[derive(Debug)]
+pub struct SpriteObject {
+    id: Id,
+    is_spatial: bool,
+    velocity: (i16, i16),
+    pub body: SpriteBody,
+    pub animator: Animator,
+    state: State,
+}
+
+impl SpriteObject {
+    pub fn new(id: Id, sprite: Sprite, pos: Position, z_index: u8) -> Self {
+        Self {
+            id,
+            is_spatial: false,
+            velocity: (0, 0),
+            body: SpriteBody::new(id, sprite, pos, z_index),
+            animator: Animator::new(),
+            state: State::new(),
+        }
+    }
+
+    pub fn spatial(id: Id, sprite: Sprite, pos: Position, z_index: u8) -> Self {
+        Self {
+            is_spatial: true,
+            ..Self::new(id, sprite, pos, z_index)
+        }
+    }
+
+    pub fn velocity(&self) -> (i16, i16) {
+        self.velocity
+    }
+
+    /// Cells a spatial sprite moves each tick, only the grid moves it
+    pub fn set_velocity(&mut self, dx: i16, dy: i16) {
+        self.velocity = (dx, dy);
+    }
+
+    pub fn move_to(&mut self, pos: Position) {
+        self.body.move_to(pos, &mut self.state);
+    }
+
+    pub fn translate(&mut self, dx: i16, dy: i16) {
+        self.body.translate(dx, dy, &mut self.state);
+    }
+
+    pub fn set_sprite(&mut self, sprite: Sprite) {
+        self.body.set_sprite(sprite, &mut self.state);
+    }
+
+    pub fn rotate_cw(&mut self) {
+        self.body.rotate_cw(&mut self.state);
+    }
+
+    pub fn rotate_ccw(&mut self) {
+        self.body.rotate_ccw(&mut self.state);
+    }
+
+    pub fn flip_horizontal(&mut self) {
+        self.body.flip_horizontal(&mut self.state);
+    }
+
+    pub fn flip_vertical(&mut self) {
+        self.body.flip_vertical(&mut self.state);
+    }
+}
+
+// Implemented by hand as the spatial capability is chosen at construction
+impl Object for SpriteObject {
+    fn id(&self) -> Id {
+        self.id
+    }
+
+    fn t_cells(&self) -> Box<dyn Iterator<Item = &TCell> + '_> {
+        Box::new(self.body.t_cells())
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn as_stateful(&self) -> Option<&dyn Stateful> {
+        Some(self)
+    }
+
+    fn as_stateful_mut(&mut self) -> Option<&mut dyn Stateful> {
+        Some(self)
+    }
+
+    fn as_destructible(&self) -> Option<&dyn Destructible> {
+        Some(self)
+    }
+
+    fn as_destructible_mut(&mut self) -> Option<&mut dyn Destructible> {
+        Some(self)
+    }
+
+    fn as_animated(&self) -> Option<&dyn Animated> {
+        Some(self)
+    }
+
+    fn as_animated_mut(&mut self) -> Option<&mut dyn Animated> {
+        Some(self)
+    }
+
+    fn as_spatial(&self) -> Option<&dyn Spatial> {
+        if self.is_spatial { Some(self) } else { None }
+    }
+
+    fn as_spatial_mut(&mut self) -> Option<&mut dyn Spatial> {
+        if self.is_spatial { Some(self) } else { None }
+    }
+
+    fn as_movable(&self) -> Option<&dyn Movable> {
+        if self.is_spatial { Some(self) } else { None }
+    }
+
+    fn as_movable_mut(&mut self) -> Option<&mut dyn Movable> {
+        if self.is_spatial { Some(self) } else { None }
+    }
+}
+
+impl Stateful for SpriteObject {
+    fn state(&self) -> &State {
+        &self.state
+    }
+
+    fn state_mut(&mut self) -> &mut State {
+        &mut self.state
+    }
+}
+
+impl Destructible for SpriteObject {}
+
+impl Animated for SpriteObject {
+    fn animator(&self) -> &Animator {
+        &self.animator
+    }
+
+    fn animator_mut(&mut self) -> &mut Animator {
+        &mut self.animator
+    }
+
+    fn t_cells_mut(&mut self) -> Box<dyn Iterator<Item = &mut TCell> + '_> {
+        Box::new(self.body.t_cells_mut())
+    }
+
+    fn slide_to(&mut self, pos: Position) {
+        self.move_to(pos);
+    }
+}
+
+impl Spatial for SpriteObject {}
+
+impl Movable for SpriteObject {
+    fn probe_move(&self) -> Box<dyn Iterator<Item = Position> + '_> {
+        match self.velocity {
+            (0, 0) => Box::new(std::iter::empty()),
+            (dx, dy) => Box::new(self.body.probe_translate(dx, dy)),
+        }
+    }
+
+    /// Leaving the grid, solid terrain and other objects block the move,
+    /// the sprite waits until the way is clear
+    fn make_move(&mut self, probe: Option<Vec<CellRef>>) -> Vec<Box<dyn Event>> {
+        let (dx, dy) = self.velocity;
+        if (dx, dy) == (0, 0) {
+            return Vec::new();
+        }
+
+        let hits = probe.unwrap_or_default();
+        let is_blocked = hits.len() < self.body.probe_translate(dx, dy).count()
+            || hits.iter().any(|hit| {
+                hit.cell.terrain.is_collidable
+                    || hit
+                        .cell
+                        .occ_by
+                        .is_some_and(|t_cell| t_cell.occ.obj_id != self.id)
+            });
+
+        if !is_blocked {
+            self.translate(dx, dy);
+        }
+        Vec::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prelude::{Glyph, IdCounter, SpatialGrid, Terrain};
+
+    // A 6x3 grid with a wall at (4, 1)
+    fn grid() -> SpatialGrid {
+        SpatialGrid::new(6, 3, None, Position::new(0, 0), |pos| {
+            let mut terrain = Terrain::new(Glyph::new(None, None, ' '), 0);
+            terrain.is_collidable = pos == Position::new(4, 1);
+            terrain
+        })
+    }
+
+    fn spatial_sprite(pos: Position) -> SpriteObject {
+        let id = IdCounter::new().next();
+        SpriteObject::spatial(id, Sprite::from_text("##", None, None), pos, 0)
+    }
+
+    fn step(grid: &SpatialGrid, sprite: &mut SpriteObject) {
+        let id = sprite.id();
+        let mut probes = grid.probe_moves(sprite.probe_move().map(|pos| (id, pos)));
+        sprite.make_move(probes.remove(&id));
+    }
+
+    #[test]
+    fn spatial_sprites_are_movable() {
+        let mut sprite = spatial_sprite(Position::new(0, 1));
+        assert!(sprite.as_movable().is_some());
+        assert_eq!(sprite.probe_move().count(), 0);
+
+        sprite.set_velocity(1, 0);
+        assert_eq!(
+            sprite.probe_move().collect::<Vec<_>>(),
+            vec![Position::new(2, 1)]
+        );
+
+        let id = IdCounter::new().next();
+        let loose = SpriteObject::new(id, Sprite::from_text("#", None, None), Position::empty(), 0);
+        assert!(loose.as_movable().is_none());
+    }
+
+    #[test]
+    fn moves_until_blocked() {
+        let grid = grid();
+        let mut sprite = spatial_sprite(Position::new(0, 1));
+        sprite.set_velocity(1, 0);
+
+        for _ in 0..4 {
+            step(&grid, &mut sprite);
+        }
+        // The wall stops the right cell at x = 3
+        assert_eq!(sprite.body.pos(), Position::new(2, 1));
+
+        // Leaving the grid is blocked too
+        let mut sprite = spatial_sprite(Position::new(0, 0));
+        sprite.set_velocity(1, 0);
+        for _ in 0..6 {
+            step(&grid, &mut sprite);
+        }
+        assert_eq!(sprite.body.pos(), Position::new(4, 0));
+    }
+}
@@ -1,5 +1,5 @@
-use std::collections::{hash_map::Entry};
 use rustc_hash::FxHashMap;
+use std::collections::hash_map::Entry;
 use std::mem;
 
 use super::Occupant;
@@ -0,0 +1,299 @@
+use crossterm::style::Color;
+use std::f32::consts::PI;
+
+use super::animation::{AnimationTarget, GlyphPatch, PlayMode};
+use crate::prelude::Position;
+
+/// Standard easing curves, mapping a progress of `0.0..=1.0` onto itself
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Easing {
+    Linear,
+    QuadIn,
+    QuadOut,
+    QuadInOut,
+    CubicIn,
+    CubicOut,
+    CubicInOut,
+    ElasticIn,
+    ElasticOut,
+    BounceIn,
+    BounceOut,
+}
+
+impl Easing {
+    pub fn apply(&self, t: f32) -> f32 {
+        let t = t.clamp(0.0, 1.0);
+        match self {
+            Easing::Linear => t,
+            Easing::QuadIn => t * t,
+            Easing::QuadOut => 1.0 - (1.0 - t) * (1.0 - t),
+            Easing::QuadInOut => {
+                if t < 0.5 {
+                    2.0 * t * t
+                } else {
+                    1.0 - (-2.0 * t + 2.0).powi(2) / 2.0
+                }
+            }
+            Easing::CubicIn => t * t * t,
+            Easing::CubicOut => 1.0 - (1.0 - t).powi(3),
+            Easing::CubicInOut => {
+                if t < 0.5 {
+                    4.0 * t * t * t
+                } else {
+                    1.0 - (-2.0 * t + 2.0).powi(3) / 2.0
+                }
+            }
+            Easing::ElasticIn => 1.0 - elastic_out(1.0 - t),
+            Easing::ElasticOut => elastic_out(t),
+            Easing::BounceIn => 1.0 - bounce_out(1.0 - t),
+            Easing::BounceOut => bounce_out(t),
+        }
+    }
+}
+
+fn elastic_out(t: f32) -> f32 {
+    if t == 0.0 || t == 1.0 {
+        return t;
+    }
+    2f32.powf(-10.0 * t) * ((t * 10.0 - 0.75) * (2.0 * PI / 3.0)).sin() + 1.0
+}
+
+fn bounce_out(t: f32) -> f32 {
+    const N: f32 = 7.5625;
+    const D: f32 = 2.75;
+
+    if t < 1.0 / D {
+        N * t * t
+    } else if t < 2.0 / D {
+        let t = t - 1.5 / D;
+        N * t * t + 0.75
+    } else if t < 2.5 / D {
+        let t = t - 2.25 / D;
+        N * t * t + 0.9375
+    } else {
+        let t = t - 2.625 / D;
+        N * t * t + 0.984375
+    }
+}
+
+/// A value which can be interpolated. Terminal values are quantised,
+/// so `lerp` rounds to the nearest representable value.
+pub trait Tweenable: Copy + PartialEq {
+    fn lerp(&self, to: &Self, t: f32) -> Self;
+}
+
+impl Tweenable for f32 {
+    fn lerp(&self, to: &Self, t: f32) -> Self {
+        self + (to - self) * t
+    }
+}
+
+impl Tweenable for u8 {
+    fn lerp(&self, to: &Self, t: f32) -> Self {
+        (*self as f32)
+            .lerp(&(*to as f32), t)
+            .round()
+            .clamp(0.0, 255.0) as u8
+    }
+}
+
+impl Tweenable for u16 {
+    fn lerp(&self, to: &Self, t: f32) -> Self {
+        (*self as f32)
+            .lerp(&(*to as f32), t)
+            .round()
+            .clamp(0.0, u16::MAX as f32) as u16
+    }
+}
+
+impl Tweenable for Position {
+    fn lerp(&self, to: &Self, t: f32) -> Self {
+        Position::new(self.x.lerp(&to.x, t), self.y.lerp(&to.y, t))
+    }
+}
+
+/// Only `Color::Rgb` blends, any other colour switches halfway
+impl Tweenable for Color {
+    fn lerp(&self, to: &Self, t: f32) -> Self {
+        match (self, to) {
+            (
+                Color::Rgb { r, g, b },
+                Color::Rgb {
+                    r: r2,
+                    g: g2,
+                    b: b2,
+                },
+            ) => Color::Rgb {
+                r: r.lerp(r2, t),
+                g: g.lerp(g2, t),
+                b: b.lerp(b2, t),
+            },
+            _ if t < 0.5 => *self,
+            _ => *to,
+        }
+    }
+}
+
+/// Interpolates between two values over a number of ticks
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Tween<T: Tweenable> {
+    from: T,
+    to: T,
+    ticks: u32,
+    elapsed: u32,
+    easing: Easing,
+    mode: PlayMode,
+    is_reversed: bool,
+}
+
+impl<T: Tweenable> Tween<T> {
+    pub fn new(from: T, to: T, ticks: u32, easing: Easing) -> Self {
+        Self {
+            from,
+            to,
+            ticks: ticks.max(1),
+            elapsed: 0,
+            easing,
+            mode: PlayMode::Once,
+            is_reversed: false,
+        }
+    }
+
+    pub fn mode(mut self, mode: PlayMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    /// Goes from `from` to `to` and back, over `ticks` ticks each way
+    pub fn pulse(from: T, to: T, ticks: u32, easing: Easing) -> Self {
+        Self::new(from, to, ticks, easing).mode(PlayMode::PingPong)
+    }
+
+    /// Eased progress between `0.0` (from) and `1.0` (to)
+    pub fn progress(&self) -> f32 {
+        let t = self.elapsed as f32 / self.ticks as f32;
+        let t = if self.is_reversed { 1.0 - t } else { t };
+        self.easing.apply(t)
+    }
+
+    pub fn value(&self) -> T {
+        self.from.lerp(&self.to, self.progress())
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.mode == PlayMode::Once && self.elapsed >= self.ticks
+    }
+
+    pub fn advance(&mut self) {
+        if self.is_finished() {
+            return;
+        }
+
+        self.elapsed += 1;
+        if self.elapsed < self.ticks {
+            return;
+        }
+
+        match self.mode {
+            PlayMode::Once => {}
+            PlayMode::Loop => self.elapsed = 0,
+            PlayMode::PingPong => {
+                self.elapsed = 0;
+                self.is_reversed = !self.is_reversed;
+            }
+        }
+    }
+}
+
+/// Symbols from the smallest to the largest, for scaling a dot
+pub const DOT_SCALE: [char; 4] = ['·', '∙', '•', '●'];
+
+/// Blends the colours of an object's t_cells and scales their symbol,
+/// played by an `Animator`
+#[derive(Debug, Clone, PartialEq)]
+pub struct GlyphTween {
+    pub name: &'static str,
+    target: AnimationTarget,
+    fg: Option<Tween<Color>>,
+    bg: Option<Tween<Color>>,
+    scale: Option<(Tween<f32>, &'static [char])>,
+    is_held: bool,
+}
+
+impl GlyphTween {
+    pub fn new(name: &'static str) -> Self {
+        Self {
+            name,
+            target: AnimationTarget::All,
+            fg: None,
+            bg: None,
+            scale: None,
+            is_held: false,
+        }
+    }
+
+    pub fn fg(mut self, tween: Tween<Color>) -> Self {
+        self.fg = Some(tween);
+        self
+    }
+
+    pub fn bg(mut self, tween: Tween<Color>) -> Self {
+        self.bg = Some(tween);
+        self
+    }
+
+    /// Scales the symbol, `0.0` is the first of `symbols` and `1.0` the last.
+    /// E.g. `Tween::pulse(0.0, 1.0, 4, Easing::QuadInOut)` with `&DOT_SCALE`.
+    pub fn scale(mut self, tween: Tween<f32>, symbols: &'static [char]) -> Self {
+        self.scale = Some((tween, symbols));
+        self
+    }
+
+    pub fn target(mut self, target: AnimationTarget) -> Self {
+        self.target = target;
+        self
+    }
+
+    /// Keeps the end values once the tween finished, instead of restoring the original glyph
+    pub fn hold(mut self) -> Self {
+        self.is_held = true;
+        self
+    }
+
+    pub fn is_held(&self) -> bool {
+        self.is_held
+    }
+
+    pub(super) fn targets(&self) -> &AnimationTarget {
+        &self.target
+    }
+
+    pub fn patch(&self) -> GlyphPatch {
+        let symbol = self.scale.and_then(|(tween, symbols)| {
+            let last = symbols.len().checked_sub(1)?;
+            let index = (tween.value().clamp(0.0, 1.0) * last as f32).round() as usize;
+            Some(symbols[index])
+        });
+
+        GlyphPatch {
+            symbol,
+            fg_clr: self.fg.map(|tween| tween.value()),
+            bg_clr: self.bg.map(|tween| tween.value()),
+        }
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.fg.is_none_or(|tween| tween.is_finished())
+            && self.bg.is_none_or(|tween| tween.is_finished())
+            && self.scale.is_none_or(|(tween, _)| tween.is_finished())
+    }
+
+    pub fn advance(&mut self) {
+        for tween in [&mut self.fg, &mut self.bg].into_iter().flatten() {
+            tween.advance();
+        }
+        if let Some((tween, _)) = &mut self.scale {
+            tween.advance();
+        }
+    }
+}
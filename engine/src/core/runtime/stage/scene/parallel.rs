@@ -0,0 +1,78 @@
+//! Per-object phases of a tick run on the rayon thread pool.
+//! Each worker only reads the grid or touches its own object, the results are
+//! merged back in index order so they match the single-threaded path exactly.
+
+use rayon::prelude::*;
+use rustc_hash::FxHashMap;
+
+use super::store::ObjectStore;
+use super::{ObjectIndex, Scene};
+use crate::core::profiler;
+use crate::prelude::{CellRef, Event, Id, Span, State};
+
+pub(super) fn move_objects(scene: &mut Scene) -> Vec<Box<dyn Event>> {
+    let (Some(grid), Some(movable_ids)) = (
+        &scene.spatial_grid,
+        scene.indexes.get(&ObjectIndex::Movable),
+    ) else {
+        return Vec::new();
+    };
+
+    // Probing only reads the objects and the grid
+    let ids: Vec<Id> = movable_ids.iter().copied().collect();
+    let objects = &scene.objects;
+    let span = Span::start(&scene.resources);
+    let mut probe_map: FxHashMap<Id, Vec<CellRef>> = ids
+        .par_iter()
+        .filter_map(|id| {
+            let movable = objects.get(id)?.as_movable()?;
+            let mut probe = grid.probe_moves(movable.probe_move().map(|pos| (*id, pos)));
+            probe.remove(id).map(|cells| (*id, cells))
+        })
+        .collect();
+    span.end(&mut scene.resources, profiler::PROBE);
+
+    let span = Span::start(&scene.resources);
+    let moves: Vec<_> = scene
+        .objects
+        .get_many_mut(&ids)
+        .into_iter()
+        .map(|(id, object)| (id, object, probe_map.remove(&id)))
+        .collect();
+
+    let mut events: FxHashMap<Id, Vec<Box<dyn Event>>> = moves
+        .into_par_iter()
+        .filter_map(|(id, object, probe)| {
+            let movable = object.as_movable_mut()?;
+            Some((id, movable.make_move(probe)))
+        })
+        .collect();
+    span.end(&mut scene.resources, profiler::MAKE_MOVE);
+
+    ids.iter()
+        .filter_map(|id| events.remove(id))
+        .flatten()
+        .collect()
+}
+
+pub(super) fn drain_states<'a>(
+    objects: &mut ObjectStore,
+    global: &mut State,
+    ids: impl Iterator<Item = &'a Id>,
+) {
+    let ids: Vec<Id> = ids.copied().collect();
+    let mut drained: FxHashMap<Id, _> = objects
+        .get_many_mut(&ids)
+        .into_par_iter()
+        .filter_map(|(id, object)| {
+            let stateful = object.as_stateful_mut()?;
+            Some((id, stateful.state_mut().drain_changes()))
+        })
+        .collect();
+
+    for id in &ids {
+        if let Some(changes) = drained.remove(id) {
+            global.changes.extend(changes);
+        }
+    }
+}
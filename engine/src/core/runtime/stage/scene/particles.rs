@@ -0,0 +1,342 @@
+use crossterm::style::Color;
+use rustc_hash::FxHashMap;
+use std::f32::consts::TAU;
+use std::sync::Arc;
+
+use crate::prelude::{Glyph, Position, Tweenable};
+
+/// Look of a particle over its lifetime. The symbols and colours are
+/// spread evenly over the lifetime, colours blend between their steps.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParticleStyle {
+    pub symbols: Vec<char>,
+    pub colors: Vec<Color>,
+    pub z_index: u8,
+}
+
+impl ParticleStyle {
+    pub fn new(symbols: &str, colors: Vec<Color>, z_index: u8) -> Self {
+        Self {
+            symbols: symbols.chars().collect(),
+            colors,
+            z_index,
+        }
+    }
+
+    fn glyph(&self, t: f32) -> Glyph {
+        let symbol = match self.symbols.len() {
+            0 => '*',
+            len => self.symbols[((t * len as f32) as usize).min(len - 1)],
+        };
+
+        let fg_clr = match self.colors.len() {
+            0 => None,
+            1 => Some(self.colors[0]),
+            len => {
+                let step = t * (len - 1) as f32;
+                let index = (step as usize).min(len - 2);
+                Some(self.colors[index].lerp(&self.colors[index + 1], step - index as f32))
+            }
+        };
+
+        Glyph::new(fg_clr, None, symbol)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum EmitMode {
+    /// Spawns all particles at once
+    Burst(u32),
+    /// Spawns `rate` particles per tick, forever or for a number of ticks
+    Continuous { rate: f32, ticks: Option<u32> },
+}
+
+/// Spawns particles around an origin. Directions are angles in radians,
+/// where `0.0` points right and `PI / 2.0` points down.
+#[derive(Debug, Clone)]
+pub struct Emitter {
+    pub origin: Position,
+    mode: EmitMode,
+    direction: f32,
+    spread: f32,
+    speed: (f32, f32),
+    lifetime: (u32, u32),
+    gravity: f32,
+    style: Arc<ParticleStyle>,
+    carry: f32,
+}
+
+impl Emitter {
+    pub fn new(origin: Position, mode: EmitMode, style: ParticleStyle) -> Self {
+        Self {
+            origin,
+            mode,
+            direction: 0.0,
+            spread: TAU,
+            speed: (0.5, 1.0),
+            lifetime: (4, 8),
+            gravity: 0.0,
+            style: Arc::new(style),
+            carry: 0.0,
+        }
+    }
+
+    pub fn burst(origin: Position, count: u32, style: ParticleStyle) -> Self {
+        Self::new(origin, EmitMode::Burst(count), style)
+    }
+
+    pub fn continuous(origin: Position, rate: f32, style: ParticleStyle) -> Self {
+        Self::new(origin, EmitMode::Continuous { rate, ticks: None }, style)
+    }
+
+    /// Stops a continuous emitter after `ticks` ticks
+    pub fn duration(mut self, ticks: u32) -> Self {
+        if let EmitMode::Continuous { rate, .. } = self.mode {
+            self.mode = EmitMode::Continuous {
+                rate,
+                ticks: Some(ticks),
+            };
+        }
+        self
+    }
+
+    /// The centre direction and the full angle particles spread over
+    pub fn direction(mut self, direction: f32, spread: f32) -> Self {
+        self.direction = direction;
+        self.spread = spread;
+        self
+    }
+
+    /// Speed range in cells per tick
+    pub fn speed(mut self, min: f32, max: f32) -> Self {
+        self.speed = (min, max.max(min));
+        self
+    }
+
+    /// Lifetime range in ticks
+    pub fn lifetime(mut self, min: u32, max: u32) -> Self {
+        self.lifetime = (min.max(1), max.max(min.max(1)));
+        self
+    }
+
+    /// Added to the vertical velocity each tick
+    pub fn gravity(mut self, gravity: f32) -> Self {
+        self.gravity = gravity;
+        self
+    }
+
+    fn spawn(&self, rng: &mut Rng, particles: &mut Vec<Particle>, count: u32) {
+        for _ in 0..count {
+            let angle = self.direction + (rng.next_f32() - 0.5) * self.spread;
+            let speed = self.speed.0 + rng.next_f32() * (self.speed.1 - self.speed.0);
+            let lifetime =
+                self.lifetime.0 + rng.next_u32() % (self.lifetime.1 - self.lifetime.0 + 1);
+
+            particles.push(Particle {
+                x: self.origin.x as f32,
+                y: self.origin.y as f32,
+                vx: angle.cos() * speed,
+                // Terminal cells are about twice as high as wide
+                vy: angle.sin() * speed * 0.5,
+                gravity: self.gravity,
+                age: 0,
+                lifetime,
+                style: Arc::clone(&self.style),
+            });
+        }
+    }
+
+    /// Spawns this tick's particles, returns false once the emitter is done
+    fn emit(&mut self, rng: &mut Rng, particles: &mut Vec<Particle>) -> bool {
+        match &mut self.mode {
+            EmitMode::Burst(count) => {
+                let count = *count;
+                self.spawn(rng, particles, count);
+                false
+            }
+            EmitMode::Continuous { rate, ticks } => {
+                if *ticks == Some(0) {
+                    return false;
+                }
+                if let Some(ticks) = ticks {
+                    *ticks -= 1;
+                }
+
+                self.carry += *rate;
+                let count = self.carry as u32;
+                self.carry -= count as f32;
+                self.spawn(rng, particles, count);
+                true
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Particle {
+    x: f32,
+    y: f32,
+    vx: f32,
+    vy: f32,
+    gravity: f32,
+    age: u32,
+    lifetime: u32,
+    style: Arc<ParticleStyle>,
+}
+
+impl Particle {
+    fn pos(&self) -> Option<Position> {
+        let (x, y) = (self.x.round(), self.y.round());
+        if x < 0.0 || y < 0.0 || x > u16::MAX as f32 || y > u16::MAX as f32 {
+            return None;
+        }
+        Some(Position::new(x as u16, y as u16))
+    }
+}
+
+// Xorshift, keeps the simulation deterministic for a given seed
+#[derive(Debug, Clone)]
+struct Rng(u64);
+
+impl Rng {
+    fn next_u32(&mut self) -> u32 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        (self.0 >> 32) as u32
+    }
+
+    fn next_f32(&mut self) -> f32 {
+        self.next_u32() as f32 / u32::MAX as f32
+    }
+}
+
+/// Simulates short-lived single-cell particles outside of the object model.
+/// Particles are plain values in a flat list and are drawn on top of the
+/// scene by the renderer, which restores the cells they leave behind.
+#[derive(Debug)]
+pub struct ParticleSystem {
+    particles: Vec<Particle>,
+    emitters: Vec<Emitter>,
+    frame: FxHashMap<Position, (Glyph, u8)>,
+    vacated: Vec<Position>,
+    seed: u64,
+    rng: Rng,
+}
+
+impl ParticleSystem {
+    pub fn new() -> Self {
+        Self::with_seed(0x9E37_79B9_7F4A_7C15)
+    }
+
+    pub fn with_seed(seed: u64) -> Self {
+        Self {
+            particles: Vec::new(),
+            emitters: Vec::new(),
+            frame: FxHashMap::default(),
+            vacated: Vec::new(),
+            seed,
+            rng: Rng(seed.max(1)),
+        }
+    }
+
+    /// The seed the random numbers were last started from
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    /// Starts the random numbers over, the particles alive keep their paths
+    pub fn reseed(&mut self, seed: u64) {
+        self.seed = seed;
+        self.rng = Rng(seed.max(1));
+    }
+
+    /// Adds an emitter, it starts spawning on the next update
+    pub fn emit(&mut self, emitter: Emitter) {
+        self.emitters.push(emitter);
+    }
+
+    pub fn len(&self) -> usize {
+        self.particles.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.particles.is_empty() && self.emitters.is_empty() && self.frame.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.particles.clear();
+        self.emitters.clear();
+        self.frame.clear();
+        self.vacated.clear();
+    }
+
+    pub fn clear_emitters(&mut self) {
+        self.emitters.clear();
+    }
+
+    /// Spawns, moves and ages the particles and rebuilds the drawn frame
+    pub fn update(&mut self) {
+        self.vacated.clear();
+        if self.is_empty() {
+            return;
+        }
+
+        let rng = &mut self.rng;
+        let particles = &mut self.particles;
+        self.emitters
+            .retain_mut(|emitter| emitter.emit(rng, particles));
+
+        for particle in self.particles.iter_mut() {
+            particle.x += particle.vx;
+            particle.y += particle.vy;
+            particle.vy += particle.gravity;
+            particle.age += 1;
+        }
+        self.particles
+            .retain(|particle| particle.age < particle.lifetime && particle.pos().is_some());
+
+        let mut frame: FxHashMap<Position, (Glyph, u8)> = FxHashMap::default();
+        for particle in &self.particles {
+            let Some(pos) = particle.pos() else {
+                continue;
+            };
+            let t = particle.age as f32 / particle.lifetime as f32;
+            let z_index = particle.style.z_index;
+
+            // Younger particles are drawn on top of older ones
+            match frame.get(&pos) {
+                Some((_, z)) if *z > z_index => {}
+                _ => {
+                    frame.insert(pos, (particle.style.glyph(t), z_index));
+                }
+            }
+        }
+
+        self.vacated = self
+            .frame
+            .keys()
+            .filter(|pos| !frame.contains_key(pos))
+            .copied()
+            .collect();
+        self.frame = frame;
+    }
+
+    /// Cells covered by particles this tick
+    pub fn frame(&self) -> impl Iterator<Item = (Position, Glyph, u8)> + '_ {
+        self.frame
+            .iter()
+            .map(|(pos, (glyph, z_index))| (*pos, *glyph, *z_index))
+    }
+
+    /// Cells which were covered last tick but not anymore
+    pub fn vacated(&self) -> &[Position] {
+        &self.vacated
+    }
+}
+
+impl Default for ParticleSystem {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,89 @@
+use rustc_hash::FxHashMap;
+
+use crate::prelude::{Id, Object, Occupant, State, StateChange, TCell};
+
+/// The t_cells of every `Snapshot` object as of the last sync.
+/// Syncing diffs an object's current t_cells against its snapshot, so
+/// the object never has to record its own `StateChange`s.
+#[derive(Debug, Default)]
+pub struct Snapshots {
+    frames: FxHashMap<Id, FxHashMap<Occupant, TCell>>,
+}
+
+impl Snapshots {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn len(&self) -> usize {
+        self.frames.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.frames.is_empty()
+    }
+
+    pub fn get(&self, id: &Id) -> Option<&FxHashMap<Occupant, TCell>> {
+        self.frames.get(id)
+    }
+
+    /// Takes a snapshot without diffing, used once the object announced itself with `init`
+    pub fn record(&mut self, object: &dyn Object) {
+        let frame = object.t_cells().map(|t_cell| (t_cell.occ, *t_cell)).collect();
+        self.frames.insert(object.id(), frame);
+    }
+
+    /// Records the changes between the snapshot and the object's current t_cells
+    /// and replaces the snapshot
+    pub fn diff(&mut self, object: &dyn Object, state: &mut State) {
+        let mut previous = self.frames.remove(&object.id()).unwrap_or_default();
+        let mut frame = FxHashMap::default();
+
+        for t_cell in object.t_cells() {
+            let change = match previous.remove(&t_cell.occ) {
+                None => Some(StateChange::Create { new_t_cell: *t_cell }),
+                Some(old) if old != *t_cell => Some(StateChange::Update {
+                    t_cell: *t_cell,
+                    init_pos: old.pos,
+                }),
+                Some(_) => None,
+            };
+
+            // Merged with what's pending for the occupant, e.g. a create from `init` stays a create
+            if let Some(change) = change {
+                state.upsert_change(change);
+            }
+            frame.insert(t_cell.occ, *t_cell);
+        }
+
+        for (occupant, old) in previous {
+            state.upsert_change(StateChange::Delete {
+                occupant,
+                init_pos: old.pos,
+            });
+        }
+
+        self.frames.insert(object.id(), frame);
+    }
+
+    /// Drops an object's snapshot and deletes the t_cells it last synced
+    pub fn remove(&mut self, id: &Id) -> Option<FxHashMap<Occupant, StateChange>> {
+        let frame = self.frames.remove(id)?;
+        Some(
+            frame
+                .into_iter()
+                .map(|(occupant, t_cell)| {
+                    let change = StateChange::Delete {
+                        occupant,
+                        init_pos: t_cell.pos,
+                    };
+                    (occupant, change)
+                })
+                .collect(),
+        )
+    }
+
+    pub fn clear(&mut self) {
+        self.frames.clear();
+    }
+}
@@ -0,0 +1,262 @@
+use crate::core::global::next_generation;
+use crate::prelude::{Id, Object};
+
+const VACANT: u32 = u32::MAX;
+
+#[derive(Debug, Clone, Copy)]
+struct Slot {
+    generation: u32,
+    // Dense index, `VACANT` while the slot is free
+    index: u32,
+}
+
+/// Dense storage for the objects of a scene.
+/// The objects are packed into a `Vec` and found through a generational
+/// slot table, so lookups are a plain array access and iterating touches
+/// one contiguous block instead of hash map buckets. Removing swaps the
+/// last object into the freed spot.
+///
+/// An object's id is handed out by `next_id`: its value is the slot and its
+/// generation is new for every object. Freed slots are reused, an id which
+/// outlived its object doesn't match the slot's generation anymore.
+#[derive(Debug, Default)]
+pub struct ObjectStore {
+    objects: Vec<Box<dyn Object>>,
+    ids: Vec<Id>,
+    slots: Vec<Slot>,
+    // Vacant slots, reused before the table grows
+    free: Vec<u32>,
+}
+
+impl ObjectStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn len(&self) -> usize {
+        self.objects.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.objects.is_empty()
+    }
+
+    /// The id of the next inserted object
+    pub fn next_id(&self) -> Id {
+        let slot = self.free.last().copied().unwrap_or(self.slots.len() as u32);
+        Id {
+            value: slot as u64,
+            generation: next_generation(),
+        }
+    }
+
+    fn dense_index(&self, id: &Id) -> Option<usize> {
+        let slot = self.slots.get(usize::try_from(id.value).ok()?)?;
+        if slot.index == VACANT || slot.generation != id.generation {
+            return None;
+        }
+        Some(slot.index as usize)
+    }
+
+    pub fn contains_key(&self, id: &Id) -> bool {
+        self.dense_index(id).is_some()
+    }
+
+    pub fn get(&self, id: &Id) -> Option<&(dyn Object + 'static)> {
+        self.dense_index(id)
+            .map(|index| self.objects[index].as_ref())
+    }
+
+    pub fn get_mut(&mut self, id: &Id) -> Option<&mut Box<dyn Object>> {
+        self.dense_index(id).map(|index| &mut self.objects[index])
+    }
+
+    /// Two different objects at once, `None` if either is missing or the ids are equal
+    pub fn get_pair_mut(
+        &mut self,
+        a: &Id,
+        b: &Id,
+    ) -> Option<(&mut Box<dyn Object>, &mut Box<dyn Object>)> {
+        let (index_a, index_b) = (self.dense_index(a)?, self.dense_index(b)?);
+        if index_a == index_b {
+            return None;
+        }
+
+        let (low, high) = (index_a.min(index_b), index_a.max(index_b));
+        let (head, tail) = self.objects.split_at_mut(high);
+        let (low_obj, high_obj) = (&mut head[low], &mut tail[0]);
+        if index_a < index_b {
+            Some((low_obj, high_obj))
+        } else {
+            Some((high_obj, low_obj))
+        }
+    }
+
+    /// Several different objects at once, in storage order.
+    /// Missing and repeated ids are skipped.
+    pub fn get_many_mut<'a>(
+        &mut self,
+        ids: impl IntoIterator<Item = &'a Id>,
+    ) -> Vec<(Id, &mut Box<dyn Object>)> {
+        let mut indexes: Vec<usize> = ids
+            .into_iter()
+            .filter_map(|id| self.dense_index(id))
+            .collect();
+        indexes.sort_unstable();
+        indexes.dedup();
+
+        let mut found = Vec::with_capacity(indexes.len());
+        let mut rest = self.objects.as_mut_slice();
+        let mut offset = 0;
+        for index in indexes {
+            let (_, tail) = rest.split_at_mut(index - offset);
+            let Some((object, tail)) = tail.split_first_mut() else {
+                break;
+            };
+            found.push((self.ids[index], object));
+            rest = tail;
+            offset = index + 1;
+        }
+        found
+    }
+
+    /// Inserts an object under an id from `next_id`, returns the object
+    /// which held the slot before
+    pub fn insert(&mut self, id: Id, object: Box<dyn Object>) -> Option<Box<dyn Object>> {
+        let slot = id.value as usize;
+        if slot >= self.slots.len() {
+            // Only ids which weren't handed out by `next_id` skip slots
+            let vacant = Slot {
+                generation: 0,
+                index: VACANT,
+            };
+            self.free.extend(self.slots.len() as u32..slot as u32);
+            self.slots.resize(slot + 1, vacant);
+        }
+
+        let index = self.slots[slot].index;
+        if index != VACANT {
+            self.slots[slot].generation = id.generation;
+            self.ids[index as usize] = id;
+            return Some(std::mem::replace(&mut self.objects[index as usize], object));
+        }
+
+        // Usually the last free slot, unless objects were removed since `next_id`
+        if let Some(position) = self.free.iter().rposition(|free| *free as usize == slot) {
+            self.free.swap_remove(position);
+        }
+        self.slots[slot] = Slot {
+            generation: id.generation,
+            index: self.objects.len() as u32,
+        };
+        self.objects.push(object);
+        self.ids.push(id);
+        None
+    }
+
+    pub fn remove(&mut self, id: &Id) -> Option<Box<dyn Object>> {
+        let index = self.dense_index(id)?;
+        self.slots[id.value as usize].index = VACANT;
+        self.free.push(id.value as u32);
+
+        let object = self.objects.swap_remove(index);
+        self.ids.swap_remove(index);
+        if let Some(moved) = self.ids.get(index) {
+            self.slots[moved.value as usize].index = index as u32;
+        }
+        Some(object)
+    }
+
+    pub fn clear(&mut self) {
+        self.objects.clear();
+        self.ids.clear();
+        self.slots.clear();
+        self.free.clear();
+    }
+
+    pub fn ids(&self) -> impl Iterator<Item = &Id> {
+        self.ids.iter()
+    }
+
+    pub fn values(&self) -> impl Iterator<Item = &Box<dyn Object>> {
+        self.objects.iter()
+    }
+
+    pub fn values_mut(&mut self) -> impl Iterator<Item = &mut Box<dyn Object>> {
+        self.objects.iter_mut()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&Id, &Box<dyn Object>)> {
+        self.ids.iter().zip(self.objects.iter())
+    }
+
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = (&Id, &mut Box<dyn Object>)> {
+        self.ids.iter().zip(self.objects.iter_mut())
+    }
+}
+
+impl<'a> IntoIterator for &'a ObjectStore {
+    type Item = (&'a Id, &'a Box<dyn Object>);
+    type IntoIter = std::iter::Zip<std::slice::Iter<'a, Id>, std::slice::Iter<'a, Box<dyn Object>>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.ids.iter().zip(self.objects.iter())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prelude::{Rect, TextBlock};
+
+    fn insert(store: &mut ObjectStore) -> Id {
+        let id = store.next_id();
+        store.insert(id, Box::new(TextBlock::new(id, Rect::default())));
+        id
+    }
+
+    #[test]
+    fn freed_slots_are_reused() {
+        let mut store = ObjectStore::new();
+        let mut ids: Vec<Id> = (0..3).map(|_| insert(&mut store)).collect();
+
+        for _ in 0..100 {
+            store.remove(&ids[1]);
+            ids[1] = insert(&mut store);
+        }
+        assert_eq!(store.len(), 3);
+        assert_eq!(store.slots.len(), 3);
+    }
+
+    #[test]
+    fn stale_ids_miss_the_reused_slot() {
+        let mut store = ObjectStore::new();
+        let old = insert(&mut store);
+        let other = insert(&mut store);
+
+        assert!(store.remove(&old).is_some());
+        let new = insert(&mut store);
+        assert_eq!(new.value, old.value);
+        assert!(!store.contains_key(&old));
+        assert!(store.remove(&old).is_none());
+        assert_eq!(store.get(&new).map(|object| object.id()), Some(new));
+        assert_eq!(store.get(&other).map(|object| object.id()), Some(other));
+    }
+
+    #[test]
+    fn removing_before_insert_keeps_the_handed_out_slot() {
+        let mut store = ObjectStore::new();
+        let first = insert(&mut store);
+        let second = insert(&mut store);
+        store.remove(&first);
+
+        // A conflict removes another object between `next_id` and `insert`
+        let id = store.next_id();
+        store.remove(&second);
+        store.insert(id, Box::new(TextBlock::new(id, Rect::default())));
+
+        assert!(store.contains_key(&id));
+        assert_eq!(store.len(), 1);
+        assert_eq!(store.next_id().value, second.value);
+    }
+}
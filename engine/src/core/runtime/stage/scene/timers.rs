@@ -0,0 +1,334 @@
+use rustc_hash::FxHashMap;
+use std::time::Duration;
+
+use crate::prelude::{Event, Id, IdCounter, Scene};
+
+/// When a timer fires, counted in ticks or in game time.
+/// Game time advances by the tick rate each tick, so a timed delay keeps
+/// its length when the tick rate changes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Delay {
+    Ticks(u64),
+    Time(Duration),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct TimerHandle(Id);
+
+pub enum TimerAction {
+    Event(Box<dyn FnMut() -> Box<dyn Event> + Send>),
+    Callback(Box<dyn FnMut(&mut Scene) + Send>),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Due {
+    Tick(u64),
+    Time(Duration),
+}
+
+struct Timer {
+    due: Due,
+    period: Option<Delay>,
+    // Taken out while the timer fires
+    action: Option<TimerAction>,
+}
+
+/// One-shot and repeating timers of a scene. Timers only advance while the
+/// scene ticks, so they pause with their stage, and timers due on the same
+/// tick fire in the order they were scheduled (at most once per tick).
+/// A timed period shorter than a tick fires once per tick, the firings it
+/// missed in between are dropped instead of being caught up later.
+pub struct Scheduler {
+    timers: FxHashMap<Id, Timer>,
+    id_counter: IdCounter,
+    ticks: u64,
+    elapsed: Duration,
+    is_paused: bool,
+}
+
+impl Scheduler {
+    pub fn new() -> Self {
+        Self {
+            timers: FxHashMap::default(),
+            id_counter: IdCounter::new(),
+            ticks: 0,
+            elapsed: Duration::ZERO,
+            is_paused: false,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.timers.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.timers.is_empty()
+    }
+
+    /// Ticks the scheduler advanced since it was created or cleared
+    pub fn ticks(&self) -> u64 {
+        self.ticks
+    }
+
+    /// Game time the scheduler advanced since it was created or cleared
+    pub fn elapsed(&self) -> Duration {
+        self.elapsed
+    }
+
+    pub fn pause(&mut self) {
+        self.is_paused = true;
+    }
+
+    pub fn resume(&mut self) {
+        self.is_paused = false;
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.is_paused
+    }
+
+    /// Pushes a clone of the event once the delay passed
+    pub fn after<E: Event + Clone>(&mut self, delay: Delay, event: E) -> TimerHandle {
+        self.schedule(delay, None, Self::event_action(event))
+    }
+
+    /// Pushes a clone of the event every period
+    pub fn every<E: Event + Clone>(&mut self, period: Delay, event: E) -> TimerHandle {
+        self.schedule(period, Some(period), Self::event_action(event))
+    }
+
+    /// Runs the callback once the delay passed
+    pub fn after_fn<F>(&mut self, delay: Delay, callback: F) -> TimerHandle
+    where
+        F: FnMut(&mut Scene) + Send + 'static,
+    {
+        self.schedule(delay, None, TimerAction::Callback(Box::new(callback)))
+    }
+
+    /// Runs the callback every period
+    pub fn every_fn<F>(&mut self, period: Delay, callback: F) -> TimerHandle
+    where
+        F: FnMut(&mut Scene) + Send + 'static,
+    {
+        self.schedule(period, Some(period), TimerAction::Callback(Box::new(callback)))
+    }
+
+    /// Returns false if the timer already fired or was cancelled
+    pub fn cancel(&mut self, handle: TimerHandle) -> bool {
+        self.timers.remove(&handle.0).is_some()
+    }
+
+    pub fn is_scheduled(&self, handle: TimerHandle) -> bool {
+        self.timers.contains_key(&handle.0)
+    }
+
+    /// Time or ticks left until the timer fires next
+    pub fn remaining(&self, handle: TimerHandle) -> Option<Delay> {
+        self.timers.get(&handle.0).map(|timer| match timer.due {
+            Due::Tick(tick) => Delay::Ticks(tick.saturating_sub(self.ticks)),
+            Due::Time(time) => Delay::Time(time.saturating_sub(self.elapsed)),
+        })
+    }
+
+    pub fn clear(&mut self) {
+        self.timers.clear();
+        self.id_counter.reset();
+        self.ticks = 0;
+        self.elapsed = Duration::ZERO;
+        self.is_paused = false;
+    }
+
+    fn event_action<E: Event + Clone>(event: E) -> TimerAction {
+        TimerAction::Event(Box::new(move || Box::new(event.clone())))
+    }
+
+    fn due_after(&self, delay: Delay) -> Due {
+        match delay {
+            Delay::Ticks(ticks) => Due::Tick(self.ticks + ticks.max(1)),
+            Delay::Time(time) => Due::Time(self.elapsed + time),
+        }
+    }
+
+    fn schedule(
+        &mut self,
+        delay: Delay,
+        period: Option<Delay>,
+        action: TimerAction,
+    ) -> TimerHandle {
+        let id = self.id_counter.next();
+        let timer = Timer {
+            due: self.due_after(delay),
+            period,
+            action: Some(action),
+        };
+        self.timers.insert(id, timer);
+        TimerHandle(id)
+    }
+
+    /// Advances by one tick of `delta` game time, returns the due timers in order
+    pub(super) fn advance(&mut self, delta: Duration) -> Vec<TimerHandle> {
+        if self.is_paused {
+            return Vec::new();
+        }
+
+        self.ticks += 1;
+        self.elapsed += delta;
+        if self.timers.is_empty() {
+            return Vec::new();
+        }
+
+        let mut due: Vec<Id> = self
+            .timers
+            .iter()
+            .filter(|(_, timer)| match timer.due {
+                Due::Tick(tick) => tick <= self.ticks,
+                Due::Time(time) => time <= self.elapsed,
+            })
+            .map(|(id, _)| *id)
+            .collect();
+        due.sort_unstable_by_key(|id| id.value);
+        due.into_iter().map(TimerHandle).collect()
+    }
+
+    pub(super) fn take_action(&mut self, handle: TimerHandle) -> Option<TimerAction> {
+        self.timers.get_mut(&handle.0)?.action.take()
+    }
+
+    /// Reschedules a repeating timer after it fired, unless it was cancelled meanwhile
+    pub(super) fn finish(&mut self, handle: TimerHandle, action: TimerAction) {
+        let Some(timer) = self.timers.get_mut(&handle.0) else {
+            return;
+        };
+
+        match timer.period {
+            Some(Delay::Ticks(ticks)) => {
+                timer.due = Due::Tick(self.ticks + ticks.max(1));
+                timer.action = Some(action);
+            }
+            Some(Delay::Time(period)) => {
+                // Keeps the rhythm instead of drifting with the tick rate
+                if let Due::Time(time) = timer.due {
+                    timer.due = Due::Time(next_due(time, period, self.elapsed));
+                }
+                timer.action = Some(action);
+            }
+            None => {
+                self.timers.remove(&handle.0);
+            }
+        }
+    }
+}
+
+impl Default for Scheduler {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// The first time on the period's rhythm after `elapsed`, skipping the missed ones
+fn next_due(due: Duration, period: Duration, elapsed: Duration) -> Duration {
+    let period = period.max(Duration::from_nanos(1)).as_nanos();
+    let periods = elapsed.saturating_sub(due).as_nanos() / period + 1;
+    let next = due.as_nanos() + periods * period;
+    Duration::new((next / 1_000_000_000) as u64, (next % 1_000_000_000) as u32)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TICK: Duration = Duration::from_millis(100);
+
+    // Advances one tick and fires the due timers, returns which fired
+    fn tick(scheduler: &mut Scheduler) -> Vec<TimerHandle> {
+        let due = scheduler.advance(TICK);
+        for handle in &due {
+            let action = scheduler.take_action(*handle).unwrap();
+            scheduler.finish(*handle, action);
+        }
+        due
+    }
+
+    // The ticks (counting from 1) on which the timer fired
+    fn fired_on(scheduler: &mut Scheduler, handle: TimerHandle, ticks: u64) -> Vec<u64> {
+        (1..=ticks)
+            .filter(|_| tick(scheduler).contains(&handle))
+            .collect()
+    }
+
+    #[test]
+    fn tick_delays() {
+        let mut scheduler = Scheduler::new();
+        let handle = scheduler.after_fn(Delay::Ticks(3), |_| {});
+        assert_eq!(scheduler.remaining(handle), Some(Delay::Ticks(3)));
+
+        assert_eq!(fired_on(&mut scheduler, handle, 5), vec![3]);
+        assert!(!scheduler.is_scheduled(handle));
+    }
+
+    #[test]
+    fn time_delays() {
+        let mut scheduler = Scheduler::new();
+        let handle = scheduler.after_fn(Delay::Time(Duration::from_millis(250)), |_| {});
+
+        assert_eq!(fired_on(&mut scheduler, handle, 5), vec![3]);
+        assert_eq!(scheduler.elapsed(), TICK * 5);
+    }
+
+    #[test]
+    fn repeating_timers_keep_their_rhythm() {
+        let mut scheduler = Scheduler::new();
+        let by_ticks = scheduler.every_fn(Delay::Ticks(2), |_| {});
+        assert_eq!(fired_on(&mut scheduler, by_ticks, 6), vec![2, 4, 6]);
+
+        let mut scheduler = Scheduler::new();
+        let by_time = scheduler.every_fn(Delay::Time(Duration::from_millis(150)), |_| {});
+        // Due at 150, 300, 450 and 600ms
+        assert_eq!(fired_on(&mut scheduler, by_time, 6), vec![2, 3, 5, 6]);
+    }
+
+    #[test]
+    fn periods_shorter_than_a_tick_drop_the_backlog() {
+        let mut scheduler = Scheduler::new();
+        let period = Duration::from_millis(30);
+        let handle = scheduler.every_fn(Delay::Time(period), |_| {});
+
+        assert_eq!(
+            fired_on(&mut scheduler, handle, 10),
+            (1..=10).collect::<Vec<_>>()
+        );
+        // Still on its rhythm, due at the next 30ms step after 1s
+        assert_eq!(
+            scheduler.remaining(handle),
+            Some(Delay::Time(Duration::from_millis(20)))
+        );
+    }
+
+    #[test]
+    fn cancelled_timers_never_fire() {
+        let mut scheduler = Scheduler::new();
+        let handle = scheduler.every_fn(Delay::Ticks(1), |_| {});
+        assert_eq!(tick(&mut scheduler), vec![handle]);
+
+        assert!(scheduler.cancel(handle));
+        assert!(!scheduler.cancel(handle));
+        assert!(tick(&mut scheduler).is_empty());
+        assert_eq!(scheduler.remaining(handle), None);
+    }
+
+    #[test]
+    fn paused_schedulers_stand_still() {
+        let mut scheduler = Scheduler::new();
+        let handle = scheduler.after_fn(Delay::Ticks(2), |_| {});
+        tick(&mut scheduler);
+
+        scheduler.pause();
+        assert!(tick(&mut scheduler).is_empty());
+        assert!(tick(&mut scheduler).is_empty());
+        assert_eq!(scheduler.ticks(), 1);
+        assert_eq!(scheduler.elapsed(), TICK);
+
+        scheduler.resume();
+        assert_eq!(tick(&mut scheduler), vec![handle]);
+    }
+}
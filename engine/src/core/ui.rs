@@ -0,0 +1,115 @@
+//! Retained-mode widgets. Each widget is a `Snapshot` object which redraws its
+//! `Canvas` when its content changes, the sync then only emits the characters
+//! that actually changed.
+
+use crossterm::style::Color;
+
+pub mod border;
+pub mod canvas;
+pub mod label;
+pub mod layout;
+pub mod log_view;
+pub mod menu;
+pub mod progress_bar;
+pub mod table;
+pub mod text_block;
+pub mod text_input;
+
+pub use border::Border;
+pub use canvas::Canvas;
+pub use label::Label;
+pub use layout::{Align, Anchor, Axis, Layout, Node, Size};
+pub use log_view::LogView;
+pub use menu::Menu;
+pub use progress_bar::ProgressBar;
+pub use table::Table;
+pub use text_block::TextBlock;
+pub use text_input::TextInput;
+
+use crate::prelude::{Glyph, Object, Position};
+
+/// Widgets are drawn above the grid
+pub const UI_Z_INDEX: u8 = 253;
+/// Debug overlays like the console are drawn above the other widgets
+pub const OVERLAY_Z_INDEX: u8 = 255;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Rect {
+    pub x: u16,
+    pub y: u16,
+    pub width: u16,
+    pub height: u16,
+}
+
+impl Rect {
+    pub fn new(x: u16, y: u16, width: u16, height: u16) -> Self {
+        Self {
+            x,
+            y,
+            width,
+            height,
+        }
+    }
+
+    pub fn origin(&self) -> Position {
+        Position::new(self.x, self.y)
+    }
+
+    /// First column right of the rect
+    pub fn right(&self) -> u16 {
+        self.x.saturating_add(self.width)
+    }
+
+    /// First row below the rect
+    pub fn bottom(&self) -> u16 {
+        self.y.saturating_add(self.height)
+    }
+
+    pub fn contains(&self, pos: Position) -> bool {
+        (self.x..self.right()).contains(&pos.x) && (self.y..self.bottom()).contains(&pos.y)
+    }
+
+    /// The rect shrunk by `margin` on every side
+    pub fn inner(&self, margin: u16) -> Rect {
+        Rect::new(
+            self.x.saturating_add(margin),
+            self.y.saturating_add(margin),
+            self.width.saturating_sub(margin * 2),
+            self.height.saturating_sub(margin * 2),
+        )
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Style {
+    pub fg: Option<Color>,
+    pub bg: Option<Color>,
+}
+
+impl Style {
+    pub fn new(fg: Option<Color>, bg: Option<Color>) -> Self {
+        Self { fg, bg }
+    }
+
+    pub fn fg(color: Color) -> Self {
+        Self::new(Some(color), None)
+    }
+
+    /// Swaps the colours, used to highlight selections
+    pub fn inverted(&self) -> Self {
+        Self::new(
+            Some(self.bg.unwrap_or(Color::Black)),
+            Some(self.fg.unwrap_or(Color::White)),
+        )
+    }
+
+    pub fn glyph(&self, symbol: char) -> Glyph {
+        Glyph::new(self.fg, self.bg, symbol)
+    }
+}
+
+/// An object drawn into a rect, which layouts can move and resize
+pub trait Widget: Object {
+    fn rect(&self) -> Rect;
+    fn set_rect(&mut self, rect: Rect);
+}
@@ -0,0 +1,123 @@
+use super::{Canvas, Rect, Style, UI_Z_INDEX, Widget};
+use crate::prelude::{Id, TCell};
+
+/// A box around its rect with an optional title in the top edge.
+/// Widgets go inside `rect.inner(1)`, the box is drawn just below them so a
+/// fill covers whatever is behind it without hiding them.
+#[derive(Debug)]
+pub struct Border {
+    id: Id,
+    canvas: Canvas,
+    title: Option<String>,
+    style: Style,
+    fill: Option<Style>,
+}
+
+impl Border {
+    pub fn new(id: Id, rect: Rect) -> Self {
+        let mut canvas = Canvas::new(id, rect);
+        canvas.set_z_index(UI_Z_INDEX - 1);
+
+        let mut border = Self {
+            id,
+            canvas,
+            title: None,
+            style: Style::default(),
+            fill: None,
+        };
+        border.redraw();
+        border
+    }
+
+    pub fn title(mut self, title: impl Into<String>) -> Self {
+        self.title = Some(title.into());
+        self.redraw();
+        self
+    }
+
+    pub fn style(mut self, style: Style) -> Self {
+        self.style = style;
+        self.redraw();
+        self
+    }
+
+    pub fn fill(mut self, fill: Style) -> Self {
+        self.fill = Some(fill);
+        self.redraw();
+        self
+    }
+
+    /// The widgets inside the box are expected at `z_index`
+    pub fn z_index(mut self, z_index: u8) -> Self {
+        self.canvas.set_z_index(z_index.saturating_sub(1));
+        self.redraw();
+        self
+    }
+
+    pub fn set_title(&mut self, title: Option<String>) {
+        if self.title != title {
+            self.title = title;
+            self.redraw();
+        }
+    }
+
+    fn redraw(&mut self) {
+        self.canvas.clear();
+        let Rect { width, height, .. } = self.canvas.rect();
+        if width < 2 || height < 2 {
+            return;
+        }
+
+        if let Some(fill) = self.fill {
+            self.canvas.fill(' ', fill);
+        }
+
+        let (right, bottom) = (width - 1, height - 1);
+        for x in 1..right {
+            self.canvas.put(x, 0, self.style.glyph('─'));
+            self.canvas.put(x, bottom, self.style.glyph('─'));
+        }
+        for y in 1..bottom {
+            self.canvas.put(0, y, self.style.glyph('│'));
+            self.canvas.put(right, y, self.style.glyph('│'));
+        }
+        self.canvas.put(0, 0, self.style.glyph('┌'));
+        self.canvas.put(right, 0, self.style.glyph('┐'));
+        self.canvas.put(0, bottom, self.style.glyph('└'));
+        self.canvas.put(right, bottom, self.style.glyph('┘'));
+
+        // Stays clear of the corners
+        if let Some(title) = &self.title {
+            let title: String = format!(" {} ", title)
+                .chars()
+                .take(width.saturating_sub(4) as usize)
+                .collect();
+            self.canvas.print(2, 0, &title, self.style);
+        }
+    }
+
+    fn get_t_cells(&self) -> Box<dyn Iterator<Item = &TCell> + '_> {
+        self.canvas.t_cells()
+    }
+}
+
+impl Widget for Border {
+    fn rect(&self) -> Rect {
+        self.canvas.rect()
+    }
+
+    fn set_rect(&mut self, rect: Rect) {
+        self.canvas.set_rect(rect);
+        self.redraw();
+    }
+}
+
+crate::define_object! {
+    struct Border,
+    id_field: id,
+    t_cells: custom(get_t_cells),
+    capabilities: {
+        Snapshot {}
+        Destructible {}
+    }
+}
@@ -0,0 +1,87 @@
+use rustc_hash::FxHashMap;
+
+use super::{Rect, Style, UI_Z_INDEX};
+use crate::prelude::{Glyph, Id, Occupant, Position, TCell};
+
+/// The t_cells of a widget, clipped to its rect.
+/// The t_cell ids follow the offset inside the rect, so a character which
+/// didn't change keeps its id and the snapshot diff skips it. Drawing over
+/// an offset replaces what was drawn there before.
+#[derive(Debug, Clone)]
+pub struct Canvas {
+    obj_id: Id,
+    rect: Rect,
+    z_index: u8,
+    t_cells: FxHashMap<Id, TCell>,
+}
+
+impl Canvas {
+    pub fn new(obj_id: Id, rect: Rect) -> Self {
+        Self {
+            obj_id,
+            rect,
+            z_index: UI_Z_INDEX,
+            t_cells: FxHashMap::default(),
+        }
+    }
+
+    pub fn rect(&self) -> Rect {
+        self.rect
+    }
+
+    /// Only moves or clips the next draws, the widget redraws afterwards
+    pub fn set_rect(&mut self, rect: Rect) {
+        self.rect = rect;
+    }
+
+    pub fn set_z_index(&mut self, z_index: u8) {
+        self.z_index = z_index;
+    }
+
+    pub fn clear(&mut self) {
+        self.t_cells.clear();
+    }
+
+    /// Draws a glyph at an offset inside the rect, offsets outside are dropped
+    pub fn put(&mut self, x: u16, y: u16, glyph: Glyph) {
+        if x >= self.rect.width || y >= self.rect.height {
+            return;
+        }
+
+        let t_cell_id = Id::new(((y as u64) << 16) | x as u64);
+        let t_cell = TCell::new(
+            Occupant::new(self.obj_id, t_cell_id),
+            glyph,
+            Some(Position::new(self.rect.x + x, self.rect.y + y)),
+            self.z_index,
+        );
+        self.t_cells.insert(t_cell_id, t_cell);
+    }
+
+    /// Prints a single line and returns the columns it took up
+    pub fn print(&mut self, x: u16, y: u16, text: &str, style: Style) -> u16 {
+        let mut width = 0;
+        for (offset, symbol) in text.chars().enumerate() {
+            let column = x.saturating_add(offset as u16);
+            if column >= self.rect.width {
+                break;
+            }
+            self.put(column, y, style.glyph(symbol));
+            width += 1;
+        }
+        width
+    }
+
+    /// Fills the whole rect with a symbol
+    pub fn fill(&mut self, symbol: char, style: Style) {
+        for y in 0..self.rect.height {
+            for x in 0..self.rect.width {
+                self.put(x, y, style.glyph(symbol));
+            }
+        }
+    }
+
+    pub fn t_cells(&self) -> Box<dyn Iterator<Item = &TCell> + '_> {
+        Box::new(self.t_cells.values())
+    }
+}
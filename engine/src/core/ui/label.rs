@@ -0,0 +1,79 @@
+use super::{Canvas, Rect, Style, Widget};
+use crate::prelude::{Id, TCell};
+
+/// A single line of text, cut off at the width of its rect
+#[derive(Debug)]
+pub struct Label {
+    id: Id,
+    canvas: Canvas,
+    text: String,
+    style: Style,
+}
+
+impl Label {
+    pub fn new(id: Id, rect: Rect, text: impl Into<String>) -> Self {
+        let mut label = Self {
+            id,
+            canvas: Canvas::new(id, rect),
+            text: text.into(),
+            style: Style::default(),
+        };
+        label.redraw();
+        label
+    }
+
+    pub fn style(mut self, style: Style) -> Self {
+        self.style = style;
+        self.redraw();
+        self
+    }
+
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    pub fn set_text(&mut self, text: impl Into<String>) {
+        let text = text.into();
+        if self.text != text {
+            self.text = text;
+            self.redraw();
+        }
+    }
+
+    pub fn set_style(&mut self, style: Style) {
+        if self.style != style {
+            self.style = style;
+            self.redraw();
+        }
+    }
+
+    fn redraw(&mut self) {
+        self.canvas.clear();
+        self.canvas.print(0, 0, &self.text, self.style);
+    }
+
+    fn get_t_cells(&self) -> Box<dyn Iterator<Item = &TCell> + '_> {
+        self.canvas.t_cells()
+    }
+}
+
+impl Widget for Label {
+    fn rect(&self) -> Rect {
+        self.canvas.rect()
+    }
+
+    fn set_rect(&mut self, rect: Rect) {
+        self.canvas.set_rect(rect);
+        self.redraw();
+    }
+}
+
+crate::define_object! {
+    struct Label,
+    id_field: id,
+    t_cells: custom(get_t_cells),
+    capabilities: {
+        Snapshot {}
+        Destructible {}
+    }
+}
@@ -0,0 +1,540 @@
+use rustc_hash::FxHashMap;
+use std::hash::Hash;
+
+use super::{Rect, Widget};
+use crate::prelude::{Handle, Id, Scene};
+
+/// A length resolved against the space left in the direction a node grows
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Size {
+    Fixed(u16),
+    /// Percentage of the available space, capped at 100
+    Percent(u16),
+    /// All of the available space
+    Fill,
+}
+
+impl Size {
+    fn resolve(self, available: u16) -> u16 {
+        match self {
+            Size::Fixed(size) => size,
+            Size::Percent(percent) => (available as u32 * percent.min(100) as u32 / 100) as u16,
+            Size::Fill => available,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Align {
+    TopLeft,
+    Top,
+    TopRight,
+    Left,
+    Center,
+    Right,
+    BottomLeft,
+    Bottom,
+    BottomRight,
+}
+
+// Offset of a length inside the available length
+type Offset = fn(u16, u16) -> u16;
+
+impl Align {
+    // For both axes
+    fn offsets(self) -> (Offset, Offset) {
+        let start = |_: u16, _: u16| 0;
+        let center = |available: u16, size: u16| available.saturating_sub(size) / 2;
+        let end = |available: u16, size: u16| available.saturating_sub(size);
+
+        match self {
+            Align::TopLeft => (start, start),
+            Align::Top => (center, start),
+            Align::TopRight => (end, start),
+            Align::Left => (start, center),
+            Align::Center => (center, center),
+            Align::Right => (end, center),
+            Align::BottomLeft => (start, end),
+            Align::Bottom => (center, end),
+            Align::BottomRight => (end, end),
+        }
+    }
+}
+
+/// Where a node is placed, the gap is the distance to the screen edges or the target
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Anchor<K> {
+    Screen(Align),
+    /// Inside the target's rect
+    Within(K, Align),
+    /// Right of the target, sharing its top edge
+    RightOf(K),
+    /// Left of the target, sharing its top edge
+    LeftOf(K),
+    /// Below the target, sharing its left edge
+    Below(K),
+    /// Above the target, sharing its left edge
+    Above(K),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Node<K> {
+    anchor: Anchor<K>,
+    width: Size,
+    height: Size,
+    min_size: (u16, u16),
+    max_size: (u16, u16),
+    gap: u16,
+}
+
+impl<K> Node<K> {
+    pub fn new(anchor: Anchor<K>, width: Size, height: Size) -> Self {
+        Self {
+            anchor,
+            width,
+            height,
+            min_size: (0, 0),
+            max_size: (u16::MAX, u16::MAX),
+            gap: 0,
+        }
+    }
+
+    /// The screen edge still wins over the minimum, nodes never leave the screen
+    pub fn min_size(mut self, width: u16, height: u16) -> Self {
+        self.min_size = (width, height);
+        self
+    }
+
+    pub fn max_size(mut self, width: u16, height: u16) -> Self {
+        self.max_size = (width, height);
+        self
+    }
+
+    pub fn gap(mut self, gap: u16) -> Self {
+        self.gap = gap;
+        self
+    }
+
+    fn size(&self, available: (u16, u16)) -> (u16, u16) {
+        let clamp = |size: Size, available: u16, min: u16, max: u16| {
+            size.resolve(available).max(min).min(max).min(available)
+        };
+        (
+            clamp(self.width, available.0, self.min_size.0, self.max_size.0),
+            clamp(self.height, available.1, self.min_size.1, self.max_size.1),
+        )
+    }
+
+    fn place(&self, target: Option<Rect>, screen: Rect) -> Option<Rect> {
+        let gap = self.gap;
+        let rect = match (&self.anchor, target) {
+            (Anchor::Screen(align), _) => Self::align(screen.inner(gap), *align, self),
+            (Anchor::Within(_, align), Some(target)) => {
+                Self::align(target.inner(gap), *align, self)
+            }
+            (Anchor::RightOf(_), Some(target)) => {
+                let (x, y) = (target.right().saturating_add(gap), target.y);
+                let (width, height) = self.size((
+                    screen.right().saturating_sub(x),
+                    screen.bottom().saturating_sub(y),
+                ));
+                Rect::new(x, y, width, height)
+            }
+            (Anchor::LeftOf(_), Some(target)) => {
+                let (right, y) = (target.x.saturating_sub(gap), target.y);
+                let (width, height) = self.size((
+                    right.saturating_sub(screen.x),
+                    screen.bottom().saturating_sub(y),
+                ));
+                Rect::new(right - width, y, width, height)
+            }
+            (Anchor::Below(_), Some(target)) => {
+                let (x, y) = (target.x, target.bottom().saturating_add(gap));
+                let (width, height) = self.size((
+                    screen.right().saturating_sub(x),
+                    screen.bottom().saturating_sub(y),
+                ));
+                Rect::new(x, y, width, height)
+            }
+            (Anchor::Above(_), Some(target)) => {
+                let (x, bottom) = (target.x, target.y.saturating_sub(gap));
+                let (width, height) = self.size((
+                    screen.right().saturating_sub(x),
+                    bottom.saturating_sub(screen.y),
+                ));
+                Rect::new(x, bottom - height, width, height)
+            }
+            (_, None) => return None,
+        };
+        Some(rect)
+    }
+
+    fn align(area: Rect, align: Align, node: &Self) -> Rect {
+        let (width, height) = node.size((area.width, area.height));
+        let (x_offset, y_offset) = align.offsets();
+        Rect::new(
+            area.x + x_offset(area.width, width),
+            area.y + y_offset(area.height, height),
+            width,
+            height,
+        )
+    }
+}
+
+// Moves a bound widget, returns false once it's gone from the scene
+type ApplyFn = fn(&mut Scene, Id, Rect) -> bool;
+
+/// Places rects on the screen by anchoring them to its edges, to each other or
+/// to rects set from the outside (like the grid's). Widgets bound to a node are
+/// moved and resized by `apply`, which is all a terminal resize takes.
+/// Nodes are placed in the order they were inserted, so an anchor can only
+/// refer to a fixed rect or a node inserted before it.
+#[derive(Debug, Clone)]
+pub struct Layout<K: Eq + Hash + Clone> {
+    screen: Rect,
+    nodes: Vec<(K, Node<K>)>,
+    fixed: FxHashMap<K, Rect>,
+    rects: FxHashMap<K, Rect>,
+    bindings: Vec<(K, Id, ApplyFn)>,
+}
+
+impl<K: Eq + Hash + Clone> Layout<K> {
+    pub fn new(width: u16, height: u16) -> Self {
+        Self {
+            screen: Rect::new(0, 0, width, height),
+            nodes: Vec::new(),
+            fixed: FxHashMap::default(),
+            rects: FxHashMap::default(),
+            bindings: Vec::new(),
+        }
+    }
+
+    /// Sized to the current terminal
+    pub fn from_terminal() -> Self {
+        let (width, height) = crossterm::terminal::size().unwrap_or((80, 24));
+        Self::new(width, height)
+    }
+
+    /// Resizes to the current terminal, for layouts which missed a resize event
+    pub fn fit_terminal(&mut self) -> bool {
+        match crossterm::terminal::size() {
+            Ok((width, height)) => self.resize(width, height),
+            Err(_) => false,
+        }
+    }
+
+    pub fn screen(&self) -> Rect {
+        self.screen
+    }
+
+    /// Returns false if the size didn't change
+    pub fn resize(&mut self, width: u16, height: u16) -> bool {
+        let screen = Rect::new(0, 0, width, height);
+        if self.screen == screen {
+            return false;
+        }
+        self.screen = screen;
+        self.compute();
+        true
+    }
+
+    /// Replaces a node with the same key in place, new nodes go last
+    pub fn insert(&mut self, key: K, node: Node<K>) {
+        match self.nodes.iter_mut().find(|(node_key, _)| *node_key == key) {
+            Some((_, old_node)) => *old_node = node,
+            None => self.nodes.push((key, node)),
+        }
+        self.compute();
+    }
+
+    /// A rect which isn't placed by the layout, but nodes can be anchored to
+    pub fn set_rect(&mut self, key: K, rect: Rect) {
+        if self.fixed.get(&key) != Some(&rect) {
+            self.fixed.insert(key, rect);
+            self.compute();
+        }
+    }
+
+    pub fn rect(&self, key: &K) -> Option<Rect> {
+        self.rects.get(key).or_else(|| self.fixed.get(key)).copied()
+    }
+
+    /// The widget follows the node's rect on every `apply`
+    pub fn bind<W: Widget + 'static>(&mut self, key: K, handle: Handle<W>) {
+        let apply_fn: ApplyFn = |scene, id, rect| {
+            let Some(widget) = Handle::<W>::new(id).get_mut(&mut scene.objects) else {
+                return false;
+            };
+            if widget.rect() != rect {
+                widget.set_rect(rect);
+            }
+            true
+        };
+        self.bindings.push((key, handle.id(), apply_fn));
+    }
+
+    /// Moves the bound widgets to their nodes, bindings of removed widgets are dropped
+    pub fn apply(&mut self, scene: &mut Scene) {
+        let rects = &self.rects;
+        self.bindings
+            .retain(|(key, id, apply_fn)| match rects.get(key) {
+                Some(rect) => apply_fn(scene, *id, *rect),
+                None => true,
+            });
+    }
+
+    fn compute(&mut self) {
+        self.rects.clear();
+        for (key, node) in &self.nodes {
+            let target = match &node.anchor {
+                Anchor::Screen(_) => None,
+                Anchor::Within(target, _)
+                | Anchor::RightOf(target)
+                | Anchor::LeftOf(target)
+                | Anchor::Below(target)
+                | Anchor::Above(target) => self
+                    .rects
+                    .get(target)
+                    .or_else(|| self.fixed.get(target))
+                    .copied(),
+            };
+
+            if let Some(rect) = node.place(target, self.screen) {
+                self.rects.insert(key.clone(), rect);
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Axis {
+    /// Side by side
+    Horizontal,
+    /// Stacked
+    Vertical,
+}
+
+impl Rect {
+    /// Splits the rect along an axis. Fixed and percentage sizes are resolved
+    /// against the whole length, fills share what's left equally.
+    /// Sizes which don't fit anymore are cut off at the end.
+    pub fn split(&self, axis: Axis, sizes: &[Size]) -> Vec<Rect> {
+        let length = match axis {
+            Axis::Horizontal => self.width,
+            Axis::Vertical => self.height,
+        };
+
+        let taken: u16 = sizes
+            .iter()
+            .filter(|size| **size != Size::Fill)
+            .fold(0, |taken, size| taken.saturating_add(size.resolve(length)));
+        let fills = sizes.iter().filter(|size| **size == Size::Fill).count() as u16;
+        let remaining = length.saturating_sub(taken);
+        let mut fill_rest = remaining % fills.max(1);
+
+        let mut offset = 0;
+        sizes
+            .iter()
+            .map(|size| {
+                let mut part = match size {
+                    Size::Fill => remaining / fills,
+                    size => size.resolve(length),
+                };
+                if *size == Size::Fill && fill_rest > 0 {
+                    part += 1;
+                    fill_rest -= 1;
+                }
+                let part = part.min(length - offset);
+                let rect = match axis {
+                    Axis::Horizontal => Rect::new(self.x + offset, self.y, part, self.height),
+                    Axis::Vertical => Rect::new(self.x, self.y + offset, self.width, part),
+                };
+                offset += part;
+                rect
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn widths(rects: &[Rect]) -> Vec<(u16, u16)> {
+        rects.iter().map(|rect| (rect.x, rect.width)).collect()
+    }
+
+    #[test]
+    fn split_without_sizes_is_empty() {
+        let rect = Rect::new(0, 0, 10, 10);
+        assert!(rect.split(Axis::Horizontal, &[]).is_empty());
+    }
+
+    #[test]
+    fn split_zero_size_rect() {
+        let rect = Rect::new(3, 4, 0, 0);
+        let parts = rect.split(Axis::Horizontal, &[Size::Fill, Size::Fixed(3)]);
+        assert_eq!(widths(&parts), vec![(3, 0), (3, 0)]);
+
+        let parts = rect.split(Axis::Vertical, &[Size::Percent(50), Size::Fill]);
+        assert!(parts.iter().all(|part| part.height == 0 && part.y == 4));
+    }
+
+    #[test]
+    fn split_fills_share_the_remainder_from_the_start() {
+        let rect = Rect::new(2, 0, 10, 1);
+        let parts = rect.split(Axis::Horizontal, &[Size::Fill, Size::Fill, Size::Fill]);
+        assert_eq!(widths(&parts), vec![(2, 4), (6, 3), (9, 3)]);
+    }
+
+    #[test]
+    fn split_fills_get_what_fixed_parts_leave() {
+        let rect = Rect::new(0, 1, 5, 20);
+        let parts = rect.split(
+            Axis::Vertical,
+            &[
+                Size::Fixed(2),
+                Size::Fill,
+                Size::Percent(25),
+                Size::Fixed(1),
+            ],
+        );
+        let heights: Vec<(u16, u16)> = parts.iter().map(|part| (part.y, part.height)).collect();
+        assert_eq!(heights, vec![(1, 2), (3, 12), (15, 5), (20, 1)]);
+        assert!(parts.iter().all(|part| part.x == 0 && part.width == 5));
+    }
+
+    #[test]
+    fn split_cuts_oversized_parts_at_the_end() {
+        let rect = Rect::new(0, 0, 10, 1);
+        let parts = rect.split(
+            Axis::Horizontal,
+            &[
+                Size::Fixed(6),
+                Size::Fixed(6),
+                Size::Fill,
+                Size::Fixed(u16::MAX),
+            ],
+        );
+        assert_eq!(widths(&parts), vec![(0, 6), (6, 4), (10, 0), (10, 0)]);
+    }
+
+    #[test]
+    fn percent_is_capped_at_the_whole_length() {
+        let rect = Rect::new(0, 0, 10, 1);
+        let parts = rect.split(Axis::Horizontal, &[Size::Percent(150), Size::Fill]);
+        assert_eq!(widths(&parts), vec![(0, 10), (10, 0)]);
+    }
+
+    #[test]
+    fn screen_anchor_aligns_inside_the_gap() {
+        let screen = Rect::new(0, 0, 20, 10);
+        let node: Node<u8> = Node::new(
+            Anchor::Screen(Align::BottomRight),
+            Size::Fixed(4),
+            Size::Fixed(2),
+        )
+        .gap(1);
+        assert_eq!(node.place(None, screen), Some(Rect::new(15, 7, 4, 2)));
+
+        let node: Node<u8> = Node::new(Anchor::Screen(Align::Center), Size::Fill, Size::Fixed(3));
+        assert_eq!(node.place(None, screen), Some(Rect::new(0, 3, 20, 3)));
+    }
+
+    #[test]
+    fn min_size_gives_way_to_the_screen_edge() {
+        let screen = Rect::new(0, 0, 8, 4);
+        let node: Node<u8> = Node::new(
+            Anchor::Screen(Align::TopLeft),
+            Size::Fixed(2),
+            Size::Fixed(1),
+        )
+        .min_size(20, 10);
+        assert_eq!(node.place(None, screen), Some(screen));
+
+        let node: Node<u8> =
+            Node::new(Anchor::Screen(Align::TopLeft), Size::Fill, Size::Fill).max_size(3, 2);
+        assert_eq!(node.place(None, screen), Some(Rect::new(0, 0, 3, 2)));
+    }
+
+    #[test]
+    fn left_of_ends_before_the_target() {
+        let screen = Rect::new(0, 0, 40, 20);
+        let target = Rect::new(10, 5, 6, 3);
+        let node = Node::new(Anchor::LeftOf(0), Size::Fixed(4), Size::Fixed(2)).gap(1);
+        assert_eq!(
+            node.place(Some(target), screen),
+            Some(Rect::new(5, 5, 4, 2))
+        );
+
+        // Only the columns left of the target are available
+        let node = Node::new(Anchor::LeftOf(0), Size::Fixed(30), Size::Fixed(2));
+        assert_eq!(
+            node.place(Some(target), screen),
+            Some(Rect::new(0, 5, 10, 2))
+        );
+    }
+
+    #[test]
+    fn left_of_and_above_a_target_on_the_edge_are_empty() {
+        let screen = Rect::new(0, 0, 40, 20);
+        let target = Rect::new(0, 0, 6, 3);
+
+        let node = Node::new(Anchor::LeftOf(0), Size::Fixed(4), Size::Fixed(2)).gap(2);
+        assert_eq!(
+            node.place(Some(target), screen),
+            Some(Rect::new(0, 0, 0, 2))
+        );
+
+        let node = Node::new(Anchor::Above(0), Size::Fixed(4), Size::Fixed(2)).gap(2);
+        assert_eq!(
+            node.place(Some(target), screen),
+            Some(Rect::new(0, 0, 4, 0))
+        );
+    }
+
+    #[test]
+    fn above_is_clamped_to_the_rows_over_the_target() {
+        let screen = Rect::new(0, 0, 40, 20);
+        let target = Rect::new(4, 3, 6, 3);
+        let node = Node::new(Anchor::Above(0), Size::Fill, Size::Fixed(5));
+        assert_eq!(
+            node.place(Some(target), screen),
+            Some(Rect::new(4, 0, 36, 3))
+        );
+    }
+
+    #[test]
+    fn right_of_and_below_stop_at_the_screen() {
+        let screen = Rect::new(0, 0, 20, 10);
+        let target = Rect::new(15, 8, 5, 2);
+
+        let node = Node::new(Anchor::RightOf(0), Size::Fixed(4), Size::Fixed(4)).gap(1);
+        assert_eq!(
+            node.place(Some(target), screen),
+            Some(Rect::new(21, 8, 0, 2))
+        );
+
+        let node = Node::new(Anchor::Below(0), Size::Fixed(4), Size::Fixed(4));
+        assert_eq!(
+            node.place(Some(target), screen),
+            Some(Rect::new(15, 10, 4, 0))
+        );
+    }
+
+    #[test]
+    fn missing_targets_leave_the_node_out() {
+        let mut layout: Layout<&str> = Layout::new(20, 10);
+        layout.insert(
+            "panel",
+            Node::new(Anchor::RightOf("grid"), Size::Fill, Size::Fill),
+        );
+        assert_eq!(layout.rect(&"panel"), None);
+
+        layout.set_rect("grid", Rect::new(0, 0, 12, 10));
+        assert_eq!(layout.rect(&"panel"), Some(Rect::new(12, 0, 8, 10)));
+
+        layout.resize(16, 10);
+        assert_eq!(layout.rect(&"panel"), Some(Rect::new(12, 0, 4, 10)));
+    }
+}
@@ -0,0 +1,83 @@
+use std::collections::VecDeque;
+
+use super::{Canvas, Rect, Style, Widget};
+use crate::prelude::{Id, TCell};
+
+/// Scrolling log, keeps as many entries as its rect has rows and drops the oldest
+#[derive(Debug)]
+pub struct LogView {
+    id: Id,
+    canvas: Canvas,
+    entries: VecDeque<(String, Style)>,
+}
+
+impl LogView {
+    pub fn new(id: Id, rect: Rect) -> Self {
+        Self {
+            id,
+            canvas: Canvas::new(id, rect),
+            entries: VecDeque::with_capacity(rect.height as usize),
+        }
+    }
+
+    pub fn z_index(mut self, z_index: u8) -> Self {
+        self.canvas.set_z_index(z_index);
+        self.redraw();
+        self
+    }
+
+    pub fn clear(&mut self) {
+        self.entries.clear();
+        self.canvas.clear();
+    }
+
+    pub fn push(&mut self, message: impl Into<String>, style: Style) {
+        self.entries.push_back((message.into(), style));
+        self.trim();
+        self.redraw();
+    }
+
+    pub fn entries(&self) -> impl Iterator<Item = &(String, Style)> {
+        self.entries.iter()
+    }
+
+    fn trim(&mut self) {
+        let max_entries = self.canvas.rect().height as usize;
+        while self.entries.len() > max_entries {
+            self.entries.pop_front();
+        }
+    }
+
+    fn redraw(&mut self) {
+        self.canvas.clear();
+        for (y, (message, style)) in self.entries.iter().enumerate() {
+            self.canvas.print(0, y as u16, message, *style);
+        }
+    }
+
+    fn get_t_cells(&self) -> Box<dyn Iterator<Item = &TCell> + '_> {
+        self.canvas.t_cells()
+    }
+}
+
+impl Widget for LogView {
+    fn rect(&self) -> Rect {
+        self.canvas.rect()
+    }
+
+    fn set_rect(&mut self, rect: Rect) {
+        self.canvas.set_rect(rect);
+        self.trim();
+        self.redraw();
+    }
+}
+
+crate::define_object! {
+    struct LogView,
+    id_field: id,
+    t_cells: custom(get_t_cells),
+    capabilities: {
+        Snapshot {}
+        Destructible {}
+    }
+}
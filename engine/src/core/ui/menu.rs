@@ -0,0 +1,134 @@
+use super::{Canvas, Rect, Style, Widget};
+use crate::prelude::{Id, TCell};
+
+/// A list of items with one selected, scrolls to keep the selection visible
+#[derive(Debug)]
+pub struct Menu {
+    id: Id,
+    canvas: Canvas,
+    items: Vec<String>,
+    selected: usize,
+    // First visible item
+    scroll: usize,
+    style: Style,
+    selected_style: Style,
+}
+
+impl Menu {
+    pub fn new(id: Id, rect: Rect, items: Vec<String>) -> Self {
+        let mut menu = Self {
+            id,
+            canvas: Canvas::new(id, rect),
+            items,
+            selected: 0,
+            scroll: 0,
+            style: Style::default(),
+            selected_style: Style::default().inverted(),
+        };
+        menu.redraw();
+        menu
+    }
+
+    pub fn styles(mut self, style: Style, selected_style: Style) -> Self {
+        self.style = style;
+        self.selected_style = selected_style;
+        self.redraw();
+        self
+    }
+
+    pub fn items(&self) -> &[String] {
+        &self.items
+    }
+
+    /// Keeps the selection on the same index if it still exists
+    pub fn set_items(&mut self, items: Vec<String>) {
+        if self.items != items {
+            self.items = items;
+            self.selected = self.selected.min(self.items.len().saturating_sub(1));
+            self.redraw();
+        }
+    }
+
+    pub fn selected(&self) -> Option<usize> {
+        (!self.items.is_empty()).then_some(self.selected)
+    }
+
+    pub fn selected_item(&self) -> Option<&str> {
+        self.items.get(self.selected).map(String::as_str)
+    }
+
+    pub fn select(&mut self, index: usize) {
+        let index = index.min(self.items.len().saturating_sub(1));
+        if self.selected != index {
+            self.selected = index;
+            self.redraw();
+        }
+    }
+
+    /// Wraps around to the first item
+    pub fn select_next(&mut self) {
+        if !self.items.is_empty() {
+            self.select((self.selected + 1) % self.items.len());
+        }
+    }
+
+    /// Wraps around to the last item
+    pub fn select_prev(&mut self) {
+        if !self.items.is_empty() {
+            let len = self.items.len();
+            self.select((self.selected + len - 1) % len);
+        }
+    }
+
+    fn redraw(&mut self) {
+        self.canvas.clear();
+        let rect = self.canvas.rect();
+        let rows = rect.height as usize;
+        if rows == 0 {
+            return;
+        }
+
+        if self.selected < self.scroll {
+            self.scroll = self.selected;
+        } else if self.selected >= self.scroll + rows {
+            self.scroll = self.selected + 1 - rows;
+        }
+
+        let visible = self.items.iter().enumerate().skip(self.scroll).take(rows);
+        for (y, (index, item)) in visible.enumerate() {
+            let (marker, style) = match index == self.selected {
+                true => ("> ", self.selected_style),
+                false => ("  ", self.style),
+            };
+            // The highlight covers the whole row
+            let row: String = format!("{}{}", marker, item);
+            let row = format!("{:<width$}", row, width = rect.width as usize);
+            self.canvas.print(0, y as u16, &row, style);
+        }
+    }
+
+    fn get_t_cells(&self) -> Box<dyn Iterator<Item = &TCell> + '_> {
+        self.canvas.t_cells()
+    }
+}
+
+impl Widget for Menu {
+    fn rect(&self) -> Rect {
+        self.canvas.rect()
+    }
+
+    fn set_rect(&mut self, rect: Rect) {
+        self.canvas.set_rect(rect);
+        self.redraw();
+    }
+}
+
+crate::define_object! {
+    struct Menu,
+    id_field: id,
+    t_cells: custom(get_t_cells),
+    capabilities: {
+        Snapshot {}
+        Destructible {}
+    }
+}
@@ -0,0 +1,102 @@
+use super::{Canvas, Rect, Style, Widget};
+use crate::prelude::{Id, TCell};
+
+/// A horizontal bar over the width of its rect, optionally followed by the percentage
+#[derive(Debug)]
+pub struct ProgressBar {
+    id: Id,
+    canvas: Canvas,
+    progress: f32,
+    filled_style: Style,
+    empty_style: Style,
+    show_percent: bool,
+}
+
+impl ProgressBar {
+    pub fn new(id: Id, rect: Rect) -> Self {
+        let mut bar = Self {
+            id,
+            canvas: Canvas::new(id, rect),
+            progress: 0.0,
+            filled_style: Style::default(),
+            empty_style: Style::default(),
+            show_percent: false,
+        };
+        bar.redraw();
+        bar
+    }
+
+    pub fn styles(mut self, filled_style: Style, empty_style: Style) -> Self {
+        self.filled_style = filled_style;
+        self.empty_style = empty_style;
+        self.redraw();
+        self
+    }
+
+    /// Keeps the last 5 columns for the percentage
+    pub fn show_percent(mut self, show_percent: bool) -> Self {
+        self.show_percent = show_percent;
+        self.redraw();
+        self
+    }
+
+    pub fn progress(&self) -> f32 {
+        self.progress
+    }
+
+    /// Clamped to `0.0..=1.0`
+    pub fn set_progress(&mut self, progress: f32) {
+        let progress = progress.clamp(0.0, 1.0);
+        if self.progress != progress {
+            self.progress = progress;
+            self.redraw();
+        }
+    }
+
+    fn redraw(&mut self) {
+        self.canvas.clear();
+        let width = self.canvas.rect().width;
+        let bar_width = match self.show_percent {
+            true => width.saturating_sub(5),
+            false => width,
+        };
+
+        let filled = (self.progress * bar_width as f32).round() as u16;
+        for x in 0..bar_width {
+            match x < filled {
+                true => self.canvas.put(x, 0, self.filled_style.glyph('█')),
+                false => self.canvas.put(x, 0, self.empty_style.glyph('░')),
+            }
+        }
+
+        if self.show_percent {
+            let percent = format!(" {:>3.0}%", self.progress * 100.0);
+            self.canvas.print(bar_width, 0, &percent, self.filled_style);
+        }
+    }
+
+    fn get_t_cells(&self) -> Box<dyn Iterator<Item = &TCell> + '_> {
+        self.canvas.t_cells()
+    }
+}
+
+impl Widget for ProgressBar {
+    fn rect(&self) -> Rect {
+        self.canvas.rect()
+    }
+
+    fn set_rect(&mut self, rect: Rect) {
+        self.canvas.set_rect(rect);
+        self.redraw();
+    }
+}
+
+crate::define_object! {
+    struct ProgressBar,
+    id_field: id,
+    t_cells: custom(get_t_cells),
+    capabilities: {
+        Snapshot {}
+        Destructible {}
+    }
+}
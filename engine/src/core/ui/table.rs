@@ -0,0 +1,106 @@
+use super::{Canvas, Rect, Style, Widget};
+use crate::prelude::{Id, TCell};
+
+// Columns between the longest key and the values
+const COLUMN_GAP: u16 = 2;
+
+/// Key/value rows, the values line up after the longest key
+#[derive(Debug)]
+pub struct Table {
+    id: Id,
+    canvas: Canvas,
+    rows: Vec<(String, String)>,
+    key_style: Style,
+    value_style: Style,
+}
+
+impl Table {
+    pub fn new(id: Id, rect: Rect) -> Self {
+        Self {
+            id,
+            canvas: Canvas::new(id, rect),
+            rows: Vec::new(),
+            key_style: Style::default(),
+            value_style: Style::default(),
+        }
+    }
+
+    pub fn styles(mut self, key_style: Style, value_style: Style) -> Self {
+        self.key_style = key_style;
+        self.value_style = value_style;
+        self.redraw();
+        self
+    }
+
+    pub fn clear(&mut self) {
+        self.rows.clear();
+        self.canvas.clear();
+    }
+
+    /// Replaces every row, nothing is redrawn if the rows didn't change
+    pub fn set_rows(&mut self, rows: Vec<(String, String)>) {
+        if self.rows != rows {
+            self.rows = rows;
+            self.redraw();
+        }
+    }
+
+    /// Updates the value of a key or appends a new row
+    pub fn set(&mut self, key: &str, value: impl Into<String>) {
+        let value = value.into();
+        match self.rows.iter_mut().find(|(row_key, _)| row_key == key) {
+            Some((_, row_value)) if *row_value == value => return,
+            Some((_, row_value)) => *row_value = value,
+            None => self.rows.push((key.to_string(), value)),
+        }
+        self.redraw();
+    }
+
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.rows
+            .iter()
+            .find(|(row_key, _)| row_key == key)
+            .map(|(_, value)| value.as_str())
+    }
+
+    fn redraw(&mut self) {
+        self.canvas.clear();
+        let key_width = self
+            .rows
+            .iter()
+            .map(|(key, _)| key.chars().count() as u16)
+            .max()
+            .unwrap_or(0);
+
+        for (y, (key, value)) in self.rows.iter().enumerate() {
+            self.canvas.print(0, y as u16, key, self.key_style);
+            self.canvas
+                .print(key_width + COLUMN_GAP, y as u16, value, self.value_style);
+        }
+    }
+
+    fn get_t_cells(&self) -> Box<dyn Iterator<Item = &TCell> + '_> {
+        self.canvas.t_cells()
+    }
+}
+
+impl Widget for Table {
+    fn rect(&self) -> Rect {
+        self.canvas.rect()
+    }
+
+    fn set_rect(&mut self, rect: Rect) {
+        self.canvas.set_rect(rect);
+        self.redraw();
+    }
+}
+
+crate::define_object! {
+    struct Table,
+    id_field: id,
+    t_cells: custom(get_t_cells),
+    capabilities: {
+        Snapshot {}
+        Destructible {}
+    }
+}
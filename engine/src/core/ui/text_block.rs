@@ -0,0 +1,127 @@
+use super::{Canvas, Rect, Style, Widget};
+use crate::prelude::{Id, TCell};
+
+/// Lines of text which wrap at the width of the rect, cut off at its height
+#[derive(Debug)]
+pub struct TextBlock {
+    id: Id,
+    canvas: Canvas,
+    lines: Vec<(String, Style)>,
+}
+
+impl TextBlock {
+    pub fn new(id: Id, rect: Rect) -> Self {
+        Self {
+            id,
+            canvas: Canvas::new(id, rect),
+            lines: Vec::new(),
+        }
+    }
+
+    pub fn z_index(mut self, z_index: u8) -> Self {
+        self.canvas.set_z_index(z_index);
+        self.redraw();
+        self
+    }
+
+    pub fn clear(&mut self) {
+        self.lines.clear();
+        self.canvas.clear();
+    }
+
+    pub fn push_line(&mut self, text: impl Into<String>, style: Style) {
+        self.lines.push((text.into(), style));
+        self.redraw();
+    }
+
+    /// Replaces every line, nothing is redrawn if the lines didn't change
+    pub fn set_lines(&mut self, lines: Vec<(String, Style)>) {
+        if self.lines != lines {
+            self.lines = lines;
+            self.redraw();
+        }
+    }
+
+    pub fn lines(&self) -> &[(String, Style)] {
+        &self.lines
+    }
+
+    fn redraw(&mut self) {
+        self.canvas.clear();
+        let width = self.canvas.rect().width as usize;
+
+        let mut y = 0;
+        for (text, style) in &self.lines {
+            for row in wrap(text, width) {
+                self.canvas.print(0, y, &row, *style);
+                y += 1;
+            }
+        }
+    }
+
+    fn get_t_cells(&self) -> Box<dyn Iterator<Item = &TCell> + '_> {
+        self.canvas.t_cells()
+    }
+}
+
+/// Breaks a line at whitespace, words longer than the width are split.
+/// An empty line still takes up a row.
+pub fn wrap(text: &str, width: usize) -> Vec<String> {
+    if width == 0 {
+        return Vec::new();
+    }
+
+    let mut rows = Vec::new();
+    let mut row = String::new();
+    let mut row_len = 0;
+
+    for word in text.split(' ') {
+        let mut word: Vec<char> = word.chars().collect();
+
+        if row_len > 0 && row_len + 1 + word.len() > width {
+            rows.push(std::mem::take(&mut row));
+            row_len = 0;
+        }
+
+        while word.len() > width {
+            let rest = word.split_off(width);
+            if row_len > 0 {
+                rows.push(std::mem::take(&mut row));
+            }
+            rows.push(word.into_iter().collect());
+            row_len = 0;
+            word = rest;
+        }
+
+        if row_len > 0 {
+            row.push(' ');
+            row_len += 1;
+        }
+        row.extend(word.iter());
+        row_len += word.len();
+    }
+
+    rows.push(row);
+    rows
+}
+
+impl Widget for TextBlock {
+    fn rect(&self) -> Rect {
+        self.canvas.rect()
+    }
+
+    fn set_rect(&mut self, rect: Rect) {
+        self.canvas.set_rect(rect);
+        self.redraw();
+    }
+}
+
+crate::define_object! {
+    struct TextBlock,
+    id_field: id,
+    t_cells: custom(get_t_cells),
+    capabilities: {
+        Snapshot {}
+        Destructible {}
+    }
+}
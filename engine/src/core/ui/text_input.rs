@@ -0,0 +1,167 @@
+use super::{Canvas, Rect, Style, Widget};
+use crate::prelude::{Id, TCell};
+
+/// A single editable line behind a prompt. The cursor is drawn inverted and
+/// the line scrolls horizontally to keep it inside the rect.
+#[derive(Debug)]
+pub struct TextInput {
+    id: Id,
+    canvas: Canvas,
+    prompt: String,
+    text: Vec<char>,
+    // Index into `text`, the cursor sits before this character
+    cursor: usize,
+    style: Style,
+}
+
+impl TextInput {
+    pub fn new(id: Id, rect: Rect) -> Self {
+        let mut input = Self {
+            id,
+            canvas: Canvas::new(id, rect),
+            prompt: String::new(),
+            text: Vec::new(),
+            cursor: 0,
+            style: Style::default(),
+        };
+        input.redraw();
+        input
+    }
+
+    pub fn prompt(mut self, prompt: impl Into<String>) -> Self {
+        self.prompt = prompt.into();
+        self.redraw();
+        self
+    }
+
+    pub fn style(mut self, style: Style) -> Self {
+        self.style = style;
+        self.redraw();
+        self
+    }
+
+    pub fn z_index(mut self, z_index: u8) -> Self {
+        self.canvas.set_z_index(z_index);
+        self.redraw();
+        self
+    }
+
+    pub fn text(&self) -> String {
+        self.text.iter().collect()
+    }
+
+    /// Replaces the line, the cursor moves to its end
+    pub fn set_text(&mut self, text: &str) {
+        self.text = text.chars().collect();
+        self.cursor = self.text.len();
+        self.redraw();
+    }
+
+    /// Empties the line and returns what was on it
+    pub fn take(&mut self) -> String {
+        let text = self.text();
+        self.text.clear();
+        self.cursor = 0;
+        self.redraw();
+        text
+    }
+
+    /// Characters before the cursor
+    pub fn cursor(&self) -> usize {
+        self.cursor
+    }
+
+    pub fn insert(&mut self, symbol: char) {
+        self.text.insert(self.cursor, symbol);
+        self.cursor += 1;
+        self.redraw();
+    }
+
+    /// Removes the character before the cursor
+    pub fn backspace(&mut self) {
+        if self.cursor > 0 {
+            self.cursor -= 1;
+            self.text.remove(self.cursor);
+            self.redraw();
+        }
+    }
+
+    /// Removes the character under the cursor
+    pub fn delete(&mut self) {
+        if self.cursor < self.text.len() {
+            self.text.remove(self.cursor);
+            self.redraw();
+        }
+    }
+
+    pub fn move_left(&mut self) {
+        self.set_cursor(self.cursor.saturating_sub(1));
+    }
+
+    pub fn move_right(&mut self) {
+        self.set_cursor(self.cursor + 1);
+    }
+
+    pub fn move_home(&mut self) {
+        self.set_cursor(0);
+    }
+
+    pub fn move_end(&mut self) {
+        self.set_cursor(self.text.len());
+    }
+
+    fn set_cursor(&mut self, cursor: usize) {
+        let cursor = cursor.min(self.text.len());
+        if self.cursor != cursor {
+            self.cursor = cursor;
+            self.redraw();
+        }
+    }
+
+    fn redraw(&mut self) {
+        self.canvas.clear();
+        let prompt_width = self.canvas.print(0, 0, &self.prompt, self.style);
+
+        // The cursor needs a column of its own past the end of the line
+        let width = self.canvas.rect().width.saturating_sub(prompt_width) as usize;
+        if width == 0 {
+            return;
+        }
+        let scroll = (self.cursor + 1).saturating_sub(width);
+
+        for (column, index) in (scroll..self.text.len().max(self.cursor + 1)).enumerate() {
+            let symbol = self.text.get(index).copied().unwrap_or(' ');
+            let style = match index == self.cursor {
+                true => self.style.inverted(),
+                false => self.style,
+            };
+            self.canvas
+                .put(prompt_width + column as u16, 0, style.glyph(symbol));
+        }
+    }
+
+    fn get_t_cells(&self) -> Box<dyn Iterator<Item = &TCell> + '_> {
+        self.canvas.t_cells()
+    }
+}
+
+impl Widget for TextInput {
+    fn rect(&self) -> Rect {
+        self.canvas.rect()
+    }
+
+    fn set_rect(&mut self, rect: Rect) {
+        self.canvas.set_rect(rect);
+        self.redraw();
+    }
+}
+
+crate::define_object! {
+    struct TextInput,
+    id_field: id,
+    t_cells: custom(get_t_cells),
+    capabilities: {
+        Snapshot {}
+        Destructible {}
+    }
+}
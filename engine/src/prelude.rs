@@ -1,26 +1,59 @@
 // Core Primitives & Foundational Types
-pub use crate::core::global::{Id, IdCounter, Position};
+pub use crate::core::global::{Id, IdCounter, Position, SlotMap};
 
 // Runtime, Stage & Object Model
 pub use crate::core::RuntimeManager;
 pub use crate::core::runtime::{
-    RuntimeCommand,
+    RuntimeCommand, StageEntry,
+    renderer::transition::{Screen, Transition, TransitionEffect},
     stage::{
         Logic, Stage,
+        loader::{LoadStatus, SceneLoader},
         scene::{
             Conflict, ObjectIndex, Scene,
+            commands::{Command, CommandConflict, Commands},
             grid::{CellRef, SpatialGrid, Terrain},
+            handle::Handle,
+            particles::{EmitMode, Emitter, ParticleStyle, ParticleSystem},
+            snapshot::Snapshots,
+            store::ObjectStore,
+            timers::{Delay, Scheduler, TimerAction, TimerHandle},
             object::{
-                Destructible, Movable, Object, ObjectExt, Occupant, Spatial, Stateful,
+                Animated, Destructible, MaybeSync, Movable, Object, ObjectExt, Occupant, Snapshot,
+                Spatial, Stateful,
+                animation::{
+                    Animation, AnimationFinished, AnimationTarget, Animator, GlyphPatch, PlayMode,
+                },
+                sprite::{SpriteBody, SpriteObject},
                 state::{State, StateChange},
                 t_cell::{Glyph, TCell},
+                tween::{DOT_SCALE, Easing, GlyphTween, Tween, Tweenable},
             },
         },
     },
 };
 
+// Assets
+pub use crate::core::asset::{Frame, FrameSequence, RenderMode, RgbaImage, Sprite};
+
+// Shared Resources
+pub use crate::core::resources::{Persistent, Resources};
+
+// UI Widgets
+pub use crate::core::ui::{
+    Align, Anchor, Axis, Border, Canvas, Label, Layout, LogView, Menu, Node, OVERLAY_Z_INDEX,
+    ProgressBar, Rect, Size, Style, Table, TextBlock, TextInput, UI_Z_INDEX, Widget,
+};
+
+// Debug Console
+pub use crate::core::console::{
+    Args, CommandResult, Console, ConsoleCommand, ConsoleInput, Handler, Invocation, Reply,
+};
+pub use crate::core::inspector::{Highlight, Inspector};
+pub use crate::core::profiler::{Phase, Profiler, ProfilerOverlay, Span, Stats};
+
 // Event System
-pub use crate::core::event::{Event, EventHandler, EventManager};
+pub use crate::core::event::{Event, EventHandler, EventKey, EventManager, Propagation};
 
 // Macros
 pub use crate::define_object;
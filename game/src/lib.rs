@@ -1,7 +1,12 @@
-use engine::prelude::{RuntimeManager, Stage};
-use snake_game::SnakeLogic;
+use engine::prelude::{RuntimeManager, Stage, Transition};
+use snake_game::{
+    GameOverLogic, Level, LoadingLogic, PauseLogic, Scores, Settings, SettingsLogic, SnakeLogic,
+    TitleLogic,
+};
 use std::{
     fmt::{self, Display, Formatter},
+    io,
+    path::Path,
     time::Duration,
 };
 
@@ -9,31 +14,94 @@ mod snake_game;
 
 #[derive(PartialEq, Eq, Hash, Clone, Copy, Debug)]
 pub enum StageKey {
+    Title,
     Level0,
     Level1,
+    Pause,
+    Settings,
+    GameOver,
+    Loading,
 }
 
 impl Display for StageKey {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
         match self {
+            StageKey::Title => write!(f, "Title"),
             StageKey::Level0 => write!(f, "Level 0"),
             StageKey::Level1 => write!(f, "Level 1"),
+            StageKey::Pause => write!(f, "Pause"),
+            StageKey::Settings => write!(f, "Settings"),
+            StageKey::GameOver => write!(f, "Game Over"),
+            StageKey::Loading => write!(f, "Loading"),
         }
     }
 }
 
-pub fn init() {
+// Levels on disk take precedence over the embedded ones, so they can be edited without a rebuild
+fn load_level(path: &str, embedded: &str) -> Level {
+    let level = if Path::new(path).exists() {
+        Level::load(path)
+    } else {
+        Level::parse(embedded)
+    };
+
+    match level {
+        Ok(level) => level,
+        Err(err) => panic!("Failed to load {}: {}", path, err),
+    }
+}
+
+pub fn init() -> io::Result<()> {
     let mut manager: RuntimeManager<StageKey> = RuntimeManager::new(Duration::from_millis(0));
 
-    let snake_logic = Box::new(SnakeLogic::new(StageKey::Level0));
-    let snake_stage: Stage<StageKey> = Stage::new(snake_logic);
+    // Shared by both levels and kept between sessions
+    let resources = manager.resources_mut();
+    resources.set_save_dir("saves");
+    resources.insert_persistent(Scores::default());
+    resources.insert_persistent(Settings::default());
+
+    let level = load_level("levels/level0.txt", include_str!("../levels/level0.txt"));
+    let mut levels = vec![(StageKey::Level0, level.name.clone())];
+    let tick_rate = Duration::from_millis(level.tick_rate);
+    let snake_logic = Box::new(SnakeLogic::new(StageKey::Level0, level));
+    let snake_stage: Stage<StageKey> = Stage::new(snake_logic)
+        .with_tick_rate(tick_rate)
+        .with_keep_rendering(true)
+        .with_transition(Transition::wipe(Duration::from_millis(300)));
     manager.add_stage(StageKey::Level0, snake_stage);
 
-    let snake_logic = Box::new(SnakeLogic::new(StageKey::Level1));
-    let snake_stage: Stage<StageKey> = Stage::new(snake_logic);
+    let level = load_level("levels/level1.txt", include_str!("../levels/level1.txt"));
+    levels.push((StageKey::Level1, level.name.clone()));
+    let tick_rate = Duration::from_millis(level.tick_rate);
+    let snake_logic = Box::new(SnakeLogic::new(StageKey::Level1, level));
+    let snake_stage: Stage<StageKey> = Stage::new(snake_logic)
+        .with_tick_rate(tick_rate)
+        .with_keep_rendering(true)
+        .with_transition(Transition::wipe(Duration::from_millis(300)));
     manager.add_stage(StageKey::Level1, snake_stage);
 
-    manager.set_active_stage(StageKey::Level0);
+    let title_stage: Stage<StageKey> = Stage::new(Box::new(TitleLogic::new(levels)))
+        .with_transition(Transition::fade(Duration::from_millis(300)));
+    manager.add_stage(StageKey::Title, title_stage);
+
+    // Pushed over the levels, which stay rendered underneath
+    let pause_stage: Stage<StageKey> = Stage::new(Box::new(PauseLogic::new()))
+        .with_transition(Transition::dissolve(Duration::from_millis(150)));
+    manager.add_stage(StageKey::Pause, pause_stage);
+
+    let game_over_stage: Stage<StageKey> = Stage::new(Box::new(GameOverLogic::new()))
+        .with_transition(Transition::dissolve(Duration::from_millis(300)));
+    manager.add_stage(StageKey::GameOver, game_over_stage);
+
+    // Pushed from the title or the pause menu
+    let settings_stage: Stage<StageKey> = Stage::new(Box::new(SettingsLogic::new()));
+    manager.add_stage(StageKey::Settings, settings_stage);
+
+    // Shown while a level is reloaded in the background
+    let loading_stage: Stage<StageKey> = Stage::new(Box::new(LoadingLogic::new()));
+    manager.add_stage(StageKey::Loading, loading_stage);
+
+    manager.set_active_stage(StageKey::Title);
 
-    manager.run_app();
+    manager.run_app()
 }
@@ -1,3 +1,3 @@
-fn main() {
-    app::init();
+fn main() -> std::io::Result<()> {
+    app::init()
 }
@@ -1,41 +1,47 @@
 use crossterm::event::{self, Event, KeyCode};
 use crossterm::style::Color;
 use engine::prelude::*;
-use rand::Rng;
+use std::collections::HashMap;
 use std::time::{Duration, Instant};
 
+mod brain;
+mod commands;
 mod death_logic;
 mod events;
 mod game_objects;
+mod game_over_logic;
+mod level;
+mod loading_logic;
+mod menu;
+mod pause_logic;
+mod scores;
+mod settings;
+mod settings_logic;
 mod player;
-mod ui;
+mod title_logic;
 
 use crate::StageKey;
+use brain::Brain;
 use death_logic::DeathLogic;
-use events::{CollisionHandler, DeathHandler, FoodHandler};
+use events::{BombHandler, CollisionHandler, DeathHandler, FoodEvent, FoodHandler};
 use game_objects::{
     snake::Direction,
-    {Food, Snake},
+    {Bomb, Food, Snake},
 };
+pub use game_over_logic::GameOverLogic;
+pub use level::Level;
+pub use loading_logic::LoadingLogic;
+pub use pause_logic::PauseLogic;
+pub use scores::Scores;
+pub use settings::Settings;
+pub use settings_logic::SettingsLogic;
+pub use title_logic::TitleLogic;
+use level::WinCondition;
+use menu::{MenuRequest, RunSummary};
 use player::Player;
-use ui::{InfoPanel, Logger, Statistics};
-
-// Game
-const GAME_SPEED: u64 = 20;
 
 // Grid
 const GRID_POS: Position = Position { x: 4, y: 3 };
-const GRID_WIDTH: u16 = 180;
-const GRID_HEIGHT: u16 = 60;
-const BORDER_STYLE: Glyph = Glyph {
-    fg_clr: Some(Color::Rgb {
-        r: 200,
-        g: 200,
-        b: 200,
-    }),
-    bg_clr: None,
-    symbol: '█',
-};
 
 // Statistics
 const STATS_COLOR: Color = Color::Rgb {
@@ -52,211 +58,270 @@ const LOGGER_COLOR: Color = Color::Rgb {
 };
 const MAX_LOGS: usize = 10;
 
+// Side panel
+const INFO_WIDTH: u16 = 32;
+const PANEL_GAP: u16 = 3;
+
+/// Rects of the side panel's layout, the grid's rect is set by the logic
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum Panel {
+    Grid,
+    Info,
+    Stats,
+    Logger,
+}
+
 pub struct SnakeLogic {
     stage_id: StageKey,
+    level: Level,
     switch_stage: Option<StageKey>,
     switch_logic: bool,
     event_manager: EventManager,
     player: Player,
     speed: u64,
     counter: u64,
-    quit: bool,
-    stats_id: Option<Id>,
-    logger_id: Option<Id>,
-    info_id: Option<Id>,
+    respawn_timer: Option<TimerHandle>,
+    // Set once a reloaded scene was swapped in
+    needs_setup: bool,
+    // Set when quitting to the title, the next run starts over
+    restart_on_enter: bool,
+    settings: Settings,
+    player_length: usize,
+    stats: Option<Handle<Table>>,
+    logger: Option<Handle<LogView>>,
+    info: Option<Handle<TextBlock>>,
+    layout: Layout<Panel>,
+    console: Console<StageKey, SnakeLogic>,
+    // Snakes without a brain here turn randomly
+    brains: HashMap<Id, Brain>,
     last_tick: Instant,
     is_debugging: bool,
-    is_paused: bool,
+    // Re-announces the tick rate after the logic was (re)entered
+    sync_tick_rate: bool,
     grid_pos: Position,
     grid_width: u16,
     grid_height: u16,
 }
 
 impl SnakeLogic {
-    pub fn new(key: StageKey) -> Self {
+    pub fn new(key: StageKey, level: Level) -> Self {
         let mut event_manager = EventManager::new();
         event_manager.register(CollisionHandler);
         event_manager.register(FoodHandler);
+        event_manager.register(BombHandler);
         event_manager.register(DeathHandler);
 
+        let mut console = Console::new();
+        commands::register(&mut console);
+
         Self {
             stage_id: key,
             switch_stage: None,
             switch_logic: false,
             event_manager,
             player: Player::new(),
-            speed: GAME_SPEED,
+            speed: level.tick_rate,
             counter: 0,
-            quit: false,
-            stats_id: None,
-            logger_id: None,
-            info_id: None,
+            respawn_timer: None,
+            needs_setup: false,
+            restart_on_enter: false,
+            settings: Settings::default(),
+            player_length: 0,
+            stats: None,
+            logger: None,
+            info: None,
+            layout: build_layout(),
+            console,
+            brains: HashMap::new(),
             last_tick: Instant::now(),
             is_debugging: true,
-            is_paused: false,
+            sync_tick_rate: true,
             grid_pos: GRID_POS,
-            grid_height: GRID_HEIGHT,
-            grid_width: GRID_WIDTH,
+            grid_height: level.height,
+            grid_width: level.width,
+            level,
         }
     }
 
     fn setup_scene(&mut self, scene: &mut Scene) {
-        self.setup_grid(scene);
+        let status = LoadStatus::default();
+        let bounds = (self.grid_width, self.grid_height, self.grid_pos);
+        prepare_scene(&self.level, bounds, scene, &status);
+        self.setup_actors(scene);
+    }
+
+    fn setup_grid(&self, scene: &mut Scene) {
+        let bounds = (self.grid_width, self.grid_height, self.grid_pos);
+        scene.attach_grid(build_grid(&self.level, bounds));
+    }
+
+    /// Attaches everything the logic keeps handles to, on top of a prepared scene
+    fn setup_actors(&mut self, scene: &mut Scene) {
+        self.settings = *scene.resources.get_or_insert_with(Settings::default);
+        scene.set_parallel(self.settings.parallel);
+        self.brains.clear();
         self.setup_ui(scene);
+        self.console.reattach(scene);
         self.setup_player_snake(scene);
+        self.setup_respawns(scene);
     }
 
-    fn setup_grid(&self, scene: &mut Scene) {
-        let grid = SpatialGrid::new(
-            self.grid_width,
-            self.grid_height,
-            Some(BORDER_STYLE),
-            self.grid_pos,
-            |_| {
-                let style = Glyph::new(Some(Color::Black), Some(Color::Black), ' ');
-                Terrain::new(style, 0)
-            },
-        );
-        scene.attach_grid(grid);
+    /// Picks up what was changed in the settings menu
+    fn apply_settings(&mut self, scene: &mut Scene) {
+        self.settings = *scene.resources.get_or_insert_with(Settings::default);
+        scene.set_parallel(self.settings.parallel);
+
+        let speed = self.settings.tick_rate(self.level.tick_rate);
+        if self.speed != speed {
+            self.speed = speed;
+            self.sync_tick_rate = true;
+        }
+
+        if let Some(snake) = self
+            .player
+            .snake
+            .and_then(|handle| handle.get_mut(&mut scene.objects))
+        {
+            let (head_color, body_color) = self.settings.snake_color.colors();
+            snake.head_style.fg_clr = Some(head_color);
+            snake.body_style.fg_clr = Some(body_color);
+        }
+
+        self.update_info(scene);
+    }
+
+    /// Acts on what the pause or game over menu chose
+    fn handle_menu_request(&mut self, scene: &mut Scene) -> Option<RuntimeCommand<StageKey>> {
+        match scene.resources.remove::<MenuRequest>()? {
+            MenuRequest::Restart => Some(self.reload()),
+            MenuRequest::QuitToTitle => {
+                self.restart_on_enter = true;
+                Some(RuntimeCommand::SwitchStage(StageKey::Title))
+            }
+        }
+    }
+
+    fn game_over(&mut self, scene: &mut Scene) -> RuntimeCommand<StageKey> {
+        scene.resources.get_or_insert_with(Scores::default).deaths += 1;
+        scene.resources.insert(RunSummary {
+            stage: self.stage_id,
+            score: self.player.score,
+            length: self.player_length,
+        });
+        RuntimeCommand::PushStage(StageKey::GameOver)
+    }
+
+    /// Prepares a fresh scene on the loading stage, the actors are added once it's swapped in
+    fn reload(&self) -> RuntimeCommand<StageKey> {
+        let level = self.level.clone();
+        let bounds = (self.grid_width, self.grid_height, self.grid_pos);
+        let loader = SceneLoader::spawn(move |scene, status| {
+            prepare_scene(&level, bounds, scene, status);
+        });
+
+        RuntimeCommand::LoadScene {
+            loader,
+            loading_stage: StageKey::Loading,
+        }
     }
 
     fn setup_ui(&mut self, scene: &mut Scene) {
-        self.stats_id = scene.attach_object(
-            |id| Box::new(Statistics::new(id, Position::empty())),
+        self.stats = scene.attach_typed(
+            |id| {
+                Table::new(id, Rect::default())
+                    .styles(Style::fg(STATS_COLOR), Style::fg(STATS_COLOR))
+            },
             Conflict::Ignore,
         );
 
-        self.logger_id = scene.attach_object(
-            |id| Box::new(Logger::new(id, Position::empty(), MAX_LOGS)),
-            Conflict::Ignore,
-        );
+        self.logger = scene.attach_typed(|id| LogView::new(id, Rect::default()), Conflict::Ignore);
 
-        self.info_id = scene.attach_object(
-            |id| Box::new(InfoPanel::new(id, Position::empty())),
-            Conflict::Ignore,
-        );
+        self.info = scene.attach_typed(|id| TextBlock::new(id, Rect::default()), Conflict::Ignore);
+
+        if let Some(handle) = self.info {
+            self.layout.bind(Panel::Info, handle);
+        }
+        if let Some(handle) = self.stats {
+            self.layout.bind(Panel::Stats, handle);
+        }
+        if let Some(handle) = self.logger {
+            self.layout.bind(Panel::Logger, handle);
+        }
 
-        self.update_ui_pos(scene);
+        self.update_layout(scene);
         self.update_info(scene);
     }
 
     fn setup_player_snake(&mut self, scene: &mut Scene) {
-        let snake_id = scene.attach_object(
+        let snake = scene.attach_typed(
             |id| {
+                let start = self.level.player_start();
                 let snake_pos = Position {
-                    x: self.grid_pos.x.saturating_add(50),
-                    y: self.grid_pos.y.saturating_add(10)
+                    x: self.grid_pos.x.saturating_add(start.x),
+                    y: self.grid_pos.y.saturating_add(start.y),
                 };
 
                 let mut snake = Snake::new(snake_pos, id, 3);
-                snake.head_style = Glyph::new(
-                    Some(Color::Rgb {
-                        r: 255,
-                        g: 0,
-                        b: 255,
-                    }),
-                    Some(Color::Black),
-                    '█',
-                );
-                snake.body_style = Glyph::new(
-                    Some(Color::Rgb {
-                        r: 138,
-                        g: 43,
-                        b: 226,
-                    }),
-                    Some(Color::Black),
-                    '█',
-                );
+                let (head_color, body_color) = self.settings.snake_color.colors();
+                snake.head_style = Glyph::new(Some(head_color), Some(Color::Black), '█');
+                snake.body_style = Glyph::new(Some(body_color), Some(Color::Black), '█');
                 snake.base_index = 20;
                 snake.ignore_death = true;
-                Box::new(snake)
+                snake
             },
             Conflict::Overwrite,
         );
 
-        if let Some(id) = snake_id {
-            self.player.set_snake(id);
-            scene.protected_ids.insert(id);
+        self.player.score = 0;
+        if let Some(handle) = snake {
+            self.player.set_snake(handle);
+            scene.protected_ids.insert(handle.id());
         }
     }
 
-    fn update_ui_pos(&mut self, scene: &mut Scene) {
-        if let Some(stats_id) = self.stats_id {
-            if let Some(object) = scene.objects.get_mut(&stats_id) {
-                if let Some(stats) = object.get_mut::<Statistics>() {
-                    let stats_pos = Position {
-                        x: (self.grid_width + self.grid_pos.x) + 3,
-                        y: self.grid_pos.y + 17,
-                    };
-                    stats.pos = stats_pos;
-                }
-            }
-        }
-
-        if let Some(logger_id) = self.logger_id {
-            if let Some(object) = scene.objects.get_mut(&logger_id) {
-                if let Some(logger) = object.get_mut::<Logger>() {
-                    let logger_pos = Position {
-                        x: (self.grid_width + self.grid_pos.x) + 3,
-                        y: self.grid_pos.y + 22,
-                    };
-                    logger.pos = logger_pos;
-                }
-            }
-        }
-
-        if let Some(info_id) = self.info_id {
-            if let Some(object) = scene.objects.get_mut(&info_id) {
-                if let Some(info) = object.get_mut::<InfoPanel>() {
-                    let info_pos = Position {
-                        x: (self.grid_width + self.grid_pos.x) + 3,
-                        y: self.grid_pos.y,
-                    };
-                    info.start_pos = info_pos;
-                }
-            }
-        }
+    fn update_layout(&mut self, scene: &mut Scene) {
+        let grid = Rect::new(
+            self.grid_pos.x,
+            self.grid_pos.y,
+            self.grid_width,
+            self.grid_height,
+        );
+        self.layout.fit_terminal();
+        self.layout.set_rect(Panel::Grid, grid);
+        self.layout.apply(scene);
     }
 
     fn update_info(&mut self, scene: &mut Scene) {
-        if let Some(id) = self.info_id {
-            if let Some(ui_object) = scene.objects.get_mut(&id) {
-                if let Some(panel) = ui_object.get_mut::<InfoPanel>() {
-                    panel.clear();
-                    let key_clr = Some(Color::Rgb {
-                        r: 255,
-                        g: 255,
-                        b: 255,
-                    });
-                    let title_clr = Some(Color::Rgb {
-                        r: 175,
-                        g: 200,
-                        b: 200,
-                    });
-
-                    panel.add_line(format!(":::[CONTROLS]:::"), title_clr, None);
-                    panel.add_line(format!("w,a,s,d:        Move Snake"), key_clr, None);
-                    panel.add_line(format!("q & e:          Resize Head"), key_clr, None);
-                    panel.add_line(format!("Space:          Toggle Move"), key_clr, None);
-                    panel.add_line(format!("p:              Pause Game"), key_clr, None);
-                    panel.add_line(format!("Esc:            Quit Game"), key_clr, None);
-                    panel.add_line(format!(""), None, None); // Spacer
-                    panel.add_line(format!(":::[DEBUG]:::"), title_clr, None);
-                    panel.add_line(format!("W,A,S,D:        Move camera"), key_clr, None);
-                    panel.add_line(format!("Q & E:          Resize camera"), key_clr, None);
-                    panel.add_line(format!("Up & Down:      Change Z-Index"), key_clr, None);
-                    panel.add_line(format!("Left & Right:   Switch Stage"), key_clr, None);
-                    panel.add_line(format!("g:              Switch Logic"), key_clr, None);
-                    panel.add_line(format!("r:              Reset Stage"), key_clr, None);
-                    panel.add_line(format!("f:              Spawn Food"), key_clr, None);
-                    panel.add_line(format!("Tab:            Spawn Snakes"), key_clr, None);
-                }
-            }
+        let controls = format!("{}:", self.settings.controls.name());
+        if let Some(panel) = self.info.and_then(|handle| handle.get_mut(&mut scene.objects)) {
+            let key = Style::fg(Color::Rgb {
+                r: 255,
+                g: 255,
+                b: 255,
+            });
+            let title = Style::fg(Color::Rgb {
+                r: 175,
+                g: 200,
+                b: 200,
+            });
+
+            panel.set_lines(vec![
+                (":::[CONTROLS]:::".to_string(), title),
+                (format!("{:<16}Move Snake", controls), key),
+                ("q & e:          Resize Head".to_string(), key),
+                ("Space:          Toggle Move".to_string(), key),
+                ("p & Esc:        Pause Menu".to_string(), key),
+                (String::new(), Style::default()), // Spacer
+                (":::[DEBUG]:::".to_string(), title),
+                ("`:              Console".to_string(), key),
+                ("help:           Commands".to_string(), key),
+                ("F9:             Profiler".to_string(), key),
+            ]);
         }
     }
 
     fn update_statistics(&mut self, scene: &mut Scene) {
-        if let Some(id) = self.stats_id {
+        if let Some(stats) = self.stats {
             let now = Instant::now();
             let tick_duration = now.duration_since(self.last_tick);
             self.last_tick = now;
@@ -265,16 +330,27 @@ impl SnakeLogic {
                 Some(hash_set) => hash_set.len(),
                 None => 0,
             };
-            if let Some(ui_object) = scene.objects.get_mut(&id) {
-                if let Some(stats_ui) = ui_object.get_mut::<Statistics>() {
-                    let lines = vec![
-                        format!("Current stage: {}", self.stage_id),
-                        format!("Tick Duration: {:.2?}", tick_duration),
-                        format!("Object Count: {}", objects_count),
-                        format!("Stateful Objects: {}", stateful_count),
-                    ];
-                    stats_ui.set_text(lines, Some(STATS_COLOR));
-                }
+            let length = self
+                .player
+                .snake
+                .and_then(|handle| scene.get(handle))
+                .map_or(0, |snake| snake.length());
+            self.player_length = length;
+            let scores = scene.resources.get_or_insert_with(Scores::default);
+            scores.best_length = scores.best_length.max(length);
+            let scores = *scores;
+
+            if let Some(stats_ui) = stats.get_mut(&mut scene.objects) {
+                let rows = vec![
+                    ("Current stage".to_string(), self.stage_id.to_string()),
+                    ("Tick Duration".to_string(), format!("{:.2?}", tick_duration)),
+                    ("Object Count".to_string(), objects_count.to_string()),
+                    ("Stateful Objects".to_string(), stateful_count.to_string()),
+                    ("Best Length".to_string(), scores.best_length.to_string()),
+                    ("Levels Won".to_string(), scores.levels_won.to_string()),
+                    ("Deaths".to_string(), scores.deaths.to_string()),
+                ];
+                stats_ui.set_rows(rows);
             }
         }
     }
@@ -292,8 +368,14 @@ impl SnakeLogic {
                         return Some(command);
                     }
                 }
-                Event::Resize(_, _) => {
-                    return Some(RuntimeCommand::Refresh);
+                Event::Mouse(mouse_event) => {
+                    self.console.handle_mouse(mouse_event, scene);
+                }
+                Event::Resize(width, height) => {
+                    self.layout.resize(width, height);
+                    self.layout.apply(scene);
+                    self.console.resize(scene, width, height);
+                    return Some(RuntimeCommand::Redraw);
                 }
                 _ => {}
             }
@@ -306,42 +388,43 @@ impl SnakeLogic {
         key_event: event::KeyEvent,
         scene: &mut Scene,
     ) -> Option<RuntimeCommand<StageKey>> {
+        match self.console.handle_key(key_event, scene) {
+            ConsoleInput::Ignored => {}
+            ConsoleInput::Consumed => return None,
+            ConsoleInput::Toggled => return Some(RuntimeCommand::Redraw),
+            ConsoleInput::Command(command) => return Some(command),
+            ConsoleInput::Run(invocation) => {
+                let result = invocation.run(self, scene);
+                return Some(self.console.report(scene, result));
+            }
+        }
+
         if !key_event.is_press() {
             return None;
         }
 
-        if let Some(snake_id) = self.player.snake {
-            if let Some(object) = scene.objects.get_mut(&snake_id) {
-                if let Some(snake) = object.get_mut::<Snake>() {
-                    match key_event.code {
-                        KeyCode::Char('w') => snake.direction = Direction::Up,
-                        KeyCode::Char('s') => snake.direction = Direction::Down,
-                        KeyCode::Char('a') => snake.direction = Direction::Left,
-                        KeyCode::Char('d') => snake.direction = Direction::Right,
-                        KeyCode::Char('W') => return Some(self.handle_grid_move(Direction::Up, scene)),
-                        KeyCode::Char('S') => return Some(self.handle_grid_move(Direction::Down, scene)),
-                        KeyCode::Char('A') => return Some(self.handle_grid_move(Direction::Left, scene)),
-                        KeyCode::Char('D') => return Some(self.handle_grid_move(Direction::Right, scene)),
-                        KeyCode::Char('q') => snake
-                            .resize_head_native(snake.head_size.native_size().saturating_sub(2)),
-                        KeyCode::Char('e') => snake
-                            .resize_head_native(snake.head_size.native_size().saturating_add(2)),
-                        KeyCode::Char('Q') => return Some(self.handle_new_grid(scene, false)),
-                        KeyCode::Char('E') => return Some(self.handle_new_grid(scene, true)),
-                        KeyCode::Char(' ') => snake.is_moving ^= true,
-                        KeyCode::Up => snake.base_index = snake.base_index.saturating_add(2),
-                        KeyCode::Down => snake.base_index = snake.base_index.saturating_sub(2),
-                        KeyCode::Left => self.handle_stage_switch(),
-                        KeyCode::Right => self.handle_stage_switch(),
-                        KeyCode::Char('f') => self.spawn_food(scene, 100),
-                        KeyCode::Tab => self.spawn_snakes(scene, 200),
-                        KeyCode::Char('r') => return Some(RuntimeCommand::Reset),
-                        KeyCode::Esc => self.quit = true,
-                        KeyCode::Char('p') => self.is_paused ^= true,
-                        KeyCode::Char('g') => self.switch_logic = true,
-                        _ => {}
-                    }
+        if let Some(snake) = self
+            .player
+            .snake
+            .and_then(|handle| handle.get_mut(&mut scene.objects))
+        {
+            if let KeyCode::Char(key) = key_event.code
+                && let Some(direction) = self.settings.controls.direction(key)
+            {
+                snake.direction = direction;
+                return None;
+            }
+
+            match key_event.code {
+                KeyCode::Char('q') => snake
+                    .resize_head_native(snake.head_size.native_size().saturating_sub(2)),
+                KeyCode::Char('e') => snake
+                    .resize_head_native(snake.head_size.native_size().saturating_add(2)),
+                KeyCode::Char(' ') => snake.is_moving ^= true,
+                KeyCode::Char('p') | KeyCode::Esc => {
+                    return Some(RuntimeCommand::PushStage(StageKey::Pause));
                 }
+                _ => {}
             }
         }
         None
@@ -360,9 +443,14 @@ impl SnakeLogic {
         return RuntimeCommand::Refresh;
     }
 
-    fn handle_grid_move(&mut self, direction: Direction, scene: &mut Scene) -> RuntimeCommand<StageKey> {
+    fn handle_grid_move(
+        &mut self,
+        direction: Direction,
+        distance: u8,
+        scene: &mut Scene,
+    ) -> RuntimeCommand<StageKey> {
         if let Some(grid) = &mut scene.spatial_grid {
-            let (dx, dy) = direction.get_move(5);
+            let (dx, dy) = direction.get_move(distance);
 
             self.grid_pos.x = self.grid_pos.x.saturating_add_signed(dx);
             self.grid_pos.y = self.grid_pos.y.saturating_add_signed(dy);
@@ -378,20 +466,55 @@ impl SnakeLogic {
         self.switch_stage = match self.stage_id {
             StageKey::Level0 => Some(StageKey::Level1),
             StageKey::Level1 => Some(StageKey::Level0),
+            _ => None,
         }
     }
 
-    fn spawn_food(&self, scene: &mut Scene, count: usize) {
+    /// Returns how many were spawned, a full grid takes fewer
+    fn spawn_food(&self, scene: &mut Scene, count: usize) -> usize {
+        let mut spawned = 0;
         for _ in 0..count {
-            if let Some(grid) = &scene.spatial_grid {
-                if let Some(pos) = grid.random_empty_pos() {
-                    scene.attach_object(|id| Box::new(Food::rng_food(id, pos)), Conflict::Cancel);
-                }
+            if let Some(grid) = &scene.spatial_grid
+                && let Some(pos) = grid.random_empty_pos()
+                && scene
+                    .attach_object(|id| Box::new(Food::rng_food(id, pos)), Conflict::Cancel)
+                    .is_some()
+            {
+                spawned += 1;
             }
         }
+        spawned
     }
 
-    fn spawn_snakes(&self, scene: &mut Scene, count: usize) {
+    /// Schedules a wave every `respawn_rate` ticks, the first one is spawned with the grid
+    fn setup_respawns(&mut self, scene: &mut Scene) {
+        let foods = self.level.food_spawners();
+        let bombs = self.level.bomb_spawners();
+
+        if self.level.respawn_rate != 0 {
+            let period = Delay::Ticks(self.level.respawn_rate);
+            let timer = scene
+                .timers
+                .every_fn(period, move |scene| spawn_wave(scene, &foods, &bombs));
+            self.respawn_timer = Some(timer);
+        }
+    }
+
+    fn is_level_won(&self, scene: &Scene) -> bool {
+        match self.level.win {
+            WinCondition::None => false,
+            WinCondition::Survive(ticks) => self.counter >= ticks,
+            WinCondition::Length(length) => self
+                .player
+                .snake
+                .and_then(|handle| scene.get(handle))
+                .is_some_and(|snake| snake.length() >= length),
+        }
+    }
+
+    /// Returns how many were spawned
+    fn spawn_snakes(&mut self, scene: &mut Scene, count: usize, brain: Brain) -> usize {
+        let mut spawned = 0;
         let gx = self.grid_pos.x;
         let gy = self.grid_pos.y;
 
@@ -402,7 +525,7 @@ impl SnakeLogic {
                 let y = ((self.counter as u16 + i_u16) * i_u16) % grid.height;
                 let pos = Position::new(x.saturating_add(gx), y.saturating_add(gy));
 
-                scene.attach_object(
+                let id = scene.attach_object(
                     |id| {
                         let mut snake = Snake::new(pos, id, 1);
                         snake.ignore_death = true;
@@ -434,76 +557,178 @@ impl SnakeLogic {
                     },
                     Conflict::Overwrite,
                 );
+                if let Some(id) = id {
+                    self.brains.insert(id, brain);
+                    spawned += 1;
+                }
             }
         }
+        spawned
     }
 
     fn update_ai_snakes(&self, scene: &mut Scene) {
-        let player_snake_id = self.player.snake;
         let mut rng = rand::rng();
+        let foods = match self.brains.values().any(|brain| *brain == Brain::Greedy) {
+            true => brain::food_positions(scene),
+            false => Vec::new(),
+        };
 
-        // Snakes is the only movable object here
-        // ideally we would use our own indexes
-        let movables = scene
-            .indexes
-            .get(&ObjectIndex::Movable)
-            .into_iter()
-            .flat_map(|hash_set| hash_set.iter());
-
-        for id in movables {
-            if Some(*id) == player_snake_id {
+        for (handle, snake) in scene.query_mut::<Snake>() {
+            if Some(handle) == self.player.snake {
                 continue;
             }
 
-            if let Some(object) = scene.objects.get_mut(id) {
-                if let Some(snake) = object.get_mut::<Snake>() {
-                    if rng.random_bool(0.1) {
-                        snake.direction = match rng.random_range(0..4) {
-                            0 => Direction::Up,
-                            1 => Direction::Left,
-                            2 => Direction::Down,
-                            _ => Direction::Right,
-                        };
-                    }
-                }
+            let brain = self.brains.get(&handle.id()).copied().unwrap_or_default();
+            if let Some(direction) = brain.steer(snake, &foods, &mut rng) {
+                snake.direction = direction;
             }
         }
     }
 }
 
+/// Builds the grid and spawns the first wave, runs on a loading thread when the stage reloads
+fn prepare_scene(
+    level: &Level,
+    bounds: (u16, u16, Position),
+    scene: &mut Scene,
+    status: &LoadStatus,
+) {
+    scene.attach_grid(build_grid(level, bounds));
+    status.set_progress(0.8);
+
+    if !status.is_cancelled() {
+        spawn_wave(scene, &level.food_spawners(), &level.bomb_spawners());
+    }
+}
+
+/// The info panel sits right of the grid, the statistics and the log below it
+fn build_layout() -> Layout<Panel> {
+    let mut layout = Layout::from_terminal();
+    layout.insert(
+        Panel::Info,
+        Node::new(
+            Anchor::RightOf(Panel::Grid),
+            Size::Fixed(INFO_WIDTH),
+            Size::Fixed(17),
+        )
+        .gap(PANEL_GAP),
+    );
+    layout.insert(
+        Panel::Stats,
+        Node::new(
+            Anchor::Below(Panel::Info),
+            Size::Fixed(INFO_WIDTH),
+            Size::Fixed(7),
+        )
+        .gap(1),
+    );
+    layout.insert(
+        Panel::Logger,
+        Node::new(Anchor::Below(Panel::Stats), Size::Fill, Size::Fixed(MAX_LOGS as u16))
+            .max_size(INFO_WIDTH * 2, MAX_LOGS as u16)
+            .gap(1),
+    );
+    layout
+}
+
+fn build_grid(level: &Level, (width, height, origin): (u16, u16, Position)) -> SpatialGrid {
+    SpatialGrid::new(width, height, level.border_style, origin, |grid_pos| {
+        level.terrain_at(grid_pos)
+    })
+}
+
+/// Spawns on the level's spawners, read relative to the grid's current origin.
+/// Every bomb gets a fuse and blows up by itself once it runs out.
+fn spawn_wave(scene: &mut Scene, foods: &[Position], bombs: &[Position]) {
+    let Some(origin) = scene.spatial_grid.as_ref().map(|grid| grid.origin) else {
+        return;
+    };
+
+    for grid_pos in foods {
+        let pos = Position::new(origin.x + grid_pos.x, origin.y + grid_pos.y);
+        scene.attach_object(|id| Box::new(Food::rng_food(id, pos)), Conflict::Cancel);
+    }
+
+    for grid_pos in bombs {
+        let pos = Position::new(origin.x + grid_pos.x, origin.y + grid_pos.y);
+        let Some(bomb) = scene.attach_typed(|id| Bomb::rng_bomb(id, pos), Conflict::Cancel) else {
+            continue;
+        };
+
+        if let Some(fuse) = scene.get(bomb).map(Bomb::fuse) {
+            scene
+                .timers
+                .after_fn(Delay::Time(fuse), move |scene| detonate(scene, bomb));
+        }
+    }
+}
+
+fn detonate(scene: &mut Scene, bomb: Handle<Bomb>) {
+    // Already gone if a snake ran into it
+    if let Some(explosion) = scene.get(bomb).map(Bomb::explosion) {
+        scene.particles.emit(explosion);
+        scene.commands.despawn(bomb.id());
+    }
+}
+
 impl Logic<StageKey> for SnakeLogic {
     fn init(&mut self, scene: &mut Scene) {
         self.setup_scene(scene);
     }
 
-    fn refresh(&mut self, scene: &mut Scene) {
-        if let Some(stats_id) = self.stats_id {
-            if let Some(object) = scene.objects.get_mut(&stats_id) {
-                if let Some(stats) = object.get_mut::<Statistics>() {
-                    stats.clear();
-                }
-            }
+    fn collect_old_stage(
+        &mut self,
+        old_scene: Option<Box<Scene>>,
+        _old_logic: Option<Box<dyn Logic<StageKey>>>,
+    ) {
+        // A reloaded scene still lacks the UI and the player
+        if old_scene.is_some() {
+            self.needs_setup = true;
         }
+    }
 
-        if let Some(logger_id) = self.logger_id {
-            if let Some(object) = scene.objects.get_mut(&logger_id) {
-                if let Some(logger) = object.get_mut::<Logger>() {
-                    logger.clear();
-                }
-            }
+    fn refresh(&mut self, scene: &mut Scene) {
+        self.sync_tick_rate = true;
+
+        if std::mem::take(&mut self.needs_setup) {
+            self.counter = 0;
+            self.setup_actors(scene);
         }
 
-        self.update_ui_pos(scene);
+        self.update_layout(scene);
         self.update_info(scene);
     }
 
+    // Refresh also runs on every resume, e.g. after the pause menu,
+    // so the log and the statistics are only cleared when the stage is entered
+    fn on_enter(&mut self, scene: &mut Scene) {
+        self.apply_settings(scene);
+        if let Some(stats) = self.stats.and_then(|handle| handle.get_mut(&mut scene.objects)) {
+            stats.clear();
+        }
+        if let Some(logger) = self.logger.and_then(|handle| handle.get_mut(&mut scene.objects)) {
+            logger.clear();
+            logger.push(format!("[STAGE]: {}", self.stage_id), Style::fg(LOGGER_COLOR));
+        }
+    }
+
+    fn on_resume(&mut self, scene: &mut Scene) {
+        // Time spent in the stages on top isn't a tick
+        self.last_tick = Instant::now();
+        self.apply_settings(scene);
+    }
+
     fn update(&mut self, scene: &mut Scene) -> RuntimeCommand<StageKey> {
-        if let Some(command) = self.handle_input(scene) {
+        if let Some(command) = self.handle_menu_request(scene) {
             return command;
         }
 
-        if self.quit {
-            return RuntimeCommand::Kill;
+        if std::mem::take(&mut self.restart_on_enter) {
+            return self.reload();
+        }
+
+        if let Some(command) = self.handle_input(scene) {
+            return command;
         }
 
         if let Some(key) = self.switch_stage {
@@ -513,47 +738,82 @@ impl Logic<StageKey> for SnakeLogic {
 
         if self.switch_logic {
             self.switch_logic = false;
+            if let Some(timer) = self.respawn_timer.take() {
+                scene.timers.cancel(timer);
+            }
             let new_logic = DeathLogic::build(
                 self.stage_id,
                 self.player,
-                self.stats_id,
-                self.logger_id,
-                self.info_id,
+                self.stats,
+                self.logger,
+                self.info,
+                self.layout.clone(),
                 self.grid_pos,
             );
             return RuntimeCommand::ReplaceLogic(Box::new(new_logic));
         }
 
-        if self.is_paused {
-            return RuntimeCommand::Skip;
+        if std::mem::take(&mut self.sync_tick_rate) {
+            return RuntimeCommand::SetTickRate(Duration::from_millis(self.speed));
+        }
+
+        if scene.is_paused() {
+            return RuntimeCommand::None;
         }
 
         self.update_statistics(scene);
         self.counter += 1;
 
-        if self.player.snake.is_some() && scene.objects.get(&self.player.snake.unwrap()).is_none() {
-            return RuntimeCommand::Kill;
+        if self
+            .player
+            .snake
+            .is_some_and(|handle| !handle.is_alive(&scene.objects))
+        {
+            return self.game_over(scene);
         }
 
         self.update_ai_snakes(scene);
 
-        RuntimeCommand::SetTickRate(Duration::from_millis(self.speed))
+        if self.is_level_won(scene) {
+            if let Some(logger) = self
+                .logger
+                .and_then(|handle| handle.get_mut(&mut scene.objects))
+            {
+                logger.push(format!("[WIN]: {}", self.level.name), Style::fg(LOGGER_COLOR));
+            }
+            scene.resources.get_or_insert_with(Scores::default).levels_won += 1;
+            self.handle_stage_switch();
+        }
+
+        RuntimeCommand::None
     }
 
     fn dispatch_events(&mut self, scene: &mut Scene) {
-        if self.is_debugging {
-            if let Some(logger_id) = self.logger_id {
-                if let Some(logger_object) = scene.objects.get_mut(&logger_id) {
-                    if let Some(logger_ui) = logger_object.get_mut::<Logger>() {
-                        let event_count = scene.event_bus.len();
-                        let start_index = event_count.saturating_sub(MAX_LOGS);
-                        for event in &scene.event_bus[start_index..] {
-                            logger_ui.add_log(event.log_message(), Some(LOGGER_COLOR));
-                        }
-                    }
-                }
+        self.event_manager.dispatch(scene);
+        // Before the sync, so the inspector still sees this tick's state changes
+        self.console.update(scene);
+
+        let player_id = self.player.snake.map(|handle| handle.id());
+        let meals = self
+            .event_manager
+            .dispatched()
+            .iter()
+            .filter_map(|event| event.as_any().downcast_ref::<FoodEvent>())
+            .filter(|event| Some(event.snake_id) == player_id)
+            .count();
+        self.player.score = self.player.score.saturating_add(meals as u16);
+
+        // Includes the events pushed by handlers during the dispatch
+        if self.is_debugging
+            && let Some(logger_ui) = self
+                .logger
+                .and_then(|handle| handle.get_mut(&mut scene.objects))
+        {
+            let dispatched = self.event_manager.dispatched();
+            let start_index = dispatched.len().saturating_sub(MAX_LOGS);
+            for event in &dispatched[start_index..] {
+                logger_ui.push(event.log_message(), Style::fg(LOGGER_COLOR));
             }
         }
-        self.event_manager.dispatch(scene);
     }
 }
@@ -0,0 +1,88 @@
+use engine::prelude::*;
+use rand::Rng;
+
+use super::game_objects::{Food, Snake, snake::Direction};
+
+/// Steers a snake which isn't the player's
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum Brain {
+    /// Turns now and then
+    #[default]
+    Random,
+    /// Heads for the closest food
+    Greedy,
+    /// Keeps its direction
+    Still,
+}
+
+impl Brain {
+    pub const NAMES: [&'static str; 3] = ["random", "greedy", "still"];
+
+    pub fn parse(name: &str) -> Option<Self> {
+        match name {
+            "random" => Some(Brain::Random),
+            "greedy" => Some(Brain::Greedy),
+            "still" => Some(Brain::Still),
+            _ => None,
+        }
+    }
+
+    pub fn steer(
+        &self,
+        snake: &Snake,
+        foods: &[Position],
+        rng: &mut impl Rng,
+    ) -> Option<Direction> {
+        match self {
+            Brain::Random => rng.random_bool(0.1).then(|| match rng.random_range(0..4) {
+                0 => Direction::Up,
+                1 => Direction::Left,
+                2 => Direction::Down,
+                _ => Direction::Right,
+            }),
+            Brain::Greedy => {
+                let head = snake.head_pos()?;
+                let target = foods
+                    .iter()
+                    .min_by_key(|food| head.x.abs_diff(food.x) + head.y.abs_diff(food.y))?;
+                let dx = target.x as i32 - head.x as i32;
+                let dy = target.y as i32 - head.y as i32;
+
+                // Along the longer distance first, the snake can't turn back on itself
+                let horizontal = match dx < 0 {
+                    true => Direction::Left,
+                    false => Direction::Right,
+                };
+                let vertical = match dy < 0 {
+                    true => Direction::Up,
+                    false => Direction::Down,
+                };
+                let (first, second) = match dx.abs() >= dy.abs() {
+                    true => (horizontal, vertical),
+                    false => (vertical, horizontal),
+                };
+                [first, second]
+                    .into_iter()
+                    .find(|direction| *direction != opposite(snake.direction))
+            }
+            Brain::Still => None,
+        }
+    }
+}
+
+/// Positions of every food in the scene, for greedy snakes
+pub fn food_positions(scene: &Scene) -> Vec<Position> {
+    scene
+        .query::<Food>()
+        .filter_map(|(_, food)| food.t_cells().next().map(|t_cell| t_cell.pos))
+        .collect()
+}
+
+fn opposite(direction: Direction) -> Direction {
+    match direction {
+        Direction::Up => Direction::Down,
+        Direction::Down => Direction::Up,
+        Direction::Left => Direction::Right,
+        Direction::Right => Direction::Left,
+    }
+}
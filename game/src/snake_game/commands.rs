@@ -0,0 +1,158 @@
+use engine::prelude::*;
+
+use super::SnakeLogic;
+use super::brain::Brain;
+use super::game_objects::snake::Direction;
+use crate::StageKey;
+
+type GameConsole = Console<StageKey, SnakeLogic>;
+type GameResult = CommandResult<StageKey>;
+
+/// The game's commands, next to the engine's
+pub fn register(console: &mut GameConsole) {
+    console.register(
+        ConsoleCommand::new(
+            "spawn",
+            "spawn <food|snake> [count]",
+            "Spawns food or snakes, brain=random|greedy|still",
+            spawn,
+        )
+        .completions(&["food", "snake"]),
+    );
+    console.register(ConsoleCommand::new(
+        "teleport",
+        "teleport [x y]",
+        "Moves the player, random without a cell",
+        teleport,
+    ));
+    console.register(
+        ConsoleCommand::new(
+            "camera",
+            "camera <direction> [cells]",
+            "Moves the grid on the screen",
+            camera,
+        )
+        .completions(&["up", "down", "left", "right"]),
+    );
+    console.register(
+        ConsoleCommand::new("grid", "grid <grow|shrink>", "Resizes the grid", grid)
+            .completions(&["grow", "shrink"]),
+    );
+    console.register(ConsoleCommand::new(
+        "zindex",
+        "zindex <index>",
+        "Sets the player's z-index",
+        z_index,
+    ));
+    console.register(ConsoleCommand::new(
+        "stage",
+        "stage",
+        "Switches to the other level",
+        stage,
+    ));
+    console.register(ConsoleCommand::new(
+        "logic",
+        "logic",
+        "Switches to the death logic",
+        logic,
+    ));
+    console.register(ConsoleCommand::new(
+        "reset",
+        "reset",
+        "Reloads the level",
+        reset,
+    ));
+}
+
+fn spawn(logic: &mut SnakeLogic, scene: &mut Scene, args: &Args) -> GameResult {
+    let count: usize = args.optional(1, "count", 1)?;
+    match args.get(0) {
+        Some("food") => {
+            let spawned = logic.spawn_food(scene, count);
+            Ok(Reply::message(format!("spawned {spawned} food")))
+        }
+        Some("snake") => {
+            let name = args.named("brain").unwrap_or("random");
+            let brain = Brain::parse(name).ok_or_else(|| {
+                format!("unknown brain '{name}', one of {}", Brain::NAMES.join(", "))
+            })?;
+            let spawned = logic.spawn_snakes(scene, count, brain);
+            Ok(Reply::message(format!("spawned {spawned} snakes")))
+        }
+        Some(kind) => Err(format!("can't spawn '{kind}'")),
+        None => Err("missing what to spawn".to_string()),
+    }
+}
+
+fn teleport(logic: &mut SnakeLogic, scene: &mut Scene, args: &Args) -> GameResult {
+    let grid = scene.spatial_grid.as_ref().ok_or("there is no grid")?;
+    let pos = match args.len() {
+        0 => grid.random_empty_pos().ok_or("the grid is full")?,
+        _ => {
+            let x: u16 = args.required(0, "x")?;
+            let y: u16 = args.required(1, "y")?;
+            if x >= grid.width || y >= grid.height {
+                return Err(format!("{x},{y} is outside of the grid"));
+            }
+            grid.pos_to_world(Position::new(x, y))
+        }
+    };
+
+    let snake = logic
+        .player
+        .snake
+        .and_then(|handle| handle.get_mut(&mut scene.objects))
+        .ok_or("the player has no snake")?;
+    snake.teleport(pos);
+    Ok(Reply::command(RuntimeCommand::Redraw)
+        .with_message(format!("teleported to {},{}", pos.x, pos.y)))
+}
+
+fn camera(logic: &mut SnakeLogic, scene: &mut Scene, args: &Args) -> GameResult {
+    let direction = match args.get(0) {
+        Some("up") => Direction::Up,
+        Some("down") => Direction::Down,
+        Some("left") => Direction::Left,
+        Some("right") => Direction::Right,
+        Some(direction) => return Err(format!("unknown direction '{direction}'")),
+        None => return Err("missing direction".to_string()),
+    };
+    let cells: u8 = args.optional(1, "cells", 5)?;
+    Ok(Reply::command(
+        logic.handle_grid_move(direction, cells, scene),
+    ))
+}
+
+fn grid(logic: &mut SnakeLogic, scene: &mut Scene, args: &Args) -> GameResult {
+    let is_grow = match args.get(0) {
+        Some("grow") => true,
+        Some("shrink") => false,
+        _ => return Err("grow or shrink?".to_string()),
+    };
+    Ok(Reply::command(logic.handle_new_grid(scene, is_grow)))
+}
+
+fn z_index(logic: &mut SnakeLogic, scene: &mut Scene, args: &Args) -> GameResult {
+    let z_index: u8 = args.required(0, "z-index")?;
+    let snake = logic
+        .player
+        .snake
+        .and_then(|handle| handle.get_mut(&mut scene.objects))
+        .ok_or("the player has no snake")?;
+    snake.base_index = z_index;
+    Ok(Reply::message(format!("z-index {z_index}")))
+}
+
+fn stage(logic: &mut SnakeLogic, _: &mut Scene, _: &Args) -> GameResult {
+    logic.handle_stage_switch();
+    Ok(Reply::none())
+}
+
+fn logic(logic: &mut SnakeLogic, _: &mut Scene, _: &Args) -> GameResult {
+    logic.switch_logic = true;
+    Ok(Reply::none())
+}
+
+fn reset(logic: &mut SnakeLogic, _: &mut Scene, _: &Args) -> GameResult {
+    Ok(Reply::command(logic.reload()))
+}
@@ -6,17 +6,14 @@ use std::time::{Duration, Instant};
 
 use super::events::{CollisionHandler, DeathHandler};
 use super::game_objects::{Snake, snake::Direction};
+use super::menu::{MenuRequest, RunSummary};
+use super::settings::Settings;
+use super::{Panel, Scores};
 use super::player::Player;
-use super::ui::{InfoPanel, Logger, Statistics};
 use crate::StageKey;
 use crate::snake_game::events::BombHandler;
 use crate::snake_game::game_objects::Bomb;
 
-const STATS_COLOR: Color = Color::Rgb {
-    r: 170,
-    g: 170,
-    b: 170,
-};
 const LOGGER_COLOR: Color = Color::Rgb {
     r: 200,
     g: 100,
@@ -30,13 +27,15 @@ pub struct DeathLogic {
     event_manager: EventManager,
     player: Player,
     counter: u64,
-    quit: bool,
-    stats_id: Option<Id>,
-    logger_id: Option<Id>,
-    info_id: Option<Id>,
+    player_length: usize,
+    stats: Option<Handle<Table>>,
+    logger: Option<Handle<LogView>>,
+    info: Option<Handle<TextBlock>>,
+    layout: Layout<Panel>,
     last_tick: Instant,
     is_debugging: bool,
-    is_paused: bool,
+    // Re-announces the tick rate after the logic was (re)entered
+    sync_tick_rate: bool,
     old_logic: Option<Box<dyn Logic<StageKey>>>,
     revert_logic: bool,
     grid_pos: Position,
@@ -46,9 +45,10 @@ impl DeathLogic {
     pub fn build(
         stage_id: StageKey,
         player: Player,
-        stats_id: Option<Id>,
-        logger_id: Option<Id>,
-        info_id: Option<Id>,
+        stats: Option<Handle<Table>>,
+        logger: Option<Handle<LogView>>,
+        info: Option<Handle<TextBlock>>,
+        layout: Layout<Panel>,
         grid_pos: Position,
     ) -> Self {
         let mut event_manager = EventManager::new();
@@ -61,13 +61,14 @@ impl DeathLogic {
             event_manager,
             player,
             counter: 0,
-            quit: false,
-            stats_id,
-            logger_id,
-            info_id,
+            player_length: 0,
+            stats,
+            logger,
+            info,
+            layout,
             last_tick: Instant::now(),
             is_debugging: true,
-            is_paused: false,
+            sync_tick_rate: true,
             old_logic: None,
             revert_logic: false,
             grid_pos
@@ -87,8 +88,10 @@ impl DeathLogic {
                         return Some(command);
                     }
                 }
-                Event::Resize(_, _) => {
-                    return Some(RuntimeCommand::Refresh);
+                Event::Resize(width, height) => {
+                    self.layout.resize(width, height);
+                    self.layout.apply(scene);
+                    return Some(RuntimeCommand::Redraw);
                 }
                 _ => {}
             }
@@ -105,29 +108,37 @@ impl DeathLogic {
             return None;
         }
 
-        if let Some(snake_id) = self.player.snake {
-            if let Some(object) = scene.objects.get_mut(&snake_id) {
-                if let Some(snake) = object.get_mut::<Snake>() {
-                    match key_event.code {
-                        KeyCode::Char('w') => snake.direction = Direction::Up,
-                        KeyCode::Char('s') => snake.direction = Direction::Down,
-                        KeyCode::Char('a') => snake.direction = Direction::Left,
-                        KeyCode::Char('d') => snake.direction = Direction::Right,
-                        KeyCode::Char('q') => snake
-                            .resize_head_native(snake.head_size.native_size().saturating_sub(2)),
-                        KeyCode::Char('e') => snake
-                            .resize_head_native(snake.head_size.native_size().saturating_add(2)),
-                        KeyCode::Char(' ') => snake.is_moving ^= true,
-                        KeyCode::Up => snake.base_index = snake.base_index.saturating_add(2),
-                        KeyCode::Down => snake.base_index = snake.base_index.saturating_sub(2),
-                        KeyCode::Char('f') => self.spawn_bomb(scene, 100),
-                        KeyCode::Char('g') => self.revert_logic = true,
-                        KeyCode::Tab => self.spawn_snakes(scene, 200),
-                        KeyCode::Esc => self.quit = true,
-                        KeyCode::Char('p') => self.is_paused ^= true,
-                        _ => {}
-                    }
+        if let Some(snake) = self
+            .player
+            .snake
+            .and_then(|handle| handle.get_mut(&mut scene.objects))
+        {
+            let controls = scene
+                .resources
+                .get::<Settings>()
+                .map_or_else(Default::default, |settings| settings.controls);
+            if let KeyCode::Char(key) = key_event.code
+                && let Some(direction) = controls.direction(key)
+            {
+                snake.direction = direction;
+                return None;
+            }
+
+            match key_event.code {
+                KeyCode::Char('q') => snake
+                    .resize_head_native(snake.head_size.native_size().saturating_sub(2)),
+                KeyCode::Char('e') => snake
+                    .resize_head_native(snake.head_size.native_size().saturating_add(2)),
+                KeyCode::Char(' ') => snake.is_moving ^= true,
+                KeyCode::Up => snake.base_index = snake.base_index.saturating_add(2),
+                KeyCode::Down => snake.base_index = snake.base_index.saturating_sub(2),
+                KeyCode::Char('f') => self.spawn_bomb(scene, 100),
+                KeyCode::Char('g') => self.revert_logic = true,
+                KeyCode::Tab => self.spawn_snakes(scene, 200),
+                KeyCode::Char('p') | KeyCode::Esc => {
+                    return Some(RuntimeCommand::PushStage(StageKey::Pause));
                 }
+                _ => {}
             }
         }
         None
@@ -192,73 +203,61 @@ impl DeathLogic {
     }
 
     fn update_ai_snakes(&self, scene: &mut Scene) {
-        let player_snake_id = self.player.snake;
         let mut rng = rand::rng();
 
-        // Snakes is the only movable object here
-        // ideally we would use our own indexes
-        let movables = scene
-            .indexes
-            .get(&ObjectIndex::Movable)
-            .into_iter()
-            .flat_map(|hash_set| hash_set.iter());
-
-        for id in movables {
-            if Some(*id) == player_snake_id {
+        for (handle, snake) in scene.query_mut::<Snake>() {
+            if Some(handle) == self.player.snake {
                 continue;
             }
 
-            if let Some(object) = scene.objects.get_mut(id) {
-                if let Some(snake) = object.get_mut::<Snake>() {
-                    snake.ignore_death = false;
-                    if rng.random_bool(0.4) {
-                        snake.direction = match rng.random_range(0..4) {
-                            0 => Direction::Up,
-                            1 => Direction::Left,
-                            2 => Direction::Down,
-                            _ => Direction::Right,
-                        };
-                    }
-                }
+            snake.ignore_death = false;
+            if rng.random_bool(0.4) {
+                snake.direction = match rng.random_range(0..4) {
+                    0 => Direction::Up,
+                    1 => Direction::Left,
+                    2 => Direction::Down,
+                    _ => Direction::Right,
+                };
             }
         }
     }
 
     fn update_info(&mut self, scene: &mut Scene) {
-        if let Some(id) = self.info_id {
-            if let Some(ui_object) = scene.objects.get_mut(&id) {
-                if let Some(panel) = ui_object.get_mut::<InfoPanel>() {
-                    panel.clear();
-                    let title_clr = Some(Color::Rgb {
-                        r: 200,
-                        g: 50,
-                        b: 50,
-                    });
-                    let key_clr = Some(Color::Rgb {
-                        r: 200,
-                        g: 175,
-                        b: 175,
-                    });
-
-                    panel.add_line(format!(":::[DEATH CONTROLS]:::"), title_clr, None);
-                    panel.add_line(format!("w,a,s,d:        Move Snake"), key_clr, None);
-                    panel.add_line(format!("q & e:          Resize Head"), key_clr, None);
-                    panel.add_line(format!("Space:          Toggle Move"), key_clr, None);
-                    panel.add_line(format!("p:              Pause Game"), key_clr, None);
-                    panel.add_line(format!("Esc:            Quit Game"), key_clr, None);
-                    panel.add_line(format!(""), None, None); // Spacer
-                    panel.add_line(format!(":::[DEATH DEBUG]:::"), title_clr, None);
-                    panel.add_line(format!("Up & Down:      Change Z-Index"), key_clr, None);
-                    panel.add_line(format!("g:              Switch Logic"), key_clr, None);
-                    panel.add_line(format!("f:              Spawn Bombs"), key_clr, None);
-                    panel.add_line(format!("Tab:            Spawn Snakes"), key_clr, None);
-                }
-            }
+        let controls = scene
+            .resources
+            .get::<Settings>()
+            .map_or_else(Default::default, |settings| settings.controls);
+        let controls = format!("{}:", controls.name());
+        if let Some(panel) = self.info.and_then(|handle| handle.get_mut(&mut scene.objects)) {
+            let key = Style::fg(Color::Rgb {
+                r: 255,
+                g: 255,
+                b: 255,
+            });
+            let title = Style::fg(Color::Rgb {
+                r: 175,
+                g: 200,
+                b: 200,
+            });
+
+            panel.set_lines(vec![
+                (":::[DEATH CONTROLS]:::".to_string(), title),
+                (format!("{:<16}Move Snake", controls), key),
+                ("q & e:          Resize Head".to_string(), key),
+                ("Space:          Toggle Move".to_string(), key),
+                ("p & Esc:        Pause Menu".to_string(), key),
+                (String::new(), Style::default()), // Spacer
+                (":::[DEATH DEBUG]:::".to_string(), title),
+                ("Up & Down:      Change Z-Index".to_string(), key),
+                ("g:              Switch Logic".to_string(), key),
+                ("f:              Spawn Bombs".to_string(), key),
+                ("Tab:            Spawn Snakes".to_string(), key),
+            ]);
         }
     }
 
     fn update_statistics(&mut self, scene: &mut Scene) {
-        if let Some(id) = self.stats_id {
+        if let Some(stats) = self.stats {
             let now = Instant::now();
             let tick_duration = now.duration_since(self.last_tick);
             self.last_tick = now;
@@ -267,17 +266,14 @@ impl DeathLogic {
                 Some(hash_set) => hash_set.len(),
                 None => 0,
             };
-            if let Some(ui_object) = scene.objects.get_mut(&id) {
-                if let Some(stats_ui) = ui_object.get_mut::<Statistics>() {
-                    let lines = vec![
-                        format!("Current stage: {}", self.stage_id),
-                        format!("Tick Duration: {:.2?}", tick_duration),
-                        format!("Object Count: {}", objects_count),
-                        format!("Stateful Objects: {}", stateful_count),
-                    ];
-                    // Colors has no effect due to optimisations
-                    stats_ui.set_text(lines, Some(STATS_COLOR));
-                }
+            if let Some(stats_ui) = stats.get_mut(&mut scene.objects) {
+                let rows = vec![
+                    ("Current stage".to_string(), self.stage_id.to_string()),
+                    ("Tick Duration".to_string(), format!("{:.2?}", tick_duration)),
+                    ("Object Count".to_string(), objects_count.to_string()),
+                    ("Stateful Objects".to_string(), stateful_count.to_string()),
+                ];
+                stats_ui.set_rows(rows);
             }
         }
     }
@@ -301,9 +297,20 @@ impl Logic<StageKey> for DeathLogic {
     }
 
     fn refresh(&mut self, scene: &mut Scene) {
+        self.sync_tick_rate = true;
         self.update_info(scene);
     }
 
+    fn on_resume(&mut self, scene: &mut Scene) {
+        self.last_tick = Instant::now();
+        self.update_info(scene);
+
+        // Menu requests are left for the snake logic
+        if scene.resources.contains::<MenuRequest>() {
+            self.revert_logic = true;
+        }
+    }
+
     fn update(&mut self, scene: &mut Scene) -> RuntimeCommand<StageKey> {
         if self.revert_logic {
             if let Some(logic) = self.old_logic.take() {
@@ -317,40 +324,54 @@ impl Logic<StageKey> for DeathLogic {
             return command;
         }
 
-        if self.quit {
-            return RuntimeCommand::Kill;
+        if std::mem::take(&mut self.sync_tick_rate) {
+            return RuntimeCommand::SetTickRate(Duration::from_millis(GAME_SPEED));
         }
 
-        if self.is_paused {
-            return RuntimeCommand::Skip;
+        if scene.is_paused() {
+            return RuntimeCommand::None;
         }
 
         self.update_statistics(scene);
         self.counter += 1;
 
-        if self.player.snake.is_some() && scene.objects.get(&self.player.snake.unwrap()).is_none() {
-            return RuntimeCommand::Kill;
+        if self
+            .player
+            .snake
+            .is_some_and(|handle| !handle.is_alive(&scene.objects))
+        {
+            scene.resources.get_or_insert_with(Scores::default).deaths += 1;
+            scene.resources.insert(RunSummary {
+                stage: self.stage_id,
+                score: self.player.score,
+                length: self.player_length,
+            });
+            return RuntimeCommand::PushStage(StageKey::GameOver);
+        }
+
+        if let Some(snake) = self.player.snake.and_then(|handle| scene.get(handle)) {
+            self.player_length = snake.length();
         }
 
         self.update_ai_snakes(scene);
 
-        RuntimeCommand::SetTickRate(Duration::from_millis(GAME_SPEED))
+        RuntimeCommand::None
     }
 
     fn dispatch_events(&mut self, scene: &mut Scene) {
-        if self.is_debugging {
-            if let Some(logger_id) = self.logger_id {
-                if let Some(logger_object) = scene.objects.get_mut(&logger_id) {
-                    if let Some(logger_ui) = logger_object.get_mut::<Logger>() {
-                        let event_count = scene.event_bus.len();
-                        let start_index = event_count.saturating_sub(MAX_LOGS);
-                        for event in &scene.event_bus[start_index..] {
-                            logger_ui.add_log(event.log_message(), Some(LOGGER_COLOR));
-                        }
-                    }
-                }
+        self.event_manager.dispatch(scene);
+
+        // Includes the events pushed by handlers during the dispatch
+        if self.is_debugging
+            && let Some(logger_ui) = self
+                .logger
+                .and_then(|handle| handle.get_mut(&mut scene.objects))
+        {
+            let dispatched = self.event_manager.dispatched();
+            let start_index = dispatched.len().saturating_sub(MAX_LOGS);
+            for event in &dispatched[start_index..] {
+                logger_ui.push(event.log_message(), Style::fg(LOGGER_COLOR));
             }
         }
-        self.event_manager.dispatch(scene);
     }
 }
@@ -1,4 +1,4 @@
-use engine::prelude::{Event, EventHandler, Id, ObjectExt, Scene};
+use engine::prelude::{Event, EventHandler, EventKey, Id, ObjectExt, Propagation, Scene};
 
 use crate::snake_game::game_objects::{
     snake::animation::{Effect, EffectStyle, EffectZone},
@@ -21,15 +21,20 @@ impl Event for BombEvent {
             self.snake_id.value, self.bomb_id.value,
         )
     }
+
+    fn dedup_key(&self) -> Option<EventKey> {
+        Some(EventKey::of(&self.bomb_id))
+    }
 }
 
 pub struct BombHandler;
 impl EventHandler<BombEvent> for BombHandler {
-    fn handle_event(&mut self, event: &BombEvent, scene: &mut Scene) {
+    fn handle_event(&mut self, event: &BombEvent, scene: &mut Scene) -> Propagation {
         let mut damage = 0;
         if let Some(object) = scene.objects.get(&event.bomb_id) {
             if let Some(bomb) = object.get::<Bomb>() {
                 damage = bomb.get_damage();
+                scene.particles.emit(bomb.explosion());
             }
         }
 
@@ -49,6 +54,7 @@ impl EventHandler<BombEvent> for BombHandler {
             }
         }
 
-        scene.remove_object(&event.bomb_id);
+        scene.commands.despawn(event.bomb_id);
+        Propagation::Continue
     }
 }
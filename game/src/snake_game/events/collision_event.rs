@@ -1,4 +1,4 @@
-use engine::prelude::{Event, EventHandler, Id, ObjectExt, Position, Scene};
+use engine::prelude::{Event, EventHandler, EventKey, Id, Position, Propagation, Scene};
 
 use super::{BombEvent, FoodEvent};
 
@@ -22,12 +22,18 @@ impl Event for CollisionEvent {
             self.actor.value, self.target.value, self.pos.x, self.pos.y
         )
     }
+
+    fn dedup_key(&self) -> Option<EventKey> {
+        // Wide heads hit the same object with several cells. Ordered, so two
+        // snakes running into each other in the same tick both collide.
+        Some(EventKey::of(&(self.actor, self.target)))
+    }
 }
 
 pub struct CollisionHandler;
 
 impl EventHandler<CollisionEvent> for CollisionHandler {
-    fn handle_event(&mut self, event: &CollisionEvent, scene: &mut Scene) {
+    fn handle_event(&mut self, event: &CollisionEvent, scene: &mut Scene) -> Propagation {
         #[derive(PartialEq, Eq)]
         enum ObjectType {
             Snake,
@@ -38,16 +44,14 @@ impl EventHandler<CollisionEvent> for CollisionHandler {
         }
 
         let get_object_type = |id: &Id| {
-            if let Some(obj) = scene.objects.get(id) {
-                if obj.get::<Snake>().is_some() {
-                    ObjectType::Snake
-                } else if obj.get::<Food>().is_some() {
-                    ObjectType::Food
-                } else if obj.get::<Bomb>().is_some() {
-                    ObjectType::Bomb
-                } else {
-                    ObjectType::Other
-                }
+            if scene.is::<Snake>(id) {
+                ObjectType::Snake
+            } else if scene.is::<Food>(id) {
+                ObjectType::Food
+            } else if scene.is::<Bomb>(id) {
+                ObjectType::Bomb
+            } else if scene.objects.contains_key(id) {
+                ObjectType::Other
             } else {
                 ObjectType::None
             }
@@ -66,6 +70,20 @@ impl EventHandler<CollisionEvent> for CollisionHandler {
                         pos: event.pos,
                     }));
                 }
+
+                // Heads meeting on the same cell, the target may not have run into the actor
+                // itself (e.g. it moved onto the cell the actor's head is on). Deaths are
+                // keyed by the snake, so a mirrored collision doesn't kill it twice.
+                let head_on = scene
+                    .handle::<Snake>(event.target)
+                    .and_then(|handle| scene.get(handle))
+                    .is_some_and(|target| target.head_covers(event.pos) && !target.ignore_death);
+                if head_on && !scene.protected_ids.contains(&event.target) {
+                    scene.event_bus.push(Box::new(DeathEvent {
+                        actor: event.target,
+                        pos: event.pos,
+                    }));
+                }
             }
 
             // Snake & Food
@@ -97,5 +115,89 @@ impl EventHandler<CollisionEvent> for CollisionHandler {
             }
             _ => {}
         }
+        Propagation::Continue
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use engine::prelude::{Conflict, EventManager};
+
+    use super::*;
+
+    fn deaths(events: &EventManager) -> Vec<Id> {
+        events
+            .dispatched()
+            .iter()
+            .filter_map(|event| event.as_any().downcast_ref::<DeathEvent>())
+            .map(|death| death.actor)
+            .collect()
+    }
+
+    fn collide(actor: Id, target: Id, pos: Position) -> Box<CollisionEvent> {
+        Box::new(CollisionEvent {
+            actor,
+            target,
+            pos,
+            ignore: false,
+        })
+    }
+
+    fn setup() -> (Scene, EventManager, Id, Id) {
+        let mut scene = Scene::new();
+        let a = scene
+            .attach_typed(
+                |id| Snake::new(Position::new(2, 2), id, 1),
+                Conflict::Ignore,
+            )
+            .unwrap()
+            .id();
+        let b = scene
+            .attach_typed(
+                |id| Snake::new(Position::new(8, 8), id, 1),
+                Conflict::Ignore,
+            )
+            .unwrap()
+            .id();
+
+        let mut events = EventManager::new();
+        events.register(CollisionHandler);
+        (scene, events, a, b)
+    }
+
+    #[test]
+    fn snakes_running_into_each_others_body_both_die() {
+        let (mut scene, mut events, a, b) = setup();
+        // Neither hit the other's head
+        scene.event_bus.push(collide(a, b, Position::new(8, 9)));
+        scene.event_bus.push(collide(b, a, Position::new(2, 3)));
+        events.dispatch(&mut scene);
+
+        assert_eq!(deaths(&events), vec![a, b]);
+    }
+
+    #[test]
+    fn a_wide_head_collides_once() {
+        let (mut scene, mut events, a, b) = setup();
+        scene.event_bus.push(collide(a, b, Position::new(8, 9)));
+        scene.event_bus.push(collide(a, b, Position::new(9, 9)));
+        events.dispatch(&mut scene);
+
+        assert_eq!(deaths(&events), vec![a]);
+    }
+
+    #[test]
+    fn heads_meeting_kill_both_snakes() {
+        let (mut scene, mut events, a, b) = setup();
+        // Only one side reported, the target's head is on the cell
+        scene.event_bus.push(collide(a, b, Position::new(8, 8)));
+        events.dispatch(&mut scene);
+        assert_eq!(deaths(&events), vec![a, b]);
+
+        // Both sides reported, each snake still dies once
+        scene.event_bus.push(collide(a, b, Position::new(8, 8)));
+        scene.event_bus.push(collide(b, a, Position::new(8, 8)));
+        events.dispatch(&mut scene);
+        assert_eq!(deaths(&events), vec![a, b]);
     }
 }
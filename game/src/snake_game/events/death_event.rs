@@ -1,4 +1,6 @@
-use engine::prelude::{Event, EventHandler, Id, Position, Scene};
+use engine::prelude::{Event, EventHandler, EventKey, Id, Position, Propagation, Scene};
+
+use crate::snake_game::game_objects::Snake;
 
 pub struct DeathEvent {
     pub actor: Id,
@@ -16,11 +18,23 @@ impl Event for DeathEvent {
             self.actor.value, self.pos.x, self.pos.y
         )
     }
+
+    fn dedup_key(&self) -> Option<EventKey> {
+        Some(EventKey::of(&self.actor))
+    }
 }
 
 pub struct DeathHandler;
 impl EventHandler<DeathEvent> for DeathHandler {
-    fn handle_event(&mut self, event: &DeathEvent, scene: &mut Scene) {
-        scene.remove_object(&event.actor);
+    fn handle_event(&mut self, event: &DeathEvent, scene: &mut Scene) -> Propagation {
+        if let Some(debris) = scene
+            .handle::<Snake>(event.actor)
+            .and_then(|handle| scene.get(handle))
+            .map(|snake| snake.debris(event.pos))
+        {
+            scene.particles.emit(debris);
+        }
+        scene.commands.despawn(event.actor);
+        Propagation::Continue
     }
 }
@@ -1,4 +1,4 @@
-use engine::prelude::{Event, EventHandler, Id, ObjectExt, Scene};
+use engine::prelude::{Event, EventHandler, EventKey, Id, ObjectExt, Propagation, Scene};
 
 use crate::snake_game::game_objects::{
     snake::animation::{Effect, EffectStyle, EffectZone},
@@ -21,15 +21,21 @@ impl Event for FoodEvent {
             self.snake_id.value, self.food_id.value,
         )
     }
+
+    fn dedup_key(&self) -> Option<EventKey> {
+        // A food is only eaten once, even when several snakes reach it
+        Some(EventKey::of(&self.food_id))
+    }
 }
 
 pub struct FoodHandler;
 impl EventHandler<FoodEvent> for FoodHandler {
-    fn handle_event(&mut self, event: &FoodEvent, scene: &mut Scene) {
+    fn handle_event(&mut self, event: &FoodEvent, scene: &mut Scene) -> Propagation {
         let mut meals = 0;
         if let Some(object) = scene.objects.get(&event.food_id) {
             if let Some(food) = object.get::<Food>() {
                 meals = food.get_meal();
+                scene.particles.emit(food.crumbs());
             }
         }
 
@@ -49,6 +55,7 @@ impl EventHandler<FoodEvent> for FoodHandler {
             }
         }
 
-        scene.remove_object(&event.food_id);
+        scene.commands.despawn(event.food_id);
+        Propagation::Continue
     }
 }
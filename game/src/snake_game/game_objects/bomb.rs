@@ -1,5 +1,6 @@
 use crossterm::style::Color;
 use rand::Rng;
+use std::time::Duration;
 
 use ::engine::prelude::*;
 
@@ -75,6 +76,44 @@ impl Bomb {
 
         bomb
     }
+
+    /// Game time until the bomb blows up by itself, bigger bombs burn longer
+    pub fn fuse(&self) -> Duration {
+        match self.kind {
+            Kind::LittleBoy => Duration::from_secs(8),
+            Kind::FatMan => Duration::from_secs(12),
+            Kind::ThinMan => Duration::from_secs(16),
+        }
+    }
+
+    /// Blast which scales with the damage of the bomb
+    pub fn explosion(&self) -> Emitter {
+        let style = ParticleStyle::new(
+            "✹✶*+·",
+            vec![
+                Color::Rgb {
+                    r: 255,
+                    g: 220,
+                    b: 120,
+                },
+                Color::Rgb {
+                    r: 230,
+                    g: 80,
+                    b: 40,
+                },
+                Color::Rgb {
+                    r: 90,
+                    g: 90,
+                    b: 90,
+                },
+            ],
+            200,
+        );
+
+        Emitter::burst(self.body.pos, 6 * self.damage as u32, style)
+            .speed(0.6, 1.6)
+            .lifetime(4, 8)
+    }
 }
 
 define_object! {
@@ -12,21 +12,20 @@ pub enum Kind {
     Grower,
 }
 
-// TODO - Change food to contain multiple elements (requires update loop and collision checks)
 #[derive(Debug)]
 pub struct Food {
     id: Id,
     kind: Kind,
     meal: u16,
-    body: TCell,
+    body: SpriteBody,
 }
 
 impl Food {
     pub fn new(obj_id: Id, kind: Kind, pos: Position) -> Self {
-        let (meal, symbol, color) = match kind {
+        let (meal, shape, color) = match kind {
             Kind::Cherry => (
                 1,
-                '⧝',
+                "⧝",
                 Color::Rgb {
                     r: 169,
                     g: 42,
@@ -35,7 +34,7 @@ impl Food {
             ),
             Kind::Mouse => (
                 2,
-                '⦺',
+                "~⦺",
                 Color::Rgb {
                     r: 42,
                     g: 69,
@@ -44,7 +43,7 @@ impl Food {
             ),
             Kind::Grower => (
                 3,
-                '⌘',
+                "⌘⌘\n⌘⌘",
                 Color::Rgb {
                     r: 242,
                     g: 242,
@@ -53,20 +52,13 @@ impl Food {
             ),
         };
 
+        let sprite = Sprite::from_text(shape, Some(color), None);
+
         Self {
             id: obj_id,
             kind,
             meal,
-            body: TCell {
-                occ: Occupant::new(obj_id, Id::new(0)),
-                style: Glyph {
-                    fg_clr: Some(color),
-                    bg_clr: None,
-                    symbol,
-                },
-                pos,
-                z_index: 0,
-            },
+            body: SpriteBody::new(obj_id, sprite, pos, 0),
         }
     }
 
@@ -79,12 +71,43 @@ impl Food {
 
         food
     }
+
+    /// Crumbs falling off when the food is eaten
+    pub fn crumbs(&self) -> Emitter {
+        let color = self
+            .body
+            .t_cells()
+            .find_map(|t_cell| t_cell.style.fg_clr)
+            .unwrap_or(Color::White);
+        let style = ParticleStyle::new(
+            "•·",
+            vec![
+                color,
+                Color::Rgb {
+                    r: 60,
+                    g: 60,
+                    b: 60,
+                },
+            ],
+            200,
+        );
+
+        Emitter::burst(self.body.pos(), 3 + 2 * self.meal as u32, style)
+            .direction(-std::f32::consts::FRAC_PI_2, std::f32::consts::PI)
+            .speed(0.3, 0.9)
+            .gravity(0.15)
+            .lifetime(3, 6)
+    }
+
+    fn get_t_cells(&self) -> Box<dyn Iterator<Item = &TCell> + '_> {
+        Box::new(self.body.t_cells())
+    }
 }
 
 define_object! {
     struct Food,
     id_field: id,
-    t_cells: single(body),
+    t_cells: custom(get_t_cells),
     capabilities: {
         Spatial{}
         Destructible{}
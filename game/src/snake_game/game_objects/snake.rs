@@ -2,8 +2,8 @@ pub mod animation;
 pub mod utils;
 
 use crossterm::style::Color;
-use std::collections::VecDeque;
 use engine::prelude::*;
+use std::collections::VecDeque;
 
 use crate::snake_game::events::{CollisionEvent, DeathEvent};
 use animation::Effect;
@@ -16,6 +16,7 @@ pub struct Snake {
     pub head_size: ResizeState,
     pending_resize: Option<ResizeState>,
     effect: Option<Effect>,
+    animator: Animator,
     pub is_alive: bool,
     pub meals: i16,
     head: Vec<TCell>, // Unsorted 2d vec
@@ -53,6 +54,7 @@ impl Snake {
             head_size: ResizeState::Normal { size: 1 },
             pending_resize: None,
             effect: None,
+            animator: Animator::new(),
             is_alive: true,
             meals: 30,
             head: Vec::from([TCell::new(
@@ -341,7 +343,6 @@ impl Snake {
         }
     }
 
-    // TODO - implement EffectZone and EffectStyle
     fn tick_effect(&mut self) {
         let Some(mut effect) = self.effect.take() else {
             return;
@@ -351,6 +352,7 @@ impl Snake {
 
         if effect.is_expired() {
             self.reset_head_size();
+            self.animator.stop(effect.kind.name());
             self.effect = None;
         } else {
             self.effect = Some(effect)
@@ -363,9 +365,75 @@ impl Snake {
         } else {
             self.reset_head_size();
         }
+        if let Some(effect) = &self.effect {
+            self.animator.stop(effect.kind.name());
+        }
+        new_effect.play(&mut self.animator);
         self.effect = Some(new_effect);
     }
 
+    /// Debris scattered from the head when the snake dies
+    pub fn debris(&self, pos: Position) -> Emitter {
+        let symbols: String = [self.head_style.symbol, self.body_style.symbol, '·']
+            .iter()
+            .collect();
+        let colors = [
+            self.head_style.fg_clr,
+            self.body_style.fg_clr,
+            Some(Color::DarkGrey),
+        ]
+        .into_iter()
+        .flatten()
+        .collect();
+
+        Emitter::burst(
+            pos,
+            12 + self.length() as u32 / 4,
+            ParticleStyle::new(&symbols, colors, 200),
+        )
+        .speed(0.4, 1.4)
+        .gravity(0.1)
+        .lifetime(6, 12)
+    }
+
+    /// Length of the snake in body segments
+    pub fn length(&self) -> usize {
+        self.body.len()
+    }
+
+    /// Top left cell of the head
+    pub fn head_pos(&self) -> Option<Position> {
+        self.head.iter().map(|t_cell| t_cell.pos).min_by_key(|pos| (pos.y, pos.x))
+    }
+
+    pub fn head_covers(&self, pos: Position) -> bool {
+        self.head.iter().any(|t_cell| t_cell.pos == pos)
+    }
+
+    /// Moves the whole snake, keeping its shape, so the head's top left cell lands on `pos`
+    pub fn teleport(&mut self, pos: Position) {
+        let Some(head_pos) = self.head_pos() else {
+            return;
+        };
+        let dx = pos.x as i32 - head_pos.x as i32;
+        let dy = pos.y as i32 - head_pos.y as i32;
+        let shift = |value: u16, delta: i32| (value as i32 + delta).clamp(0, u16::MAX as i32) as u16;
+
+        let mut changes = Vec::new();
+        for t_cell in self.get_t_cells_mut() {
+            let init_pos = t_cell.pos;
+            t_cell.pos = Position::new(shift(init_pos.x, dx), shift(init_pos.y, dy));
+            changes.push(StateChange::Update {
+                t_cell: *t_cell,
+                init_pos,
+            });
+        }
+
+        for change in changes {
+            self.state.upsert_change(change);
+        }
+    }
+
     pub fn get_t_cells(&self) -> Box<dyn Iterator<Item = &TCell> + '_> {
         Box::new(
             self.head
@@ -374,6 +442,16 @@ impl Snake {
         )
     }
 
+    fn get_t_cells_mut(&mut self) -> Box<dyn Iterator<Item = &mut TCell> + '_> {
+        Box::new(
+            self.head.iter_mut().chain(
+                self.body
+                    .iter_mut()
+                    .flat_map(|segment| segment.t_cells.iter_mut()),
+            ),
+        )
+    }
+
     // TODO - Fix
     fn predict_head_resize_pos(&self, new_size: usize) -> Vec<Position> {
         if self.head.is_empty() {
@@ -442,6 +520,7 @@ define_object! {
         Stateful { state_field: state }
         Destructible {}
         Spatial {}
+        Animated { animator_field: animator, t_cells_mut: get_t_cells_mut }
         Movable {
             impl {
                 fn probe_move(&self) -> Box<dyn Iterator<Item = Position> + '_> {
@@ -534,6 +613,17 @@ define_object! {
 
                     if let Some(hits) = probe {
                         for hit in hits {
+                            // Walls and other solid terrain
+                            if hit.cell.terrain.is_collidable && !self.ignore_death {
+                                let event = DeathEvent {
+                                    actor: self.id,
+                                    pos: hit.pos,
+                                };
+                                events.clear();
+                                events.push(Box::new(event));
+                                return events;
+                            }
+
                             if let Some(t_cell) = hit.cell.occ_by {
                                 if t_cell.occ.obj_id == self.id {
 
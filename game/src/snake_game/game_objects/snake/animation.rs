@@ -1,4 +1,7 @@
-//use crossterm::style::Color;
+use crossterm::style::Color;
+use engine::prelude::{
+    Animation, AnimationTarget, Animator, Easing, GlyphPatch, GlyphTween, PlayMode, Tween,
+};
 
 #[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
 pub enum EffectStyle {
@@ -14,14 +17,52 @@ pub enum EffectZone {
     All,
 }
 
-// impl EffectStyle {
-//     fn color(&self) -> Option<Color> {
-//         match self {
-//             EffectStyle::Damage => Some(Color::Red),
-//             EffectStyle::Grow => None,
-//         }
-//     }
-// }
+impl EffectStyle {
+    pub fn name(&self) -> &'static str {
+        match self {
+            EffectStyle::Damage => "damage",
+            EffectStyle::Grow => "grow",
+        }
+    }
+
+    /// Plays the glyph animation which goes alongside the effect
+    pub fn play(&self, animator: &mut Animator, duration: usize, zone: EffectZone) {
+        let target = match zone {
+            EffectZone::All => AnimationTarget::All,
+        };
+
+        match self {
+            EffectStyle::Damage => {
+                let flash = GlyphPatch::new().fg(Color::Red);
+                animator.play(
+                    Animation::new(self.name(), PlayMode::Loop)
+                        .keyframe(2, flash)
+                        .pause(2)
+                        .target(target)
+                        .duration(duration as u32),
+                );
+            }
+            EffectStyle::Grow => {
+                let shimmer = Tween::new(
+                    Color::Rgb {
+                        r: 255,
+                        g: 255,
+                        b: 180,
+                    },
+                    Color::Rgb {
+                        r: 180,
+                        g: 255,
+                        b: 255,
+                    },
+                    3,
+                    Easing::QuadInOut,
+                )
+                .mode(PlayMode::PingPong);
+                animator.play_tween(GlyphTween::new(self.name()).fg(shimmer).target(target));
+            }
+        }
+    }
+}
 
 #[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
 pub struct Effect {
@@ -46,6 +87,10 @@ impl Effect {
         }
     }
 
+    pub fn play(&self, animator: &mut Animator) {
+        self.kind.play(animator, self.duration, self.zone);
+    }
+
     pub fn next_tick(&mut self) {
         self.duration = self.duration.saturating_sub(1);
     }
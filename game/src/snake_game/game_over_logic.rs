@@ -0,0 +1,73 @@
+use engine::prelude::*;
+
+use super::Scores;
+use super::menu::{MenuInput, MenuRequest, MenuScreen, RunSummary};
+use crate::StageKey;
+
+const RETRY: usize = 0;
+const TITLE: usize = 1;
+
+/// Pushed over a level once the player died, the level restarts or quits to the title
+pub struct GameOverLogic {
+    screen: MenuScreen,
+}
+
+impl GameOverLogic {
+    pub fn new() -> Self {
+        let items = vec![
+            "Retry".to_string(),
+            "Back to Title".to_string(),
+            "Quit Game".to_string(),
+        ];
+
+        Self {
+            screen: MenuScreen::new("GAME OVER", items),
+        }
+    }
+
+    fn update_text(&mut self, scene: &mut Scene) {
+        let scores = *scene.resources.get_or_insert_with(Scores::default);
+        let mut text = Vec::new();
+        if let Some(summary) = scene.resources.get::<RunSummary>() {
+            text.push(format!("{}", summary.stage));
+            text.push(format!("Score: {}", summary.score));
+            text.push(format!("Length: {}", summary.length));
+        }
+        text.push(format!("Best Length: {}", scores.best_length));
+        self.screen.set_text(scene, text);
+    }
+
+    // The level below picks the request up once it's resumed
+    fn pop_with(scene: &mut Scene, request: MenuRequest) -> RuntimeCommand<StageKey> {
+        scene.resources.insert(request);
+        RuntimeCommand::PopStage
+    }
+}
+
+impl Logic<StageKey> for GameOverLogic {
+    fn init(&mut self, scene: &mut Scene) {
+        self.screen.attach(scene);
+        self.update_text(scene);
+    }
+
+    fn refresh(&mut self, scene: &mut Scene) {
+        self.screen.select(scene, RETRY);
+        self.update_text(scene);
+    }
+
+    fn update(&mut self, scene: &mut Scene) -> RuntimeCommand<StageKey> {
+        match self.screen.handle_input(scene) {
+            Some(MenuInput::Select(RETRY)) => Self::pop_with(scene, MenuRequest::Restart),
+            Some(MenuInput::Select(TITLE)) | Some(MenuInput::Back) => {
+                Self::pop_with(scene, MenuRequest::QuitToTitle)
+            }
+            Some(MenuInput::Select(_)) => RuntimeCommand::Kill,
+            Some(MenuInput::Redraw) => RuntimeCommand::Redraw,
+            Some(MenuInput::Change(..)) | None => RuntimeCommand::None,
+        }
+    }
+
+    fn dispatch_events(&mut self, scene: &mut Scene) {
+        scene.event_bus.clear();
+    }
+}
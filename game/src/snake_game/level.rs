@@ -0,0 +1,594 @@
+use crossterm::style::Color;
+use engine::prelude::{Glyph, Position, Terrain};
+use std::collections::HashMap;
+use std::fmt::{self, Display, Formatter};
+use std::path::Path;
+use std::str::FromStr;
+
+// Level files are plain text and made up of three sections:
+//
+// [level]              Metadata as `key = value` pairs
+// [legend]             `<char> = <kind> [key=value ...]`
+// [map]                ASCII map, one row per line
+//
+// Lines starting with `//` are comments. Cells which aren't
+// covered by the map are filled with the `fill` legend entry.
+
+const DEFAULT_TICK: u64 = 20;
+const DEFAULT_RESPAWN: u64 = 200;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WinCondition {
+    None,
+    Length(usize),
+    Survive(u64),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Tile {
+    Terrain(Terrain),
+    FoodSpawner,
+    BombSpawner,
+    PlayerStart,
+}
+
+#[derive(Debug)]
+pub enum LevelError {
+    Io(String),
+    MissingSection(&'static str),
+    MissingKey(&'static str),
+    InvalidValue { line: usize, value: String },
+    UnknownTile { line: usize, symbol: char },
+}
+
+impl Display for LevelError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            LevelError::Io(err) => write!(f, "Could not read level: {}", err),
+            LevelError::MissingSection(section) => write!(f, "Missing section: [{}]", section),
+            LevelError::MissingKey(key) => write!(f, "Missing key: {}", key),
+            LevelError::InvalidValue { line, value } => {
+                write!(f, "Invalid value at line {}: {}", line, value)
+            }
+            LevelError::UnknownTile { line, symbol } => {
+                write!(f, "Unknown tile at line {}: '{}'", line, symbol)
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Level {
+    pub name: String,
+    pub width: u16,
+    pub height: u16,
+    pub border_style: Option<Glyph>,
+    pub tick_rate: u64,
+    pub respawn_rate: u64,
+    pub win: WinCondition,
+    fill: Terrain,
+    rows: Vec<Vec<Tile>>,
+}
+
+impl Level {
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, LevelError> {
+        let source =
+            std::fs::read_to_string(path).map_err(|err| LevelError::Io(err.to_string()))?;
+        Self::parse(&source)
+    }
+
+    pub fn parse(source: &str) -> Result<Self, LevelError> {
+        let mut meta: HashMap<&str, (usize, &str)> = HashMap::new();
+        let mut legend: HashMap<char, (usize, &str)> = HashMap::new();
+        let mut map: Vec<(usize, &str)> = Vec::new();
+        let mut section = "";
+
+        for (index, line) in source.lines().enumerate() {
+            let line_nr = index + 1;
+
+            if section != "map" && (line.trim().is_empty() || line.trim_start().starts_with("//"))
+            {
+                continue;
+            }
+
+            if let Some(name) = line.trim().strip_prefix('[').and_then(|l| l.strip_suffix(']')) {
+                section = match name {
+                    "level" => "level",
+                    "legend" => "legend",
+                    "map" => "map",
+                    _ => {
+                        return Err(LevelError::InvalidValue {
+                            line: line_nr,
+                            value: line.to_string(),
+                        });
+                    }
+                };
+                continue;
+            }
+
+            match section {
+                "level" => {
+                    let (key, value) = split_pair(line, line_nr)?;
+                    meta.insert(key, (line_nr, value));
+                }
+                "legend" => {
+                    let (key, value) = split_pair(line, line_nr)?;
+                    let mut chars = key.chars();
+                    match (chars.next(), chars.next()) {
+                        (Some(symbol), None) => {
+                            legend.insert(symbol, (line_nr, value));
+                        }
+                        _ => {
+                            return Err(LevelError::InvalidValue {
+                                line: line_nr,
+                                value: key.to_string(),
+                            });
+                        }
+                    }
+                }
+                "map" => map.push((line_nr, line)),
+                _ => {
+                    return Err(LevelError::InvalidValue {
+                        line: line_nr,
+                        value: line.to_string(),
+                    });
+                }
+            }
+        }
+
+        if meta.is_empty() {
+            return Err(LevelError::MissingSection("level"));
+        }
+        if legend.is_empty() {
+            return Err(LevelError::MissingSection("legend"));
+        }
+
+        // Trailing blank lines are not part of the map
+        while map.last().is_some_and(|(_, row)| row.trim().is_empty()) {
+            map.pop();
+        }
+
+        let mut tiles: HashMap<char, Tile> = HashMap::new();
+        for (symbol, (line, value)) in &legend {
+            tiles.insert(*symbol, parse_tile(value, *line)?);
+        }
+
+        let name = meta
+            .get("name")
+            .map(|(_, value)| value.to_string())
+            .unwrap_or_default();
+        // Sizes out of the u16 range are rejected, not truncated
+        let width: u16 = parse_number(&meta, "width")?.ok_or(LevelError::MissingKey("width"))?;
+        let height: u16 = parse_number(&meta, "height")?.ok_or(LevelError::MissingKey("height"))?;
+        let tick_rate = parse_number(&meta, "tick")?.unwrap_or(DEFAULT_TICK);
+        let respawn_rate = parse_number(&meta, "respawn")?.unwrap_or(DEFAULT_RESPAWN);
+
+        let border_style = match meta.get("border") {
+            None => None,
+            Some((_, "none")) => None,
+            Some((line, value)) => Some(parse_glyph(value, *line)?),
+        };
+
+        let win = match meta.get("win") {
+            None => WinCondition::None,
+            Some((line, value)) => parse_win(value, *line)?,
+        };
+
+        let (fill_line, fill_value) = meta.get("fill").ok_or(LevelError::MissingKey("fill"))?;
+        let fill = match fill_value
+            .chars()
+            .next()
+            .and_then(|symbol| tiles.get(&symbol))
+        {
+            Some(Tile::Terrain(terrain)) => *terrain,
+            _ => {
+                return Err(LevelError::InvalidValue {
+                    line: *fill_line,
+                    value: fill_value.to_string(),
+                });
+            }
+        };
+
+        let mut rows = Vec::with_capacity(map.len());
+        for (index, (line, row)) in map.into_iter().enumerate() {
+            // Tiles outside of the level would be dropped silently
+            if index >= height as usize || row.chars().count() > width as usize {
+                return Err(LevelError::InvalidValue {
+                    line,
+                    value: row.to_string(),
+                });
+            }
+
+            let mut tile_row = Vec::with_capacity(row.len());
+            for symbol in row.chars() {
+                match tiles.get(&symbol) {
+                    Some(tile) => tile_row.push(*tile),
+                    None => return Err(LevelError::UnknownTile { line, symbol }),
+                }
+            }
+            rows.push(tile_row);
+        }
+
+        Ok(Self {
+            name,
+            width,
+            height,
+            border_style,
+            tick_rate,
+            respawn_rate,
+            win,
+            fill,
+            rows,
+        })
+    }
+
+    fn tile_at(&self, grid_pos: Position) -> Option<&Tile> {
+        self.rows
+            .get(grid_pos.y as usize)
+            .and_then(|row| row.get(grid_pos.x as usize))
+    }
+
+    /// Terrain at a grid position. Spawners sit on top of the fill terrain.
+    pub fn terrain_at(&self, grid_pos: Position) -> Terrain {
+        match self.tile_at(grid_pos) {
+            Some(Tile::Terrain(terrain)) => *terrain,
+            _ => self.fill,
+        }
+    }
+
+    fn positions_of(&self, kind: Tile) -> impl Iterator<Item = Position> + '_ {
+        self.rows.iter().enumerate().flat_map(move |(y, row)| {
+            row.iter()
+                .enumerate()
+                .filter(move |(_, tile)| **tile == kind)
+                .map(move |(x, _)| Position::new(x as u16, y as u16))
+        })
+    }
+
+    /// Food spawner positions in grid coordinates
+    pub fn food_spawners(&self) -> Vec<Position> {
+        self.positions_of(Tile::FoodSpawner).collect()
+    }
+
+    /// Bomb spawner positions in grid coordinates
+    pub fn bomb_spawners(&self) -> Vec<Position> {
+        self.positions_of(Tile::BombSpawner).collect()
+    }
+
+    /// Player start in grid coordinates (defaults to the center)
+    pub fn player_start(&self) -> Position {
+        self.positions_of(Tile::PlayerStart)
+            .next()
+            .unwrap_or(Position::new(self.width / 2, self.height / 2))
+    }
+}
+
+fn split_pair(line: &str, line_nr: usize) -> Result<(&str, &str), LevelError> {
+    line.split_once('=')
+        .map(|(key, value)| (key.trim(), value.trim()))
+        .ok_or(LevelError::InvalidValue {
+            line: line_nr,
+            value: line.to_string(),
+        })
+}
+
+fn parse_number<T: FromStr>(
+    meta: &HashMap<&str, (usize, &str)>,
+    key: &'static str,
+) -> Result<Option<T>, LevelError> {
+    match meta.get(key) {
+        None => Ok(None),
+        Some((line, value)) => value
+            .parse::<T>()
+            .map(Some)
+            .map_err(|_| LevelError::InvalidValue {
+                line: *line,
+                value: value.to_string(),
+            }),
+    }
+}
+
+fn parse_win(value: &str, line: usize) -> Result<WinCondition, LevelError> {
+    let invalid = || LevelError::InvalidValue {
+        line,
+        value: value.to_string(),
+    };
+
+    let mut parts = value.split_whitespace();
+    match (parts.next(), parts.next()) {
+        (Some("none"), None) => Ok(WinCondition::None),
+        (Some("length"), Some(amount)) => amount
+            .parse()
+            .map(WinCondition::Length)
+            .map_err(|_| invalid()),
+        (Some("survive"), Some(ticks)) => ticks
+            .parse()
+            .map(WinCondition::Survive)
+            .map_err(|_| invalid()),
+        _ => Err(invalid()),
+    }
+}
+
+/// Parses legend entries, e.g: `wall fg=#c8c8c8 symbol=█ z=255`
+fn parse_tile(value: &str, line: usize) -> Result<Tile, LevelError> {
+    let (kind, props) = value.split_once(' ').unwrap_or((value, ""));
+
+    match kind {
+        "food" => Ok(Tile::FoodSpawner),
+        "bomb" => Ok(Tile::BombSpawner),
+        "player" => Ok(Tile::PlayerStart),
+        "terrain" | "wall" => {
+            let style = parse_glyph(props, line)?;
+            let z_index = match find_prop(props, "z") {
+                Some(z) => z.parse().map_err(|_| LevelError::InvalidValue {
+                    line,
+                    value: z.to_string(),
+                })?,
+                None if kind == "wall" => 255,
+                None => 0,
+            };
+
+            if kind == "wall" {
+                Ok(Tile::Terrain(Terrain::solid(style, z_index)))
+            } else {
+                Ok(Tile::Terrain(Terrain::new(style, z_index)))
+            }
+        }
+        _ => Err(LevelError::InvalidValue {
+            line,
+            value: kind.to_string(),
+        }),
+    }
+}
+
+fn find_prop<'a>(props: &'a str, key: &str) -> Option<&'a str> {
+    props
+        .split_whitespace()
+        .filter_map(|prop| prop.split_once('='))
+        .find(|(prop_key, _)| *prop_key == key)
+        .map(|(_, value)| value)
+}
+
+/// Parses glyph properties, e.g: `fg=#ff00ff bg=black symbol=█`
+fn parse_glyph(props: &str, line: usize) -> Result<Glyph, LevelError> {
+    let fg_clr = find_prop(props, "fg")
+        .map(|value| parse_color(value, line))
+        .transpose()?;
+    let bg_clr = find_prop(props, "bg")
+        .map(|value| parse_color(value, line))
+        .transpose()?;
+    let symbol = find_prop(props, "symbol")
+        .and_then(|value| value.chars().next())
+        .unwrap_or(' ');
+
+    Ok(Glyph::new(fg_clr, bg_clr, symbol))
+}
+
+fn parse_color(value: &str, line: usize) -> Result<Color, LevelError> {
+    let invalid = || LevelError::InvalidValue {
+        line,
+        value: value.to_string(),
+    };
+
+    if let Some(hex) = value.strip_prefix('#') {
+        // Checked as ASCII first, the channels are sliced by byte
+        if !hex.is_ascii() || hex.len() != 6 {
+            return Err(invalid());
+        }
+        let channel = |range| u8::from_str_radix(&hex[range], 16).map_err(|_| invalid());
+        return Ok(Color::Rgb {
+            r: channel(0..2)?,
+            g: channel(2..4)?,
+            b: channel(4..6)?,
+        });
+    }
+
+    match value {
+        "black" => Ok(Color::Black),
+        "white" => Ok(Color::White),
+        "grey" => Ok(Color::Grey),
+        "red" => Ok(Color::Red),
+        "green" => Ok(Color::Green),
+        "blue" => Ok(Color::Blue),
+        "yellow" => Ok(Color::Yellow),
+        "cyan" => Ok(Color::Cyan),
+        "magenta" => Ok(Color::Magenta),
+        _ => Err(invalid()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LEVEL: &str = "\
+// A small level
+[level]
+name = Test
+width = 4
+height = 3
+border = fg=#ff00ff symbol=#
+win = length 12
+fill = .
+
+[legend]
+. = terrain fg=black bg=black
+# = wall fg=#c8c8c8 symbol=█
+f = food
+b = bomb
+@ = player
+
+[map]
+#f.#
+.@b.
+
+";
+
+    // Swaps a line of the level for another
+    fn with_line(old: &str, new: &str) -> String {
+        assert!(LEVEL.contains(old));
+        LEVEL.replace(old, new)
+    }
+
+    #[test]
+    fn parses_a_level() {
+        let level = Level::parse(LEVEL).unwrap();
+        assert_eq!(level.name, "Test");
+        assert_eq!((level.width, level.height), (4, 3));
+        assert_eq!(level.tick_rate, DEFAULT_TICK);
+        assert_eq!(level.respawn_rate, DEFAULT_RESPAWN);
+        assert_eq!(level.win, WinCondition::Length(12));
+        assert_eq!(
+            level.border_style,
+            Some(Glyph::new(
+                Some(Color::Rgb {
+                    r: 255,
+                    g: 0,
+                    b: 255
+                }),
+                None,
+                '#'
+            ))
+        );
+
+        // Trailing blank lines are dropped, missing rows are filled
+        assert_eq!(level.rows.len(), 2);
+        assert!(level.terrain_at(Position::new(0, 0)).is_collidable);
+        assert!(!level.terrain_at(Position::new(1, 0)).is_collidable);
+        assert_eq!(level.terrain_at(Position::new(3, 2)), level.fill);
+        assert_eq!(level.food_spawners(), vec![Position::new(1, 0)]);
+        assert_eq!(level.bomb_spawners(), vec![Position::new(2, 1)]);
+        assert_eq!(level.player_start(), Position::new(1, 1));
+    }
+
+    #[test]
+    fn player_starts_in_the_center_without_a_start_tile() {
+        let level = Level::parse(&with_line(".@b.", "..b.")).unwrap();
+        assert_eq!(level.player_start(), Position::new(2, 1));
+    }
+
+    #[test]
+    fn missing_sections() {
+        let no_level = &LEVEL[LEVEL.find("[legend]").unwrap()..];
+        assert!(matches!(
+            Level::parse(no_level),
+            Err(LevelError::MissingSection("level"))
+        ));
+
+        let no_legend = "[level]\nwidth = 4\n[map]\n....\n";
+        assert!(matches!(
+            Level::parse(no_legend),
+            Err(LevelError::MissingSection("legend"))
+        ));
+    }
+
+    #[test]
+    fn missing_keys() {
+        assert!(matches!(
+            Level::parse(&with_line("width = 4\n", "")),
+            Err(LevelError::MissingKey("width"))
+        ));
+        assert!(matches!(
+            Level::parse(&with_line("fill = .\n", "")),
+            Err(LevelError::MissingKey("fill"))
+        ));
+    }
+
+    #[test]
+    fn malformed_lines_point_at_their_line() {
+        // Before any section
+        assert!(matches!(
+            Level::parse(&format!("width = 4\n{LEVEL}")),
+            Err(LevelError::InvalidValue { line: 1, .. })
+        ));
+        assert!(matches!(
+            Level::parse(&with_line("[map]", "[maps]")),
+            Err(LevelError::InvalidValue { line: 17, .. })
+        ));
+        assert!(matches!(
+            Level::parse(&with_line("height = 3", "height 3")),
+            Err(LevelError::InvalidValue { line: 5, .. })
+        ));
+        assert!(matches!(
+            Level::parse(&with_line("height = 3", "height = -3")),
+            Err(LevelError::InvalidValue { line: 5, .. })
+        ));
+        assert!(matches!(
+            Level::parse(&with_line("win = length 12", "win = length")),
+            Err(LevelError::InvalidValue { line: 7, .. })
+        ));
+    }
+
+    #[test]
+    fn malformed_legend_entries() {
+        assert!(matches!(
+            Level::parse(&with_line("f = food", "ff = food")),
+            Err(LevelError::InvalidValue { line: 13, .. })
+        ));
+        assert!(matches!(
+            Level::parse(&with_line("f = food", "f = fruit")),
+            Err(LevelError::InvalidValue { line: 13, .. })
+        ));
+        assert!(matches!(
+            Level::parse(&with_line("f = food", "f = terrain z=high")),
+            Err(LevelError::InvalidValue { line: 13, .. })
+        ));
+        // The fill has to be terrain
+        assert!(matches!(
+            Level::parse(&with_line("fill = .", "fill = f")),
+            Err(LevelError::InvalidValue { line: 8, .. })
+        ));
+    }
+
+    #[test]
+    fn unknown_map_tiles() {
+        assert!(matches!(
+            Level::parse(&with_line(".@b.", ".@x.")),
+            Err(LevelError::UnknownTile {
+                line: 19,
+                symbol: 'x'
+            })
+        ));
+    }
+
+    #[test]
+    fn sizes_out_of_range() {
+        assert!(matches!(
+            Level::parse(&with_line("width = 4", "width = 65536")),
+            Err(LevelError::InvalidValue { line: 4, .. })
+        ));
+        assert!(matches!(
+            Level::parse(&with_line("height = 3", "height = 70000")),
+            Err(LevelError::InvalidValue { line: 5, .. })
+        ));
+    }
+
+    #[test]
+    fn map_larger_than_the_level() {
+        assert!(matches!(
+            Level::parse(&with_line(".@b.", ".@b..")),
+            Err(LevelError::InvalidValue { line: 19, .. })
+        ));
+        assert!(matches!(
+            Level::parse(&with_line(".@b.", ".@b.\n....\n....")),
+            Err(LevelError::InvalidValue { line: 21, .. })
+        ));
+        // Multi-byte tiles count as one column
+        let wide = with_line("f = food", "é = food").replace("#f.#", "#é.#");
+        assert!(Level::parse(&wide).is_ok());
+    }
+
+    #[test]
+    fn invalid_colors() {
+        for color in ["#ff00f", "#ff00ff0", "#gg0000", "#aééb", "purple"] {
+            let line = format!("border = fg={color}");
+            assert!(
+                matches!(
+                    Level::parse(&with_line("border = fg=#ff00ff symbol=#", &line)),
+                    Err(LevelError::InvalidValue { line: 6, .. })
+                ),
+                "{color}"
+            );
+        }
+    }
+}
@@ -0,0 +1,103 @@
+use crossterm::event::{self, Event, KeyCode};
+use crossterm::style::Color;
+use engine::prelude::*;
+use std::time::Duration;
+
+use crate::StageKey;
+
+const LOADING_POS: Position = Position { x: 6, y: 4 };
+const BAR_WIDTH: u16 = 27;
+const TITLE_COLOR: Color = Color::Rgb {
+    r: 175,
+    g: 200,
+    b: 200,
+};
+const BAR_COLOR: Color = Color::Rgb {
+    r: 120,
+    g: 200,
+    b: 120,
+};
+
+/// Renders the progress of the load in the resources and pops once the scene is ready
+pub struct LoadingLogic {
+    bar: Option<Handle<ProgressBar>>,
+}
+
+impl LoadingLogic {
+    pub fn new() -> Self {
+        Self { bar: None }
+    }
+
+    fn handle_input(&mut self, status: Option<&LoadStatus>) -> Option<RuntimeCommand<StageKey>> {
+        while event::poll(Duration::from_millis(0)).unwrap_or(false) {
+            let Ok(Event::Key(key_event)) = event::read() else {
+                continue;
+            };
+
+            if key_event.is_press() && key_event.code == KeyCode::Esc {
+                if let Some(status) = status {
+                    status.cancel();
+                }
+                return Some(RuntimeCommand::PopStage);
+            }
+        }
+        None
+    }
+
+    fn update_bar(&self, scene: &mut Scene, progress: f32) {
+        if let Some(bar) = self.bar.and_then(|handle| handle.get_mut(&mut scene.objects)) {
+            bar.set_progress(progress);
+        }
+    }
+}
+
+impl Logic<StageKey> for LoadingLogic {
+    fn init(&mut self, scene: &mut Scene) {
+        let Position { x, y } = LOADING_POS;
+        scene.attach_typed(
+            |id| {
+                Label::new(id, Rect::new(x, y, BAR_WIDTH, 1), ":::[LOADING]:::")
+                    .style(Style::fg(TITLE_COLOR))
+            },
+            Conflict::Ignore,
+        );
+        self.bar = scene.attach_typed(
+            |id| {
+                ProgressBar::new(id, Rect::new(x, y + 1, BAR_WIDTH, 1))
+                    .styles(Style::fg(BAR_COLOR), Style::fg(BAR_COLOR))
+                    .show_percent(true)
+            },
+            Conflict::Ignore,
+        );
+        scene.attach_typed(
+            |id| Label::new(id, Rect::new(x, y + 2, BAR_WIDTH, 1), "Esc: Cancel"),
+            Conflict::Ignore,
+        );
+    }
+
+    fn refresh(&mut self, scene: &mut Scene) {
+        self.update_bar(scene, 0.0);
+    }
+
+    fn update(&mut self, scene: &mut Scene) -> RuntimeCommand<StageKey> {
+        let status = scene.resources.get::<LoadStatus>().cloned();
+        if let Some(command) = self.handle_input(status.as_ref()) {
+            return command;
+        }
+
+        // Without a load there is nothing to wait for
+        let Some(status) = status else {
+            return RuntimeCommand::PopStage;
+        };
+
+        self.update_bar(scene, status.progress());
+        if status.is_finished() {
+            return RuntimeCommand::PopStage;
+        }
+        RuntimeCommand::None
+    }
+
+    fn dispatch_events(&mut self, scene: &mut Scene) {
+        scene.event_bus.clear();
+    }
+}
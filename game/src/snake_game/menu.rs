@@ -0,0 +1,229 @@
+use crossterm::event::{self, Event, KeyCode};
+use crossterm::style::Color;
+use engine::prelude::*;
+use std::time::Duration;
+
+use crate::StageKey;
+
+const TITLE_COLOR: Color = Color::Rgb {
+    r: 175,
+    g: 200,
+    b: 200,
+};
+const TEXT_COLOR: Color = Color::Rgb {
+    r: 255,
+    g: 255,
+    b: 255,
+};
+const PANEL_COLOR: Color = Color::Rgb {
+    r: 30,
+    g: 30,
+    b: 40,
+};
+
+/// Left in the resources by a menu for the level below it, which acts on it once resumed
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MenuRequest {
+    Restart,
+    QuitToTitle,
+}
+
+/// How the last run of a level ended, shown by the game over screen
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RunSummary {
+    pub stage: StageKey,
+    pub score: u16,
+    pub length: usize,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MenuInput {
+    Select(usize),
+    /// Left or right on an item, for options with values
+    Change(usize, bool),
+    Back,
+    Redraw,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum MenuPanel {
+    Frame,
+    Text,
+    Items,
+}
+
+/// A bordered menu in the middle of the screen with some text above its items,
+/// shared by the menu stages
+pub struct MenuScreen {
+    title: String,
+    text: Vec<String>,
+    items: Vec<String>,
+    border: Option<Handle<Border>>,
+    text_block: Option<Handle<TextBlock>>,
+    menu: Option<Handle<Menu>>,
+    layout: Layout<MenuPanel>,
+}
+
+impl MenuScreen {
+    pub fn new(title: &str, items: Vec<String>) -> Self {
+        let mut screen = Self {
+            title: title.to_string(),
+            text: Vec::new(),
+            items,
+            border: None,
+            text_block: None,
+            menu: None,
+            layout: Layout::from_terminal(),
+        };
+        screen.update_layout();
+        screen
+    }
+
+    pub fn attach(&mut self, scene: &mut Scene) {
+        let panel = Style::new(Some(TEXT_COLOR), Some(PANEL_COLOR));
+        let title = self.title.clone();
+        self.border = scene.attach_typed(
+            |id| {
+                Border::new(id, Rect::default())
+                    .title(title)
+                    .style(Style::new(Some(TITLE_COLOR), Some(PANEL_COLOR)))
+                    .fill(Style::new(None, Some(PANEL_COLOR)))
+            },
+            Conflict::Ignore,
+        );
+        self.text_block =
+            scene.attach_typed(|id| TextBlock::new(id, Rect::default()), Conflict::Ignore);
+        let items = self.items.clone();
+        self.menu = scene.attach_typed(
+            |id| Menu::new(id, Rect::default(), items).styles(panel, panel.inverted()),
+            Conflict::Ignore,
+        );
+
+        if let Some(handle) = self.border {
+            self.layout.bind(MenuPanel::Frame, handle);
+        }
+        if let Some(handle) = self.text_block {
+            self.layout.bind(MenuPanel::Text, handle);
+        }
+        if let Some(handle) = self.menu {
+            self.layout.bind(MenuPanel::Items, handle);
+        }
+        let text = std::mem::take(&mut self.text);
+        self.set_text(scene, text);
+    }
+
+    pub fn set_text(&mut self, scene: &mut Scene, text: Vec<String>) {
+        let style = Style::new(Some(TEXT_COLOR), Some(PANEL_COLOR));
+        if let Some(text_block) = self
+            .text_block
+            .and_then(|handle| handle.get_mut(&mut scene.objects))
+        {
+            text_block.set_lines(text.iter().map(|line| (line.clone(), style)).collect());
+        }
+        self.text = text;
+        self.update_layout();
+        self.layout.fit_terminal();
+        self.layout.apply(scene);
+    }
+
+    pub fn set_items(&mut self, scene: &mut Scene, items: Vec<String>) {
+        if let Some(menu) = self
+            .menu
+            .and_then(|handle| handle.get_mut(&mut scene.objects))
+        {
+            menu.set_items(items.clone());
+        }
+        self.items = items;
+        self.update_layout();
+        self.layout.apply(scene);
+    }
+
+    pub fn select(&self, scene: &mut Scene, index: usize) {
+        if let Some(menu) = self
+            .menu
+            .and_then(|handle| handle.get_mut(&mut scene.objects))
+        {
+            menu.select(index);
+        }
+    }
+
+    /// Moves the selection, everything else is left to the stage
+    pub fn handle_input(&mut self, scene: &mut Scene) -> Option<MenuInput> {
+        while event::poll(Duration::from_millis(0)).unwrap_or(false) {
+            let key_event = match event::read() {
+                Ok(Event::Key(key_event)) => key_event,
+                Ok(Event::Resize(width, height)) => {
+                    self.layout.resize(width, height);
+                    self.layout.apply(scene);
+                    return Some(MenuInput::Redraw);
+                }
+                _ => continue,
+            };
+
+            if !key_event.is_press() {
+                continue;
+            }
+
+            let menu = self
+                .menu
+                .and_then(|handle| handle.get_mut(&mut scene.objects))?;
+            let selected = menu.selected()?;
+            match key_event.code {
+                KeyCode::Up | KeyCode::Char('w') => menu.select_prev(),
+                KeyCode::Down | KeyCode::Char('s') => menu.select_next(),
+                KeyCode::Left | KeyCode::Char('a') => {
+                    return Some(MenuInput::Change(selected, false));
+                }
+                KeyCode::Right | KeyCode::Char('d') => {
+                    return Some(MenuInput::Change(selected, true));
+                }
+                KeyCode::Enter | KeyCode::Char(' ') => return Some(MenuInput::Select(selected)),
+                KeyCode::Esc => return Some(MenuInput::Back),
+                _ => {}
+            }
+        }
+        None
+    }
+
+    // The frame grows with its content, the text sits above the items
+    fn update_layout(&mut self) {
+        // Items are indented by the selection marker
+        let text_width = self.text.iter().map(|line| line.chars().count());
+        let items_width = self.items.iter().map(|item| item.chars().count() + 2);
+        let content_width = text_width
+            .chain(items_width)
+            .chain([self.title.chars().count() + 4])
+            .max()
+            .unwrap_or(0) as u16;
+        let text_height = self.text.len() as u16;
+        let items_height = self.items.len() as u16;
+        let spacer = u16::from(text_height > 0);
+
+        self.layout.insert(
+            MenuPanel::Frame,
+            Node::new(
+                Anchor::Screen(Align::Center),
+                Size::Fixed(content_width + 4),
+                Size::Fixed(text_height + spacer + items_height + 2),
+            ),
+        );
+        self.layout.insert(
+            MenuPanel::Text,
+            Node::new(
+                Anchor::Within(MenuPanel::Frame, Align::Top),
+                Size::Fixed(content_width),
+                Size::Fixed(text_height),
+            )
+            .gap(1),
+        );
+        self.layout.insert(
+            MenuPanel::Items,
+            Node::new(
+                Anchor::Below(MenuPanel::Text),
+                Size::Fixed(content_width),
+                Size::Fixed(items_height),
+            )
+            .gap(spacer),
+        );
+    }
+}
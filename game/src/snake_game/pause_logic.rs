@@ -0,0 +1,60 @@
+use engine::prelude::*;
+
+use super::menu::{MenuInput, MenuRequest, MenuScreen};
+use crate::StageKey;
+
+const RESUME: usize = 0;
+const RESTART: usize = 1;
+const SETTINGS: usize = 2;
+const QUIT: usize = 3;
+
+/// Pushed on top of a running level, which stays frozen underneath until it's popped again
+pub struct PauseLogic {
+    screen: MenuScreen,
+}
+
+impl PauseLogic {
+    pub fn new() -> Self {
+        let items = vec![
+            "Resume".to_string(),
+            "Restart".to_string(),
+            "Settings".to_string(),
+            "Quit to Title".to_string(),
+        ];
+
+        Self {
+            screen: MenuScreen::new("PAUSED", items),
+        }
+    }
+
+    // The level below picks the request up once it's resumed
+    fn pop_with(scene: &mut Scene, request: MenuRequest) -> RuntimeCommand<StageKey> {
+        scene.resources.insert(request);
+        RuntimeCommand::PopStage
+    }
+}
+
+impl Logic<StageKey> for PauseLogic {
+    fn init(&mut self, scene: &mut Scene) {
+        self.screen.attach(scene);
+    }
+
+    fn on_enter(&mut self, scene: &mut Scene) {
+        self.screen.select(scene, RESUME);
+    }
+
+    fn update(&mut self, scene: &mut Scene) -> RuntimeCommand<StageKey> {
+        match self.screen.handle_input(scene) {
+            Some(MenuInput::Select(RESUME)) | Some(MenuInput::Back) => RuntimeCommand::PopStage,
+            Some(MenuInput::Select(RESTART)) => Self::pop_with(scene, MenuRequest::Restart),
+            Some(MenuInput::Select(SETTINGS)) => RuntimeCommand::PushStage(StageKey::Settings),
+            Some(MenuInput::Select(QUIT)) => Self::pop_with(scene, MenuRequest::QuitToTitle),
+            Some(MenuInput::Redraw) => RuntimeCommand::Redraw,
+            Some(MenuInput::Select(_)) | Some(MenuInput::Change(..)) | None => RuntimeCommand::None,
+        }
+    }
+
+    fn dispatch_events(&mut self, scene: &mut Scene) {
+        scene.event_bus.clear();
+    }
+}
@@ -1,11 +1,13 @@
-use ::engine::core::global::Id;
+use ::engine::prelude::Handle;
 use uuid::Uuid;
 
+use super::game_objects::Snake;
+
 #[derive(PartialEq, Eq, Clone, Copy)]
 pub struct Player {
     pub id: Uuid,
     pub score: u16,
-    pub snake: Option<Id>,
+    pub snake: Option<Handle<Snake>>,
 }
 
 impl Player {
@@ -17,7 +19,7 @@ impl Player {
         }
     }
 
-    pub fn set_snake(&mut self, snake_id: Id) {
-        self.snake = Some(snake_id)
+    pub fn set_snake(&mut self, snake: Handle<Snake>) {
+        self.snake = Some(snake)
     }
 }
@@ -0,0 +1,35 @@
+use engine::prelude::Persistent;
+
+/// Totals over every stage and session, saved as `key = value` lines
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct Scores {
+    pub best_length: usize,
+    pub levels_won: u64,
+    pub deaths: u64,
+}
+
+impl Persistent for Scores {
+    const KEY: &'static str = "scores";
+
+    fn save(&self) -> String {
+        format!(
+            "best_length = {}\nlevels_won = {}\ndeaths = {}\n",
+            self.best_length, self.levels_won, self.deaths
+        )
+    }
+
+    fn load(source: &str) -> Option<Self> {
+        let mut scores = Scores::default();
+        for line in source.lines().filter(|line| !line.trim().is_empty()) {
+            let (key, value) = line.split_once('=')?;
+            let value = value.trim();
+            match key.trim() {
+                "best_length" => scores.best_length = value.parse().ok()?,
+                "levels_won" => scores.levels_won = value.parse().ok()?,
+                "deaths" => scores.deaths = value.parse().ok()?,
+                _ => return None,
+            }
+        }
+        Some(scores)
+    }
+}
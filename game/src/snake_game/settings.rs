@@ -0,0 +1,183 @@
+use crossterm::style::Color;
+use engine::prelude::Persistent;
+
+use super::game_objects::snake::Direction;
+
+const MIN_SPEED: u16 = 50;
+const MAX_SPEED: u16 = 200;
+const SPEED_STEP: u16 = 25;
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum SnakeColor {
+    #[default]
+    Purple,
+    Green,
+    Orange,
+    Cyan,
+}
+
+impl SnakeColor {
+    const ALL: [SnakeColor; 4] = [
+        SnakeColor::Purple,
+        SnakeColor::Green,
+        SnakeColor::Orange,
+        SnakeColor::Cyan,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            SnakeColor::Purple => "Purple",
+            SnakeColor::Green => "Green",
+            SnakeColor::Orange => "Orange",
+            SnakeColor::Cyan => "Cyan",
+        }
+    }
+
+    /// Colours of the head and the body
+    pub fn colors(&self) -> (Color, Color) {
+        let rgb = |r, g, b| Color::Rgb { r, g, b };
+        match self {
+            SnakeColor::Purple => (rgb(255, 0, 255), rgb(138, 43, 226)),
+            SnakeColor::Green => (rgb(120, 255, 120), rgb(40, 160, 60)),
+            SnakeColor::Orange => (rgb(255, 200, 80), rgb(230, 120, 30)),
+            SnakeColor::Cyan => (rgb(120, 255, 255), rgb(30, 150, 190)),
+        }
+    }
+}
+
+/// Keys steering the player snake, the camera stays on `W`,`A`,`S`,`D`
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum Controls {
+    #[default]
+    Wasd,
+    Ijkl,
+    Vim,
+}
+
+impl Controls {
+    const ALL: [Controls; 3] = [Controls::Wasd, Controls::Ijkl, Controls::Vim];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Controls::Wasd => "w,a,s,d",
+            Controls::Ijkl => "i,j,k,l",
+            Controls::Vim => "h,j,k,l",
+        }
+    }
+
+    pub fn direction(&self, key: char) -> Option<Direction> {
+        let [up, left, down, right] = match self {
+            Controls::Wasd => ['w', 'a', 's', 'd'],
+            Controls::Ijkl => ['i', 'j', 'k', 'l'],
+            Controls::Vim => ['k', 'h', 'j', 'l'],
+        };
+
+        match key {
+            key if key == up => Some(Direction::Up),
+            key if key == left => Some(Direction::Left),
+            key if key == down => Some(Direction::Down),
+            key if key == right => Some(Direction::Right),
+            _ => None,
+        }
+    }
+}
+
+/// Chosen in the settings menu, saved as `key = value` lines
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Settings {
+    /// Percentage of the level's speed
+    pub speed: u16,
+    pub snake_color: SnakeColor,
+    pub controls: Controls,
+    /// Ticks the level on a thread pool, only with the `parallel` feature
+    pub parallel: bool,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            speed: 100,
+            snake_color: SnakeColor::default(),
+            controls: Controls::default(),
+            parallel: false,
+        }
+    }
+}
+
+impl Settings {
+    /// Tick rate of a level at the chosen speed
+    pub fn tick_rate(&self, level_tick_rate: u64) -> u64 {
+        level_tick_rate * 100 / self.speed.clamp(MIN_SPEED, MAX_SPEED) as u64
+    }
+
+    pub fn step_speed(&mut self, forward: bool) {
+        self.speed = match forward {
+            true => self.speed.saturating_add(SPEED_STEP),
+            false => self.speed.saturating_sub(SPEED_STEP),
+        }
+        .clamp(MIN_SPEED, MAX_SPEED);
+    }
+
+    pub fn step_color(&mut self, forward: bool) {
+        self.snake_color = step(&SnakeColor::ALL, self.snake_color, forward);
+    }
+
+    pub fn step_controls(&mut self, forward: bool) {
+        self.controls = step(&Controls::ALL, self.controls, forward);
+    }
+
+    /// Stays off in builds without the `parallel` feature
+    pub fn toggle_parallel(&mut self) {
+        self.parallel = cfg!(feature = "parallel") && !self.parallel;
+    }
+}
+
+// The next or previous option, wrapping around
+fn step<T: Copy + PartialEq>(options: &[T], current: T, forward: bool) -> T {
+    let index = options
+        .iter()
+        .position(|option| *option == current)
+        .unwrap_or(0);
+    let len = options.len();
+    match forward {
+        true => options[(index + 1) % len],
+        false => options[(index + len - 1) % len],
+    }
+}
+
+impl Persistent for Settings {
+    const KEY: &'static str = "settings";
+
+    fn save(&self) -> String {
+        format!(
+            "speed = {}\nsnake_color = {}\ncontrols = {}\nparallel = {}\n",
+            self.speed,
+            self.snake_color.name(),
+            self.controls.name(),
+            self.parallel
+        )
+    }
+
+    fn load(source: &str) -> Option<Self> {
+        let mut settings = Settings::default();
+        for line in source.lines().filter(|line| !line.trim().is_empty()) {
+            let (key, value) = line.split_once('=')?;
+            let value = value.trim();
+            match key.trim() {
+                "speed" => settings.speed = value.parse::<u16>().ok()?.clamp(MIN_SPEED, MAX_SPEED),
+                "snake_color" => {
+                    settings.snake_color =
+                        *SnakeColor::ALL.iter().find(|color| color.name() == value)?
+                }
+                "controls" => {
+                    settings.controls = *Controls::ALL
+                        .iter()
+                        .find(|controls| controls.name() == value)?
+                }
+                "parallel" => settings.parallel = value.parse().ok()?,
+                _ => return None,
+            }
+        }
+        Some(settings)
+    }
+}
@@ -0,0 +1,92 @@
+use engine::prelude::*;
+
+use super::menu::{MenuInput, MenuScreen};
+use super::settings::Settings;
+use crate::StageKey;
+
+const SPEED: usize = 0;
+const SNAKE_COLOR: usize = 1;
+const CONTROLS: usize = 2;
+const PARALLEL: usize = 3;
+const BACK: usize = 4;
+
+/// Pushed from the title or the pause menu, changes the `Settings` in the resources
+pub struct SettingsLogic {
+    screen: MenuScreen,
+}
+
+impl SettingsLogic {
+    pub fn new() -> Self {
+        Self {
+            screen: MenuScreen::new("SETTINGS", items(&Settings::default())),
+        }
+    }
+
+    fn update_items(&mut self, scene: &mut Scene) {
+        let settings = *scene.resources.get_or_insert_with(Settings::default);
+        self.screen.set_items(scene, items(&settings));
+    }
+
+    fn change(&mut self, scene: &mut Scene, index: usize, forward: bool) {
+        let settings = scene.resources.get_or_insert_with(Settings::default);
+        match index {
+            SPEED => settings.step_speed(forward),
+            SNAKE_COLOR => settings.step_color(forward),
+            CONTROLS => settings.step_controls(forward),
+            PARALLEL => settings.toggle_parallel(),
+            _ => return,
+        }
+        self.update_items(scene);
+    }
+}
+
+fn items(settings: &Settings) -> Vec<String> {
+    vec![
+        format!("Speed:     < {:>3}% >", settings.speed),
+        format!("Colour:    < {} >", settings.snake_color.name()),
+        format!("Controls:  < {} >", settings.controls.name()),
+        format!(
+            "Threads:   < {} >",
+            match (cfg!(feature = "parallel"), settings.parallel) {
+                (false, _) => "n/a",
+                (true, true) => "on",
+                (true, false) => "off",
+            }
+        ),
+        "Back".to_string(),
+    ]
+}
+
+impl Logic<StageKey> for SettingsLogic {
+    fn init(&mut self, scene: &mut Scene) {
+        self.screen.attach(scene);
+        self.screen
+            .set_text(scene, vec!["Left & Right: Change".to_string()]);
+        self.update_items(scene);
+    }
+
+    fn refresh(&mut self, scene: &mut Scene) {
+        self.screen.select(scene, 0);
+        self.update_items(scene);
+    }
+
+    fn update(&mut self, scene: &mut Scene) -> RuntimeCommand<StageKey> {
+        match self.screen.handle_input(scene) {
+            Some(MenuInput::Select(BACK)) | Some(MenuInput::Back) => RuntimeCommand::PopStage,
+            Some(MenuInput::Select(index)) => {
+                self.change(scene, index, true);
+                RuntimeCommand::None
+            }
+            Some(MenuInput::Change(index, forward)) => {
+                self.change(scene, index, forward);
+                RuntimeCommand::None
+            }
+            Some(MenuInput::Redraw) => RuntimeCommand::Redraw,
+            None => RuntimeCommand::None,
+        }
+    }
+
+    fn dispatch_events(&mut self, scene: &mut Scene) {
+        scene.event_bus.clear();
+    }
+}
@@ -0,0 +1,69 @@
+use engine::prelude::*;
+
+use super::Scores;
+use super::menu::{MenuInput, MenuScreen};
+use crate::StageKey;
+
+/// The first stage, picks the level to play
+pub struct TitleLogic {
+    levels: Vec<(StageKey, String)>,
+    screen: MenuScreen,
+}
+
+impl TitleLogic {
+    pub fn new(levels: Vec<(StageKey, String)>) -> Self {
+        let items = levels
+            .iter()
+            .map(|(_, name)| format!("Play {}", name))
+            .chain(["Settings".to_string(), "Quit".to_string()])
+            .collect();
+
+        Self {
+            screen: MenuScreen::new("KLEIN GARTER", items),
+            levels,
+        }
+    }
+
+    fn update_text(&mut self, scene: &mut Scene) {
+        let scores = *scene.resources.get_or_insert_with(Scores::default);
+        self.screen.set_text(
+            scene,
+            vec![
+                "Eat, grow and don't bite anyone.".to_string(),
+                String::new(),
+                format!("Best Length: {}", scores.best_length),
+                format!("Levels Won: {}", scores.levels_won),
+            ],
+        );
+    }
+}
+
+impl Logic<StageKey> for TitleLogic {
+    fn init(&mut self, scene: &mut Scene) {
+        self.screen.attach(scene);
+        self.update_text(scene);
+    }
+
+    fn refresh(&mut self, scene: &mut Scene) {
+        self.update_text(scene);
+    }
+
+    fn update(&mut self, scene: &mut Scene) -> RuntimeCommand<StageKey> {
+        let settings_index = self.levels.len();
+        match self.screen.handle_input(scene) {
+            Some(MenuInput::Select(index)) if index < settings_index => {
+                RuntimeCommand::SwitchStage(self.levels[index].0)
+            }
+            Some(MenuInput::Select(index)) if index == settings_index => {
+                RuntimeCommand::PushStage(StageKey::Settings)
+            }
+            Some(MenuInput::Select(_)) | Some(MenuInput::Back) => RuntimeCommand::Kill,
+            Some(MenuInput::Redraw) => RuntimeCommand::Redraw,
+            Some(MenuInput::Change(..)) | None => RuntimeCommand::None,
+        }
+    }
+
+    fn dispatch_events(&mut self, scene: &mut Scene) {
+        scene.event_bus.clear();
+    }
+}
@@ -1,7 +0,0 @@
-pub mod info_panel;
-pub mod logger;
-pub mod statistics;
-
-pub use info_panel::InfoPanel;
-pub use logger::Logger;
-pub use statistics::Statistics;